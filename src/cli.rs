@@ -1,13 +1,28 @@
-use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(name = "lazarus", version, about = "CLI training app")]
 #[command(arg_required_else_help = true)]
 pub struct Cli {
-    /// Emit machine-readable JSON instead of colorful text.
+    /// Emit machine-readable JSON instead of colorful text. Shorthand for
+    /// `--format json`.
     #[arg(global = true, long)]
     pub json: bool,
 
+    /// Output format: `pretty`, `json`, `csv` (where the command supports
+    /// it), or `cbor` (a compact binary encoding for other tools to parse).
+    /// Overrides both `--json` and the `format` config key.
+    #[arg(global = true, long)]
+    pub format: Option<crate::types::OutputFormat>,
+
+    /// Dump `EXPLAIN QUERY PLAN` + timing for each query a command runs,
+    /// plus an end-of-run summary flagging statements repeated often enough
+    /// to be an N+1 pattern. Undocumented — a diagnostic, not a stable flag.
+    #[arg(global = true, long, hide = true)]
+    pub profile: bool,
+
     #[command(subcommand)]
     pub cmd: Commands,
 }
@@ -40,6 +55,40 @@ pub enum Commands {
         /// Month to show (1-12, defaults to current month)
         #[arg(short, long)]
         month: Option<u32>,
+
+        /// Color each day by training intensity instead of a flat marker
+        #[arg(long)]
+        heatmap: bool,
+
+        /// Metric the heatmap buckets days by
+        #[arg(long, value_enum, default_value = "sessions")]
+        metric: CalendarMetric,
+
+        /// Render a GitHub-style contribution grid for the whole year
+        /// instead of a single month
+        #[arg(long)]
+        full_year: bool,
+
+        /// Print this many consecutive months side by side, starting from
+        /// the selected month, instead of a single month
+        #[arg(long)]
+        months: Option<u32>,
+
+        /// Write the month's sessions to a file as HTML or iCal instead of
+        /// printing the terminal grid
+        #[arg(long, value_enum)]
+        export: Option<CalendarExportFormat>,
+
+        /// Destination path for `--export` (defaults to `calendar.html` or
+        /// `calendar.ics`)
+        #[arg(long)]
+        out: Option<PathBuf>,
+
+        /// Locale month/weekday names and week-start day are localized to
+        /// (e.g. "en", "de", "fr", "es", "pt"). Defaults to `LC_TIME` /
+        /// `LC_ALL` / `LANG`, falling back to English
+        #[arg(long)]
+        locale: Option<String>,
     },
 
     /// Show global progression and training status
@@ -55,11 +104,153 @@ pub enum Commands {
         /// Show graph instead of summary
         #[arg(short, long)]
         graph: bool,
+
+        /// Show a single Monday-anchored week report instead (0 = current week, -1 = last week, ...)
+        #[arg(short = 'W', long)]
+        week: Option<i32>,
+
+        /// Fit a personalized Epley-style 1RM coefficient from logged sets instead
+        #[arg(long)]
+        fit_1rm: bool,
+
+        /// Render a month-by-month ASCII calendar shaded by daily tonnage
+        /// instead of the usual progression report
+        #[arg(long)]
+        calendar: bool,
+
+        /// Render a week-by-week grid of daily training volume, shaded via
+        /// ANSI background blocks, instead of the --graph trend line
+        #[arg(long)]
+        heatmap: bool,
+
+        /// Plot this exercise's true PR progression — a monotonically
+        /// increasing series of estimated-1RM records, one point per set
+        /// that beat the prior best — instead of the usual report.
+        /// Requires --exercise.
+        #[arg(long)]
+        pr_history: bool,
+
+        /// Exercise to fit against (required with --fit-1rm)
+        #[arg(long)]
+        exercise: Option<String>,
+
+        /// Project this many weeks past the graph using Holt's linear
+        /// (double exponential) smoothing, requires --graph and at least
+        /// 3 weeks of data
+        #[arg(long)]
+        forecast: Option<u32>,
+
+        /// MAD multiplier above which a weekly value is flagged as an
+        /// outlier and excluded from graph scaling and trend comparisons
+        #[arg(long, default_value = "3.5")]
+        outlier_threshold: f64,
+
+        /// Time bucket to aggregate sets into before graphing and trend math
+        #[arg(long, value_enum, default_value = "week")]
+        granularity: Granularity,
+
+        /// Statistic used to collapse each bucket's raw tonnage/1RM values
+        /// into the single number that gets graphed and trend-compared
+        #[arg(long, value_enum, default_value = "mean")]
+        stat: Stat,
+
+        /// Shift the whole `--weeks` query window back this many weeks
+        /// (e.g. `--offset -3` reviews the block ending 3 weeks ago)
+        /// instead of always ending at today
+        #[arg(long, default_value = "0")]
+        offset: i32,
     },
 
     /// Db operations
     #[command(subcommand)]
     Db(DbCmd),
+
+    /// Body measurements (bodyweight, circumferences, ...)
+    #[command(subcommand, visible_alias = "m")]
+    Measure(MeasureCmd),
+
+    /// Ranked fuzzy search over exercises
+    #[command(trailing_var_arg = true)]
+    Search {
+        /// Search query, e.g. `lazaro search "incln bnch"`
+        query: Vec<String>,
+    },
+
+    /// Record and replay sequences of commands
+    #[command(subcommand, visible_alias = "mac")]
+    Macro(MacroCmd),
+
+    /// Named, reusable set-scheme presets (e.g. a "wave" over the day's top
+    /// set, or an RPE ramp), referenced from program exercises by name
+    #[command(subcommand)]
+    Preset(PresetCmd),
+
+    /// GitHub-style training consistency heatmap
+    Heatmap {
+        /// Number of weeks to show (defaults to 52)
+        #[arg(short, long, default_value = "52")]
+        weeks: u32,
+
+        /// Color ramp to render cells with
+        #[arg(short, long, value_enum, default_value = "green")]
+        ramp: HeatmapRamp,
+
+        /// Bucket by set count instead of tonnage
+        #[arg(long)]
+        by_sets: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum HeatmapRamp {
+    Green,
+    Red,
+}
+
+/// Per-day metric `calendar --heatmap` buckets intensity by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CalendarMetric {
+    /// Number of sessions trained that day
+    Sessions,
+    /// Total session duration in minutes
+    Minutes,
+    /// Total tonnage (sum of weight × reps) logged that day
+    Volume,
+}
+
+/// File format `calendar --export` writes the month's sessions to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CalendarExportFormat {
+    /// An HTML table, one cell per day, colored by program
+    Html,
+    /// An iCal (.ics) file with one VEVENT per session
+    Ics,
+}
+
+/// Time bucket `status` aggregates sets into before graphing and trend math.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Granularity {
+    /// One bucket per calendar day
+    Day,
+    /// One bucket per Monday-anchored week (the default)
+    Week,
+    /// One bucket per calendar month
+    Month,
+}
+
+/// Statistic `status` uses to collapse a bucket's raw values into one point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Stat {
+    /// Arithmetic mean (the default, matches prior SUM/AVG-derived behavior)
+    Mean,
+    /// Middle value of the sorted bucket (resists one-off spikes/deloads)
+    Median,
+    /// 90th percentile, interpolated between the two nearest ranks
+    P90,
+    /// Mean after dropping the top and bottom 10% of values
+    Trimmed,
+    /// Population standard deviation, surfacing within-bucket consistency
+    Stddev,
 }
 
 //
@@ -78,7 +269,11 @@ pub enum SessionCmd {
 
     /// Show current session details
     #[command(visible_alias = "i")]
-    Show,
+    Show {
+        /// Also print each exercise's recent-session top-set/1RM history
+        #[arg(long, short = 'H')]
+        history: bool,
+    },
 
     /// End the current session
     // #[command(visible_alias = "e")]
@@ -100,6 +295,11 @@ pub enum SessionCmd {
         #[arg(value_name = "REPS")]
         reps: i32,
 
+        /// Rate of perceived exertion for this set (0-10), used to
+        /// autoregulate the remaining sets' target weights in `session show`
+        #[arg(long)]
+        rpe: Option<f32>,
+
         /// Specific set index to edit (defaults to next unlogged set)
         #[arg(long, short = 's')]
         set: Option<usize>,
@@ -109,6 +309,21 @@ pub enum SessionCmd {
         new: bool,
     },
 
+    /// Soft-delete a logged set, undoing it without losing the row entirely,
+    /// and recompute the exercise's PR from what's left
+    UndoSet {
+        /// Exercise index (same order shown in `session show`)
+        exercise: usize,
+
+        /// 1-based set number to undo (defaults to the last logged set)
+        #[arg(long, short = 's')]
+        set: Option<usize>,
+    },
+
+    /// Un-end the most recently completed session, making it active again,
+    /// and recompute any PRs it may have prematurely produced
+    Reopen,
+
     /// Swap an exercise in the current session with another - Usage: session swap EXERCISE NEW_EXERCISE
     #[command(visible_alias = "sw")]
     Swap {
@@ -136,12 +351,73 @@ pub enum SessionCmd {
         note: String,
     },
 
+    /// Show the most recently completed session (or the active one, if any)
+    Last,
+
     /// Show details of a completed session from a specific date
     Log {
         /// Date in DD-MM-YYYY format
         #[arg(short, long)]
         date: String,
     },
+
+    /// Show the append-only audit trail of session mutations
+    History {
+        /// Restrict to one exercise's index (same order shown in `session show`)
+        #[arg(short, long)]
+        exercise: Option<usize>,
+    },
+
+    /// Show one exercise's top set and estimated-1RM trend across recent
+    /// completed sessions, with a tiny sparkline
+    Trend {
+        /// Exercise index (same order shown in `session show`)
+        exercise: usize,
+
+        /// How many recent sessions to include
+        #[arg(short = 'n', long, default_value_t = 5)]
+        sessions: i64,
+    },
+
+    /// Browse finalized sessions, newest first
+    #[command(visible_alias = "l")]
+    List {
+        /// Only sessions on or before this date (YYYY-MM-DD)
+        #[arg(long)]
+        before: Option<String>,
+
+        /// Only sessions on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        after: Option<String>,
+
+        /// Only sessions for this program
+        #[arg(long)]
+        program: Option<String>,
+
+        /// Only sessions for this program block
+        #[arg(long)]
+        block: Option<String>,
+
+        /// Only sessions that logged this exercise
+        #[arg(long)]
+        exercise: Option<String>,
+
+        /// Only sessions lasting at least this many minutes
+        #[arg(long)]
+        min_duration: Option<i64>,
+
+        /// Max rows to return
+        #[arg(long, default_value_t = 20)]
+        limit: i64,
+
+        /// Rows to skip, for paging past `limit`
+        #[arg(long, default_value_t = 0)]
+        offset: i64,
+
+        /// Oldest first instead of newest first
+        #[arg(long)]
+        reverse: bool,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -161,11 +437,22 @@ pub enum ExerciseCmd {
         desc: Option<String>,
     },
 
+    /// Pull a shared exercise library (same TOML shape as `import`) from a
+    /// URL or local path, tracking last_sync per named source
+    Sync {
+        /// HTTP(S) URL or local path to a TOML exercise library
+        source: String,
+    },
+
     /// Import exercises from a TOML file
     #[command(visible_alias = "i")]
     Import {
         /// Path to TOML file
         file: String,
+
+        /// Validate and print the summary without writing to the database
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// List all exercises
@@ -194,6 +481,29 @@ pub enum ExerciseCmd {
         /// Show progression graph
         #[arg(short, long)]
         graph: bool,
+
+        /// Override the configured weight unit for this invocation
+        #[arg(short, long)]
+        unit: Option<crate::types::WeightUnit>,
+
+        /// Override the configured 1RM estimator for this invocation
+        #[arg(short = 'f', long)]
+        formula: Option<crate::types::OneRmFormula>,
+    },
+
+    /// Show the full record-breaking timeline for an exercise
+    #[command(visible_alias = "rec", trailing_var_arg = true)]
+    Records {
+        /// Exercise index or name
+        exercise: Vec<String>,
+    },
+
+    /// Show an exercise's time-decayed strength rating (R ± 2·RD) and a
+    /// short-term trend arrow
+    #[command(visible_alias = "rt", trailing_var_arg = true)]
+    Rating {
+        /// Exercise index or name
+        exercise: Vec<String>,
     },
 }
 
@@ -210,6 +520,11 @@ pub enum ConfigCmd {
 
     /// Remove a key
     Unset { key: String },
+
+    /// Shorthand for `config set weight_unit <kg|lb>`
+    Unit {
+        unit: crate::types::WeightUnit,
+    },
 }
 
 #[derive(Subcommand)]
@@ -235,6 +550,141 @@ pub enum ProgramCmd {
         /// Program index (from `p list`) or exact name
         program: String,
     },
+
+    /// Export a program back to the TOML format `program import` reads, for
+    /// editing or sharing
+    #[command(visible_alias = "e")]
+    Export {
+        /// Program index (from `p list`) or exact name
+        program: String,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Planned training-load report — sets, tonnage, and average target
+    /// intensity per block and for the whole program
+    #[command(visible_alias = "r")]
+    Report {
+        /// Program index (from `p list`) or exact name
+        program: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum MeasureCmd {
+    /// Log a measurement - Usage: measure add bodyweight 82.5
+    #[command(visible_alias = "a")]
+    Add {
+        /// What was measured (e.g. "bodyweight", "waist", "arm")
+        kind: String,
+
+        /// Value, in the unit given by `--unit`
+        value: f32,
+
+        /// Unit the value was entered in (defaults to the configured weight
+        /// unit for "bodyweight", "cm" for everything else)
+        #[arg(short, long)]
+        unit: Option<String>,
+
+        /// Free-form note (e.g. "after cut", "morning, fasted")
+        #[arg(short, long)]
+        notes: Option<String>,
+    },
+
+    /// List logged measurements, most recent first
+    #[command(visible_alias = "l")]
+    List {
+        /// Filter by kind
+        kind: Option<String>,
+
+        /// Max entries to show
+        #[arg(short, long, default_value = "20")]
+        limit: i64,
+    },
+
+    /// Print a chronological table of one measurement kind with deltas
+    /// between consecutive entries - Usage: measure log bodyweight
+    Log {
+        /// What was measured (e.g. "bodyweight", "waist", "arm")
+        kind: String,
+
+        /// Max entries to show, oldest first
+        #[arg(short, long, default_value = "20")]
+        limit: i64,
+    },
+
+    /// Shorthand for `measure add bodyweight <value>` — the entry that bodyweight
+    /// exercises' 1RM tracking autoregulates off of
+    Bodyweight {
+        /// Value in the configured weight unit
+        value: f32,
+
+        /// Free-form note (e.g. "after cut", "morning, fasted")
+        #[arg(short, long)]
+        notes: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PresetCmd {
+    /// Define a new preset - Usage: preset add wave percent 100:1,90:2,95:1
+    #[command(visible_alias = "a")]
+    Add {
+        /// Preset name, referenced from a program TOML's `preset` field
+        name: String,
+
+        /// "percent" (proportions of the day's top set) or "rpe" (RPE ramp)
+        kind: PresetKind,
+
+        /// Comma-separated "value:sets" pairs, e.g. "100:1,90:2,95:1"
+        scheme: String,
+    },
+
+    /// List defined presets
+    #[command(visible_alias = "l")]
+    List,
+
+    /// Show a preset's expanded per-set values
+    #[command(visible_alias = "s")]
+    Show {
+        /// Preset name
+        name: String,
+    },
+
+    /// Delete a preset
+    #[command(visible_alias = "d")]
+    Delete {
+        /// Preset name
+        name: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum PresetKind {
+    Percent,
+    Rpe,
+}
+
+#[derive(Subcommand)]
+pub enum MacroCmd {
+    /// Start recording every subsequent command into NAME (until `macro stop`)
+    Record { name: String },
+
+    /// Stop the in-progress recording
+    Stop,
+
+    /// Replay every step of a recorded macro, in order
+    Run { name: String },
+
+    /// List recorded macros
+    #[command(visible_alias = "l")]
+    List,
+
+    /// Delete a recorded macro
+    #[command(visible_alias = "d")]
+    Delete { name: String },
 }
 
 #[derive(Args)]
@@ -257,6 +707,25 @@ pub enum DbCmd {
     Import {
         /// Input TOML file path
         file: String,
+
+        /// Size of the sliding time window (in hours) used to bound the
+        /// duplicate-set lookback when merging sets from multiple devices/backups
+        #[arg(long, default_value = "24")]
+        window_hours: i64,
+
+        /// Merge with local data instead of blindly replacing: a row is only
+        /// overwritten when the incoming `last_updated` is strictly newer
+        /// than the local one, rows missing locally are inserted, and newer
+        /// local rows are left untouched. This applies to exercises, program
+        /// exercises, sessions and sets; programs, blocks, session exercises,
+        /// personal records and measurements are written as in a plain import
+        #[arg(long)]
+        merge: bool,
+
+        /// Validate the file (checksum + referential sanity) and print the
+        /// results without writing to the database
+        #[arg(long)]
+        check: bool,
     },
 
     /// Migrate an *old* lazaro.db into the current one
@@ -264,4 +733,15 @@ pub enum DbCmd {
         /// path to the old lazaro.db (source)
         old_db: String,
     },
+
+    /// Export training metrics as InfluxDB line protocol (for Grafana/InfluxDB)
+    ExportMetrics {
+        /// Output file path (defaults to metrics.line)
+        #[arg(short, long)]
+        file: Option<String>,
+
+        /// Only include sets logged at or after this RFC3339 timestamp
+        #[arg(long)]
+        since: Option<String>,
+    },
 }
@@ -1,12 +1,455 @@
+use std::collections::HashMap;
+
 use anyhow::Result;
 use chrono::{Datelike, NaiveDate, DateTime, Utc};
 use colored::Colorize;
 use sqlx::SqlitePool;
 
-pub async fn handle(pool: &SqlitePool, year: Option<i32>, month: Option<u32>) -> Result<()> {
+use crate::cli::{CalendarExportFormat, CalendarMetric};
+
+/// (id, start_time, end_time, notes, program_name, block_name) — the shape
+/// `handle`'s session query returns, everywhere left as a raw tuple.
+type SessionRow = (String, String, Option<String>, Option<String>, String, String);
+
+/// Weekday abbreviations (in week-start order) and full month names for a
+/// locale, plus whether its week starts on Monday rather than Sunday. A
+/// small built-in table, since chrono's locale-aware formatting needs the
+/// `unstable-locales` feature this crate doesn't enable.
+struct LocaleNames {
+    weekdays: [&'static str; 7],
+    months: [&'static str; 12],
+    monday_start: bool,
+}
+
+const EN: LocaleNames = LocaleNames {
+    weekdays: ["Su", "Mo", "Tu", "We", "Th", "Fr", "Sa"],
+    months: [
+        "January", "February", "March", "April", "May", "June", "July", "August", "September", "October",
+        "November", "December",
+    ],
+    monday_start: false,
+};
+
+const DE: LocaleNames = LocaleNames {
+    weekdays: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+    months: [
+        "Januar", "Februar", "März", "April", "Mai", "Juni", "Juli", "August", "September", "Oktober", "November",
+        "Dezember",
+    ],
+    monday_start: true,
+};
+
+const FR: LocaleNames = LocaleNames {
+    weekdays: ["Lu", "Ma", "Me", "Je", "Ve", "Sa", "Di"],
+    months: [
+        "janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août", "septembre", "octobre", "novembre",
+        "décembre",
+    ],
+    monday_start: true,
+};
+
+const ES: LocaleNames = LocaleNames {
+    weekdays: ["Lu", "Ma", "Mi", "Ju", "Vi", "Sa", "Do"],
+    months: [
+        "enero", "febrero", "marzo", "abril", "mayo", "junio", "julio", "agosto", "septiembre", "octubre",
+        "noviembre", "diciembre",
+    ],
+    monday_start: true,
+};
+
+const PT: LocaleNames = LocaleNames {
+    weekdays: ["Seg", "Ter", "Qua", "Qui", "Sex", "Sáb", "Dom"],
+    months: [
+        "janeiro", "fevereiro", "março", "abril", "maio", "junho", "julho", "agosto", "setembro", "outubro",
+        "novembro", "dezembro",
+    ],
+    monday_start: true,
+};
+
+/// Resolves a locale code (from `--locale`, falling back to `LC_TIME` /
+/// `LC_ALL` / `LANG`) to its name table. Unknown codes fall back to
+/// English rather than erroring, since this is purely cosmetic.
+fn resolve_locale(locale: Option<&str>) -> &'static LocaleNames {
+    let code = locale
+        .map(str::to_string)
+        .or_else(|| std::env::var("LC_TIME").ok())
+        .or_else(|| std::env::var("LC_ALL").ok())
+        .or_else(|| std::env::var("LANG").ok())
+        .unwrap_or_default();
+    let lang = code.split(['_', '.', '-']).next().unwrap_or("").to_lowercase();
+    match lang.as_str() {
+        "de" => &DE,
+        "fr" => &FR,
+        "es" => &ES,
+        "pt" => &PT,
+        _ => &EN,
+    }
+}
+
+/// Five-step dark-to-bright gradient `--heatmap` shades each day's
+/// background with, keyed by intensity level 0 (no training) through 4.
+const GREEN_GRADIENT: [(u8, u8, u8); 5] =
+    [(22, 27, 34), (14, 68, 41), (0, 109, 50), (38, 166, 65), (57, 211, 83)];
+
+/// `ceil(value / max * 4)`, clamped into `0..=4` — level 0 only when
+/// `value` is zero, so any trained day gets at least a faint highlight.
+fn intensity_level(value: f64, max: f64) -> usize {
+    if value <= 0.0 || max <= 0.0 {
+        return 0;
+    }
+    (((value / max) * 4.0).ceil() as usize).clamp(1, 4)
+}
+
+/// Total tonnage (`SUM(weight * reps)`) logged per day in `[start,
+/// end_exclusive)`, shared by the month heatmap and the year grid.
+async fn volume_by_day(
+    pool: &SqlitePool,
+    start: NaiveDate,
+    end_exclusive: NaiveDate,
+) -> Result<HashMap<NaiveDate, f64>> {
+    let rows = sqlx::query_as::<_, (String, f64)>(
+        r#"
+        SELECT date(es.timestamp) as day,
+               CAST(COALESCE(SUM(CAST(es.weight AS REAL) * CAST(es.reps AS INTEGER)), 0) AS REAL) as tonnage
+        FROM exercise_sets es
+        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+        JOIN training_sessions ts ON ts.id = tse.training_session_id
+        WHERE ts.start_time >= ? AND ts.start_time < ?
+        GROUP BY day
+        "#,
+    )
+    .bind(start.and_hms_opt(0, 0, 0).unwrap().format("%Y-%m-%d %H:%M:%S").to_string())
+    .bind(end_exclusive.and_hms_opt(0, 0, 0).unwrap().format("%Y-%m-%d %H:%M:%S").to_string())
+    .fetch_all(pool)
+    .await?;
+
+    let mut by_day = HashMap::new();
+    for (day, tonnage) in rows {
+        if let Ok(d) = NaiveDate::parse_from_str(&day, "%Y-%m-%d") {
+            by_day.insert(d, tonnage);
+        }
+    }
+    Ok(by_day)
+}
+
+/// `training_sessions.start_time`/`end_time` are stored via SQLite's
+/// `datetime('now')`, which yields `"YYYY-MM-DD HH:MM:SS"` — not RFC3339 —
+/// so they must be parsed with this exact format, not `DateTime::parse_from_rfc3339`.
+fn parse_session_time(value: &str) -> chrono::NaiveDateTime {
+    chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .unwrap_or_else(|e| panic!("invalid session timestamp `{value}`: {e}"))
+}
+
+fn session_duration(session: &SessionRow) -> chrono::Duration {
+    let start = parse_session_time(&session.1);
+    let end = session.2.as_ref().map(|e| parse_session_time(e)).unwrap_or_else(|| chrono::Local::now().naive_local());
+    end - start
+}
+
+/// Visible width of one month block as rendered by `render_month_lines`:
+/// seven `"NN "` day cells.
+const MONTH_BLOCK_WIDTH: usize = 21;
+
+/// Renders one month as a fixed 8-line block (header, weekday row, and up
+/// to 6 week rows, blank-padded if the month spans fewer) so several
+/// months can be joined side by side by `render_months_side_by_side`.
+async fn render_month_lines(
+    pool: &SqlitePool,
+    year: i32,
+    month: u32,
+    heatmap: bool,
+    metric: CalendarMetric,
+    locale: &LocaleNames,
+) -> Result<Vec<String>> {
+    let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    let last_day = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap()
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1).unwrap()
+    }
+    .pred_opt()
+    .unwrap();
+
+    let sessions = sqlx::query_as::<_, SessionRow>(
+        r#"
+        SELECT ts.id, ts.start_time, ts.end_time, ts.notes, p.name as program_name, pb.name as block_name
+        FROM training_sessions ts
+        JOIN program_blocks pb ON pb.id = ts.program_block_id
+        JOIN programs p ON p.id = pb.program_id
+        WHERE ts.start_time >= ? AND ts.start_time < ?
+        ORDER BY ts.start_time
+        "#,
+    )
+    .bind(first_day.and_hms_opt(0, 0, 0).unwrap().format("%Y-%m-%d %H:%M:%S").to_string())
+    .bind(last_day.and_hms_opt(23, 59, 59).unwrap().format("%Y-%m-%d %H:%M:%S").to_string())
+    .fetch_all(pool)
+    .await?;
+
+    let mut sessions_by_day: HashMap<usize, Vec<&SessionRow>> = HashMap::new();
+    for session in &sessions {
+        let start = parse_session_time(&session.1);
+        sessions_by_day.entry(start.day() as usize).or_default().push(session);
+    }
+
+    let mut metric_by_day: HashMap<usize, f64> = HashMap::new();
+    if heatmap {
+        match metric {
+            CalendarMetric::Sessions => {
+                for (&day, day_sessions) in &sessions_by_day {
+                    metric_by_day.insert(day, day_sessions.len() as f64);
+                }
+            }
+            CalendarMetric::Minutes => {
+                for (&day, day_sessions) in &sessions_by_day {
+                    let total_minutes: i64 = day_sessions
+                        .iter()
+                        .map(|s| session_duration(s).num_minutes())
+                        .sum();
+                    metric_by_day.insert(day, total_minutes as f64);
+                }
+            }
+            CalendarMetric::Volume => {
+                let by_date = volume_by_day(pool, first_day, last_day + chrono::Days::new(1)).await?;
+                for (day, tonnage) in by_date {
+                    metric_by_day.insert(day.day() as usize, tonnage);
+                }
+            }
+        }
+    }
+    let max_metric = metric_by_day.values().copied().fold(0.0, f64::max);
+
+    let first_weekday = if locale.monday_start {
+        first_day.weekday().num_days_from_monday() as usize
+    } else {
+        first_day.weekday().num_days_from_sunday() as usize
+    };
+    let mut lines = Vec::with_capacity(8);
+
+    let month_name = locale.months[month as usize - 1];
+    lines.push(format!("{:^w$}", format!("{} {}", month_name, year), w = MONTH_BLOCK_WIDTH).bold().cyan().to_string());
+    lines.push(format!("{:<w$}", locale.weekdays.join(" "), w = MONTH_BLOCK_WIDTH).dimmed().to_string());
+
+    let mut row = String::new();
+    let mut col = first_weekday;
+    row.push_str(&"   ".repeat(first_weekday));
+
+    for day in 1..=last_day.day() {
+        let day_num = day as usize;
+
+        if heatmap {
+            let level = intensity_level(metric_by_day.get(&day_num).copied().unwrap_or(0.0), max_metric);
+            if level == 0 {
+                row.push_str(&format!("{:2} ", day));
+            } else {
+                let (r, g, b) = GREEN_GRADIENT[level];
+                row.push_str(&format!("{} ", format!("{:2}", day).on_truecolor(r, g, b)));
+            }
+        } else if sessions_by_day.contains_key(&day_num) {
+            row.push_str(&format!("{:2} ", day.to_string().green().bold()));
+        } else {
+            row.push_str(&format!("{:2} ", day));
+        }
+
+        col += 1;
+        if col % 7 == 0 {
+            lines.push(std::mem::take(&mut row));
+        }
+    }
+    if col % 7 != 0 {
+        row.push_str(&"   ".repeat(7 - col % 7));
+        lines.push(row);
+    }
+    while lines.len() < 8 {
+        lines.push(" ".repeat(MONTH_BLOCK_WIDTH));
+    }
+
+    Ok(lines)
+}
+
+/// `cal -m 3`-style side-by-side layout: renders `count` consecutive
+/// months starting at `(year, month)` and joins their fixed-width blocks
+/// row by row, separated by a couple of spaces.
+async fn render_months_side_by_side(
+    pool: &SqlitePool,
+    year: i32,
+    month: u32,
+    heatmap: bool,
+    metric: CalendarMetric,
+    count: u32,
+    locale: &LocaleNames,
+) -> Result<()> {
+    const SEPARATOR: &str = "  ";
+
+    let mut blocks = Vec::with_capacity(count as usize);
+    let (mut y, mut m) = (year, month);
+    for _ in 0..count {
+        blocks.push(render_month_lines(pool, y, m, heatmap, metric, locale).await?);
+        if m == 12 {
+            m = 1;
+            y += 1;
+        } else {
+            m += 1;
+        }
+    }
+
+    println!();
+    for row in 0..8 {
+        let joined = blocks
+            .iter()
+            .map(|block| block[row].as_str())
+            .collect::<Vec<_>>()
+            .join(SEPARATOR);
+        println!("{}", joined);
+    }
+
+    if heatmap {
+        print!("{} ", "less".dimmed());
+        for (r, g, b) in GREEN_GRADIENT {
+            print!("{} ", "■".truecolor(r, g, b));
+        }
+        println!("{}", "more".dimmed());
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Palette `render_html` cycles through to color each program's session
+/// blocks, keyed by a simple hash of the program name so the same program
+/// always gets the same color within one export.
+const PROGRAM_COLORS: [&str; 6] = ["#2563eb", "#16a34a", "#d97706", "#db2777", "#7c3aed", "#0891b2"];
+
+fn color_for_program(name: &str) -> &'static str {
+    let hash: u32 = name.bytes().fold(0u32, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u32));
+    PROGRAM_COLORS[hash as usize % PROGRAM_COLORS.len()]
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// `calendar --export html` — one `<table>` cell per day of the month,
+/// with a colored block per session (colored by program) and a `title`
+/// tooltip carrying notes, start/end times, and duration.
+fn render_html(sessions: &[SessionRow], first_day: NaiveDate, last_day: NaiveDate) -> String {
+    let mut sessions_by_day: HashMap<u32, Vec<&SessionRow>> = HashMap::new();
+    for session in sessions {
+        let start = parse_session_time(&session.1);
+        sessions_by_day.entry(start.day()).or_default().push(session);
+    }
+
+    let first_weekday = first_day.weekday().num_days_from_sunday();
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", first_day.format("%B %Y")));
+    html.push_str(
+        "<style>table{border-collapse:collapse;width:100%}td,th{border:1px solid #ccc}\
+         td{vertical-align:top;height:80px;width:14.28%}.day{font-weight:bold}\
+         .session{display:block;color:#fff;border-radius:3px;padding:2px 4px;margin-top:2px;font-size:12px}</style>\n",
+    );
+    html.push_str("</head>\n<body>\n");
+    html.push_str(&format!("<h1>{}</h1>\n<table>\n<tr>", first_day.format("%B %Y")));
+    for label in ["Su", "Mo", "Tu", "We", "Th", "Fr", "Sa"] {
+        html.push_str(&format!("<th>{}</th>", label));
+    }
+    html.push_str("</tr>\n<tr>");
+
+    let mut col = 0;
+    for _ in 0..first_weekday {
+        html.push_str("<td></td>");
+        col += 1;
+    }
+    for day in 1..=last_day.day() {
+        html.push_str("<td><span class=\"day\">");
+        html.push_str(&day.to_string());
+        html.push_str("</span>");
+        if let Some(day_sessions) = sessions_by_day.get(&day) {
+            for session in day_sessions {
+                let start = parse_session_time(&session.1);
+                let duration = session_duration(session);
+                let end = start + duration;
+                let title = format!(
+                    "{} - {} ({}){}",
+                    start.format("%H:%M"),
+                    end.format("%H:%M"),
+                    format_duration(duration),
+                    session.3.as_deref().map(|n| format!(" — {}", n)).unwrap_or_default(),
+                );
+                html.push_str(&format!(
+                    "<span class=\"session\" style=\"background:{}\" title=\"{}\">{} — {}</span>",
+                    color_for_program(&session.4),
+                    html_escape(&title),
+                    html_escape(&session.4),
+                    html_escape(&session.5),
+                ));
+            }
+        }
+        html.push_str("</td>");
+        col += 1;
+        if col % 7 == 0 {
+            html.push_str("</tr>\n<tr>");
+        }
+    }
+    html.push_str("</tr>\n</table>\n</body>\n</html>\n");
+    html
+}
+
+fn ics_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace(',', "\\,").replace(';', "\\;").replace('\n', "\\n")
+}
+
+/// `calendar --export ics` — one `VEVENT` per session, `DTSTART`/`DTEND`
+/// in UTC from `start_time`/`end_time`, `SUMMARY` as "program — block",
+/// and `DESCRIPTION` from notes.
+fn render_ics(sessions: &[SessionRow]) -> String {
+    let mut ics = String::new();
+    ics.push_str("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//lazarus//training calendar//EN\r\n");
+    for session in sessions {
+        let start = DateTime::<Utc>::from_naive_utc_and_offset(parse_session_time(&session.1), Utc);
+        let end = session
+            .2
+            .as_ref()
+            .map(|e| DateTime::<Utc>::from_naive_utc_and_offset(parse_session_time(e), Utc))
+            .unwrap_or(start);
+
+        ics.push_str("BEGIN:VEVENT\r\n");
+        ics.push_str(&format!("UID:{}@lazarus\r\n", session.0));
+        ics.push_str(&format!("DTSTART:{}\r\n", start.format("%Y%m%dT%H%M%SZ")));
+        ics.push_str(&format!("DTEND:{}\r\n", end.format("%Y%m%dT%H%M%SZ")));
+        ics.push_str(&format!("SUMMARY:{} — {}\r\n", session.4, session.5));
+        if let Some(notes) = &session.3 {
+            if !notes.is_empty() {
+                ics.push_str(&format!("DESCRIPTION:{}\r\n", ics_escape(notes)));
+            }
+        }
+        ics.push_str("END:VEVENT\r\n");
+    }
+    ics.push_str("END:VCALENDAR\r\n");
+    ics
+}
+
+pub async fn handle(
+    pool: &SqlitePool,
+    year: Option<i32>,
+    month: Option<u32>,
+    heatmap: bool,
+    metric: CalendarMetric,
+    full_year: bool,
+    months: Option<u32>,
+    export: Option<CalendarExportFormat>,
+    out: Option<std::path::PathBuf>,
+    locale: Option<String>,
+) -> Result<()> {
+    let locale = resolve_locale(locale.as_deref());
+
     // Get current date if year/month not specified
     let now = chrono::Local::now();
     let year = year.unwrap_or(now.year());
+
+    if full_year {
+        return render_year(pool, year, metric).await;
+    }
+
     let month = month.unwrap_or(now.month());
 
     // Validate month
@@ -15,6 +458,10 @@ pub async fn handle(pool: &SqlitePool, year: Option<i32>, month: Option<u32>) ->
         return Ok(());
     }
 
+    if let Some(count) = months {
+        return render_months_side_by_side(pool, year, month, heatmap, metric, count.max(1), locale).await;
+    }
+
     // Get first and last day of the month
     let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
     let last_day = if month == 12 {
@@ -23,8 +470,8 @@ pub async fn handle(pool: &SqlitePool, year: Option<i32>, month: Option<u32>) ->
         NaiveDate::from_ymd_opt(year, month + 1, 1).unwrap()
     }.pred_opt().unwrap();
 
-    // Get all sessions in the month
-    let sessions = sqlx::query_as::<_, (String, String, Option<String>, Option<String>, String, String)>(
+    // Get all sessions in the month, for the details list below
+    let sessions = sqlx::query_as::<_, SessionRow>(
         r#"
         SELECT ts.id, ts.start_time, ts.end_time, ts.notes, p.name as program_name, pb.name as block_name
         FROM training_sessions ts
@@ -39,81 +486,214 @@ pub async fn handle(pool: &SqlitePool, year: Option<i32>, month: Option<u32>) ->
     .fetch_all(pool)
     .await?;
 
-    // Print calendar header
-    let month_name = first_day.format("%B %Y").to_string();
-    println!("\n{}", month_name.bold().cyan());
-    println!("{}", "Su Mo Tu We Th Fr Sa".dimmed());
-
-    // Get the day of week for the first day (0 = Sunday)
-    let first_weekday = first_day.weekday().num_days_from_sunday() as usize;
-    
-    // Print leading spaces
-    print!("{}", "   ".repeat(first_weekday));
-
-    // Create a map of sessions by day
-    let mut sessions_by_day = std::collections::HashMap::new();
-    for session in &sessions {
-        let start = DateTime::parse_from_rfc3339(&session.1)
-            .unwrap()
-            .with_timezone(&Utc)
-            .naive_local();
-        let day = start.day() as usize;
-        sessions_by_day.entry(day).or_insert_with(Vec::new).push(session);
+    if let Some(format) = export {
+        let path = out.unwrap_or_else(|| {
+            std::path::PathBuf::from(match format {
+                CalendarExportFormat::Html => "calendar.html",
+                CalendarExportFormat::Ics => "calendar.ics",
+            })
+        });
+        let rendered = match format {
+            CalendarExportFormat::Html => render_html(&sessions, first_day, last_day),
+            CalendarExportFormat::Ics => render_ics(&sessions),
+        };
+        std::fs::write(&path, rendered)?;
+        println!("{} exported {} session(s) to `{}`", "info:".blue().bold(), sessions.len(), path.display());
+        return Ok(());
     }
 
-    // Print calendar days
-    for day in 1..=last_day.day() {
-        let day_num = day as usize;
-        
-        // Print day number
-        if let Some(_sessions) = sessions_by_day.get(&day_num) {
-            // Day has sessions - print in green
-            print!("{:2} ", day.to_string().green().bold());
-        } else {
-            // Regular day
-            print!("{:2} ", day);
-        }
+    for line in render_month_lines(pool, year, month, heatmap, metric, locale).await? {
+        println!("{}", line);
+    }
 
-        // New line at end of week
-        if (first_weekday + day_num) % 7 == 0 {
-            println!();
+    if heatmap {
+        print!("{} ", "less".dimmed());
+        for (r, g, b) in GREEN_GRADIENT {
+            print!("{} ", "■".truecolor(r, g, b));
         }
+        println!("{}", "more".dimmed());
     }
-    println!("\n");
+    println!();
 
     // Print session details
     if !sessions.is_empty() {
         println!("{}", "Sessions:".bold().cyan());
-        for session in sessions {
-            let start = DateTime::parse_from_rfc3339(&session.1)
-                .unwrap()
-                .with_timezone(&Utc)
-                .naive_local();
-            let end = if let Some(end_time) = &session.2 {
-                DateTime::parse_from_rfc3339(end_time)
-                    .unwrap()
-                    .with_timezone(&Utc)
-                    .naive_local()
-            } else {
-                chrono::Local::now().naive_local()
-            };
-            let duration = end - start;
-            
-            println!("  {} - {} ({}) | {} - {}", 
+        for session in &sessions {
+            let start = parse_session_time(&session.1);
+            let duration = session_duration(session);
+            let end = start + duration;
+
+            println!("  {} - {} ({}) | {} - {}",
                 start.format("%a %b %d %H:%M").to_string().green(),
                 end.format("%H:%M").to_string(),
                 format_duration(duration),
                 session.4.bold(), // program name
-                session.5 // block name
+                session.5.clone() // block name
             );
-            
-            if let Some(notes) = session.3 {
+
+            if let Some(notes) = &session.3 {
                 if !notes.is_empty() {
                     println!("    {}", notes.dimmed());
                 }
             }
         }
+
+        print_month_summary(&sessions);
+    }
+
+    Ok(())
+}
+
+/// "Summary:" block printed below the session list: total training time,
+/// distinct training days, average session length, a per-program
+/// breakdown, and the longest consecutive-day training streak.
+fn print_month_summary(sessions: &[SessionRow]) {
+    let durations: Vec<chrono::Duration> = sessions.iter().map(session_duration).collect();
+    let total: chrono::Duration = durations.iter().fold(chrono::Duration::zero(), |acc, d| acc + *d);
+    let avg_minutes = total.num_minutes() / durations.len() as i64;
+
+    let mut days: Vec<u32> = sessions
+        .iter()
+        .map(|s| parse_session_time(&s.1).day())
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let mut best_streak = 1;
+    let mut current_streak = 1;
+    for window in days.windows(2) {
+        if window[1] == window[0] + 1 {
+            current_streak += 1;
+            best_streak = best_streak.max(current_streak);
+        } else {
+            current_streak = 1;
+        }
+    }
+
+    let mut by_program: HashMap<&str, (usize, chrono::Duration)> = HashMap::new();
+    for (session, duration) in sessions.iter().zip(&durations) {
+        let entry = by_program.entry(session.4.as_str()).or_insert((0, chrono::Duration::zero()));
+        entry.0 += 1;
+        entry.1 = entry.1 + *duration;
+    }
+    let mut programs: Vec<_> = by_program.into_iter().collect();
+    programs.sort_by(|a, b| a.0.cmp(b.0));
+
+    println!("\n{}", "Summary:".bold().cyan());
+    println!("  Total time: {}", format_duration(total));
+    println!("  Training days: {}", days.len());
+    println!("  Average session: {}", format_duration(chrono::Duration::minutes(avg_minutes)));
+    println!("  Longest streak: {} day{}", best_streak, if best_streak == 1 { "" } else { "s" });
+    println!("  By program:");
+    for (program, (count, time)) in programs {
+        println!("    {} - {} session{} ({})", program, count, if count == 1 { "" } else { "s" }, format_duration(time));
+    }
+}
+
+const WEEKDAY_LABELS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Column a day falls in on the year grid: its ISO week-of-year, clamped to
+/// column 0 for early-January days whose ISO week still belongs to the
+/// previous year, and to column 53 for late-December days whose ISO week
+/// already belongs to the next one.
+fn iso_week_column(day: NaiveDate, year: i32) -> usize {
+    let iso = day.iso_week();
+    if iso.year() < year {
+        0
+    } else if iso.year() > year {
+        53
+    } else {
+        iso.week() as usize
+    }
+}
+
+/// `cal --full-year` — a GitHub-style 7-row × ~53-column contribution grid
+/// for the whole year, bucketed by ISO week and weekday (rows Mon..Sun) and
+/// colored with the same intensity ramp as `cal --heatmap`.
+async fn render_year(pool: &SqlitePool, year: i32, metric: CalendarMetric) -> Result<()> {
+    let jan1 = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+    let dec31 = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
+    let next_jan1 = NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap();
+
+    let sessions = sqlx::query_as::<_, SessionRow>(
+        r#"
+        SELECT ts.id, ts.start_time, ts.end_time, ts.notes, p.name as program_name, pb.name as block_name
+        FROM training_sessions ts
+        JOIN program_blocks pb ON pb.id = ts.program_block_id
+        JOIN programs p ON p.id = pb.program_id
+        WHERE ts.start_time >= ? AND ts.start_time < ?
+        ORDER BY ts.start_time
+        "#,
+    )
+    .bind(jan1.and_hms_opt(0, 0, 0).unwrap().format("%Y-%m-%d %H:%M:%S").to_string())
+    .bind(next_jan1.and_hms_opt(0, 0, 0).unwrap().format("%Y-%m-%d %H:%M:%S").to_string())
+    .fetch_all(pool)
+    .await?;
+
+    let mut sessions_by_day: HashMap<NaiveDate, Vec<&SessionRow>> = HashMap::new();
+    for session in &sessions {
+        let day = parse_session_time(&session.1).date();
+        sessions_by_day.entry(day).or_default().push(session);
+    }
+
+    let value_by_day: HashMap<NaiveDate, f64> = match metric {
+        CalendarMetric::Sessions => {
+            sessions_by_day.iter().map(|(&day, s)| (day, s.len() as f64)).collect()
+        }
+        CalendarMetric::Minutes => sessions_by_day
+            .iter()
+            .map(|(&day, s)| (day, s.iter().map(|s| session_duration(s).num_minutes()).sum::<i64>() as f64))
+            .collect(),
+        CalendarMetric::Volume => volume_by_day(pool, jan1, next_jan1).await?,
+    };
+    let max_value = value_by_day.values().copied().fold(0.0, f64::max);
+
+    let total_cols = iso_week_column(dec31, year) + 1;
+    let mut grid: Vec<Vec<Option<NaiveDate>>> = vec![vec![None; total_cols]; 7];
+    let mut day = jan1;
+    while day <= dec31 {
+        let col = iso_week_column(day, year);
+        let row = day.weekday().num_days_from_monday() as usize;
+        grid[row][col] = Some(day);
+        day += chrono::Days::new(1);
+    }
+
+    // Month labels along the top, placed at the column where that month's
+    // 1st falls.
+    let mut month_line: Vec<char> = vec![' '; total_cols * 2];
+    for m in 1..=12u32 {
+        let first = NaiveDate::from_ymd_opt(year, m, 1).unwrap();
+        let col = iso_week_column(first, year);
+        for (i, ch) in first.format("%b").to_string().chars().enumerate() {
+            if let Some(slot) = month_line.get_mut(col * 2 + i) {
+                *slot = ch;
+            }
+        }
+    }
+
+    println!("\n{}", year.to_string().bold().cyan());
+    println!("    {}", month_line.into_iter().collect::<String>().dimmed());
+
+    for row in 0..7 {
+        print!("{} ", WEEKDAY_LABELS[row].dimmed());
+        for col in 0..total_cols {
+            match grid[row][col] {
+                Some(d) => {
+                    let level = intensity_level(value_by_day.get(&d).copied().unwrap_or(0.0), max_value);
+                    let (r, g, b) = GREEN_GRADIENT[level];
+                    print!("{} ", "■".truecolor(r, g, b));
+                }
+                None => print!("  "),
+            }
+        }
+        println!();
+    }
+
+    print!("    {} ", "less".dimmed());
+    for (r, g, b) in GREEN_GRADIENT {
+        print!("{} ", "■".truecolor(r, g, b));
     }
+    println!("{}", "more".dimmed());
 
     Ok(())
 }
@@ -127,4 +707,45 @@ fn format_duration(duration: chrono::Duration) -> String {
     } else {
         format!("{}m", minutes)
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::CalendarMetric;
+
+    /// Regression test for the `parse_session_time` fix: logs one session
+    /// through real SQL (start/end times exactly as `session::handle` writes
+    /// them — SQLite `datetime('now')`-shaped strings, not RFC3339) and
+    /// renders the month that contains it, which used to panic on the very
+    /// first session a user ever logged.
+    #[tokio::test]
+    async fn render_month_lines_survives_a_logged_session() {
+        let pool = crate::db::open(":memory:").await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO programs (id, name, description, created_at) VALUES ('prog', 'Test Program', NULL, datetime('now'))",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO program_blocks (id, program_id, name, description) VALUES ('block', 'prog', 'Test Block', NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO training_sessions (id, program_block_id, start_time, end_time, notes)
+             VALUES ('sess', 'block', '2024-03-15 10:00:00', '2024-03-15 11:30:00', NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let locale = resolve_locale(Some("en"));
+        let lines = render_month_lines(&pool, 2024, 3, false, CalendarMetric::Sessions, locale).await.unwrap();
+
+        assert_eq!(lines.len(), 8);
+        assert!(lines.iter().any(|l| l.contains("15")));
+    }
+}
@@ -43,6 +43,16 @@ pub async fn handle(cmd: ConfigCmd, mut cfg: Config, config_path: PathBuf) -> Re
                 println!("{} key `{}` not found", "warning:".yellow().bold(), key);
             }
         }
+
+        ConfigCmd::Unit { unit } => {
+            let val = match unit {
+                crate::types::WeightUnit::Kg => "kg",
+                crate::types::WeightUnit::Lb => "lb",
+            };
+            cfg.map.insert("weight_unit".to_string(), val.to_string());
+            cfg.save(&config_path)?;
+            println!("{} weight unit set to `{}`", "info:".blue().bold(), val.green());
+        }
     }
     
     Ok(())
@@ -1,18 +1,173 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use sqlx::{query, Executor, Row, SqlitePool};
-use std::fs;
+use std::{
+    collections::{HashSet, VecDeque},
+    fs,
+    time::Instant,
+};
 
 use crate::cli::DbCmd;
 
+/// Bump this whenever `DatabaseDump`'s shape changes in a way `import_db`
+/// needs to know about, and add a matching step to `MIGRATIONS`.
+const CURRENT_DUMP_VERSION: u32 = 1;
+
 #[derive(Serialize, Deserialize)]
 struct DatabaseDump {
+    /// Absent in dumps produced before this field existed, which `serde`
+    /// treats as version 0 so `migrate_dump` can forward-migrate them.
+    #[serde(default)]
+    version: u32,
+    /// SHA-256 over the canonical serialization of the data sections below,
+    /// written by `export_db` and checked by `import_db` before opening a
+    /// transaction. Empty in dumps produced before this field existed, which
+    /// `import_db` treats as "unchecked" rather than a mismatch.
+    #[serde(default)]
+    checksum: String,
     exercises: Vec<Exercise>,
     programs: Vec<Program>,
     sessions: Vec<Session>,
     #[serde(default)]
     personal_records: Vec<PersonalRecord>,
+    #[serde(default)]
+    measurements: Vec<Measurement>,
+}
+
+/// The data half of [`DatabaseDump`] — everything except the `version` and
+/// `checksum` header fields. Hashing this (rather than the whole dump)
+/// keeps the checksum stable across version bumps and keeps it from hashing
+/// itself.
+#[derive(Serialize)]
+struct DumpData<'a> {
+    exercises: &'a [Exercise],
+    programs: &'a [Program],
+    sessions: &'a [Session],
+    personal_records: &'a [PersonalRecord],
+    measurements: &'a [Measurement],
+}
+
+fn checksum_data(
+    exercises: &[Exercise],
+    programs: &[Program],
+    sessions: &[Session],
+    personal_records: &[PersonalRecord],
+    measurements: &[Measurement],
+) -> String {
+    let data = DumpData { exercises, programs, sessions, personal_records, measurements };
+    let bytes = serde_json::to_vec(&data).expect("serialize dump data for checksum");
+    format!("{:x}", Sha256::digest(&bytes))
+}
+
+/// One step per schema change, ordered by source version: `MIGRATIONS[i]`
+/// takes a dump at version `i` and upgrades it to version `i + 1`.
+type DumpMigration = fn(DatabaseDump) -> DatabaseDump;
+
+const MIGRATIONS: &[DumpMigration] = &[
+    // v0 -> v1: `personal_records` and `technique_group` postdate the very
+    // first dump format; `#[serde(default)]` already backfills them as
+    // empty/`None` on parse, so this step is an identity pass that exists
+    // purely to carry the version number forward.
+    |dump| dump,
+];
+
+/// Walks `dump` through `MIGRATIONS` until it reaches `CURRENT_DUMP_VERSION`.
+/// Bails out on a version newer than this build understands rather than
+/// risk inserting partial/garbage rows.
+fn migrate_dump(mut dump: DatabaseDump) -> Result<DatabaseDump> {
+    if dump.version > CURRENT_DUMP_VERSION {
+        return Err(anyhow!(
+            "dump version {} is newer than this build supports (max {}); upgrade lazarus before importing",
+            dump.version,
+            CURRENT_DUMP_VERSION
+        ));
+    }
+
+    for step in &MIGRATIONS[dump.version as usize..CURRENT_DUMP_VERSION as usize] {
+        dump = step(dump);
+    }
+    dump.version = CURRENT_DUMP_VERSION;
+
+    Ok(dump)
+}
+
+/// Bails with a descriptive error if `dump.checksum` doesn't match the hash
+/// of its own data sections. A blank checksum (pre-checksum dumps) is
+/// treated as unchecked rather than a mismatch.
+fn verify_checksum(dump: &DatabaseDump) -> Result<()> {
+    if dump.checksum.is_empty() {
+        return Ok(());
+    }
+    let expected = checksum_data(
+        &dump.exercises,
+        &dump.programs,
+        &dump.sessions,
+        &dump.personal_records,
+        &dump.measurements,
+    );
+    if expected != dump.checksum {
+        return Err(anyhow!(
+            "checksum mismatch: dump may be truncated or hand-edited (expected {}, got {})",
+            expected,
+            dump.checksum
+        ));
+    }
+    Ok(())
+}
+
+/// Referential-sanity pass for `db import --check`: every exercise reference
+/// inside the dump should resolve to an `Exercise` also present in the dump.
+/// Returns one human-readable problem description per dangling reference.
+fn validate_dump(dump: &DatabaseDump) -> Vec<String> {
+    let exercise_ids: HashSet<&str> = dump.exercises.iter().map(|e| e.id.as_str()).collect();
+    let mut problems = Vec::new();
+
+    for prog in &dump.programs {
+        for block in &prog.blocks {
+            for ex in &block.exercises {
+                if !exercise_ids.contains(ex.exercise_id.as_str()) {
+                    problems.push(format!(
+                        "program_exercise {} (program {:?}, block {:?}) references unknown exercise_id {}",
+                        ex.id, prog.name, block.name, ex.exercise_id
+                    ));
+                }
+            }
+        }
+    }
+
+    for sess in &dump.sessions {
+        for ex in &sess.exercises {
+            if !exercise_ids.contains(ex.exercise_id.as_str()) {
+                problems.push(format!(
+                    "session_exercise {} (session {}) references unknown exercise_id {}",
+                    ex.id, sess.id, ex.exercise_id
+                ));
+            }
+        }
+    }
+
+    problems
+}
+
+/// Parses and validates `file_path` (checksum + referential sanity) without
+/// touching the database. Returns the list of problems found, empty when
+/// the dump is clean.
+fn check_dump(file_path: &str) -> Result<Vec<String>> {
+    let toml_str = fs::read_to_string(file_path)?;
+    let dump: DatabaseDump = toml::from_str(&toml_str)?;
+
+    let mut problems = Vec::new();
+    if let Err(e) = verify_checksum(&dump) {
+        problems.push(e.to_string());
+    }
+
+    let dump = migrate_dump(dump)?;
+    problems.extend(validate_dump(&dump));
+
+    Ok(problems)
 }
 
 #[derive(Serialize, Deserialize)]
@@ -24,6 +179,11 @@ struct Exercise {
     created_at: String,
     estimated_one_rm: Option<f64>,
     current_pr_date: Option<String>,
+    /// Unix timestamp of the last local edit, used by `--merge` to decide
+    /// whether an incoming row should win. Absent in pre-merge dumps, which
+    /// `serde` defaults to 0 so any local row is treated as newer.
+    #[serde(default)]
+    last_updated: i64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -56,6 +216,8 @@ struct ProgramExercise {
     technique: Option<String>,
     technique_group: Option<i32>,
     order_index: i32,
+    #[serde(default)]
+    last_updated: i64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -66,6 +228,8 @@ struct Session {
     end_time: Option<String>,
     notes: Option<String>,
     exercises: Vec<SessionExercise>,
+    #[serde(default)]
+    last_updated: i64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -87,6 +251,8 @@ struct ExerciseSet {
     timestamp: String,
     ignore_for_one_rm: bool,
     bodyweight: bool,
+    #[serde(default)]
+    last_updated: i64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -98,6 +264,16 @@ struct PersonalRecord {
     estimated_1rm: f64,
 }
 
+#[derive(Serialize, Deserialize)]
+struct Measurement {
+    id: String,
+    kind: String,
+    value: f64,
+    unit: String,
+    timestamp: String,
+    notes: Option<String>,
+}
+
 /* ────────────────────────── public entry point ───────────────────────── */
 
 pub async fn handle(cmd: DbCmd, pool: &SqlitePool) -> Result<()> {
@@ -107,114 +283,175 @@ pub async fn handle(cmd: DbCmd, pool: &SqlitePool) -> Result<()> {
             export_db(pool, &file_path).await?;
             println!("{} database exported to {}", "ok:".green().bold(), file_path);
         }
-        DbCmd::Import { file } => {
-            import_db(pool, &file).await?;
+        DbCmd::Import { file, window_hours: _, merge: _, check: true } => {
+            let problems = check_dump(&file)?;
+            if problems.is_empty() {
+                println!("{} {} is valid", "ok:".green().bold(), file);
+            } else {
+                println!(
+                    "{} {} problem(s) found in {}",
+                    "warning:".yellow().bold(),
+                    problems.len(),
+                    file
+                );
+                for p in &problems {
+                    println!("  {} {}", "-".dimmed(), p);
+                }
+            }
+        }
+        DbCmd::Import { file, window_hours, merge, check: false } => {
+            let skipped = import_db(pool, &file, window_hours, merge).await?;
             println!("{} database imported from {}", "ok:".green().bold(), file);
+            if merge {
+                println!(
+                    "{} merged: local rows newer than the incoming dump were kept as-is",
+                    "note:".yellow().bold()
+                );
+            }
+            if skipped > 0 {
+                println!(
+                    "{} {} duplicate set(s) skipped (within a {}h window)",
+                    "note:".yellow().bold(),
+                    skipped,
+                    window_hours
+                );
+            }
         }
         DbCmd::Migrate { old_db } => migrate(pool, &old_db).await?,
+        DbCmd::ExportMetrics { file, since } => {
+            let file_path = file.unwrap_or_else(|| "metrics.line".to_string());
+            let points = export_metrics(pool, &file_path, since.as_deref()).await?;
+            println!(
+                "{} {} point(s) written to {}",
+                "ok:".green().bold(),
+                points,
+                file_path
+            );
+        }
     }
     Ok(())
 }
 
 /* ───────────────────────────── migrate old ──────────────────────────── */
 
-pub async fn migrate(pool: &SqlitePool, old_path: &str) -> Result<()> {
-    /* 1. always work on one physical connection */
-    let mut conn = pool.acquire().await?;
+/// A source schema `db migrate` knows how to onboard. Implementations probe
+/// the attached `old` database (via `old.sqlite_master` / `PRAGMA
+/// old.table_info`) rather than assuming a fixed shape, so a future schema
+/// generation — or a third-party tracker's export — can be supported by
+/// adding an implementation instead of rewriting one monolithic function.
+#[async_trait::async_trait]
+trait LegacyImporter {
+    /// Shown in the "no importer matched" error and in progress output.
+    fn name(&self) -> &'static str;
 
-    /* 2. attach the legacy file */
-    let attach = format!("ATTACH DATABASE '{}' AS old;", old_path.replace('\'', "''"));
-    conn.execute(&*attach).await?;
+    /// Cheap schema probe: does the attached `old` database look like this
+    /// importer's source format? Must not mutate anything.
+    async fn detect(&self, conn: &mut sqlx::SqliteConnection) -> Result<bool>;
 
-    /* sanity-check */
-    let has_exercises: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*) FROM old.sqlite_master
-         WHERE type='table' AND name='exercises';",
-    )
-    .fetch_one(&mut *conn)
-    .await?;
-    if has_exercises == 0 {
-        anyhow::bail!("`{old_path}` is not a Lazarus-v0 DB (missing `exercises` table)");
+    /// Perform the actual import. Only called after `detect` returned true.
+    async fn import(&self, conn: &mut sqlx::SqliteConnection) -> Result<()>;
+}
+
+/// The original Lazarus-v0 schema: `exercises`, `training_sessions`,
+/// `training_session_exercises`, `exercise_sets`, one flat muscle-name
+/// column with a few historical spellings of "quads".
+struct LazarusV0Importer;
+
+#[async_trait::async_trait]
+impl LegacyImporter for LazarusV0Importer {
+    fn name(&self) -> &'static str {
+        "Lazarus v0"
     }
 
-    /* 3. make sure a placeholder program / block exists */
-    const LEGACY_PROG: &str  = "legacy-prog";
-    const LEGACY_BLOCK: &str = "legacy-block";
+    async fn detect(&self, conn: &mut sqlx::SqliteConnection) -> Result<bool> {
+        let has_exercises: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM old.sqlite_master
+             WHERE type='table' AND name='exercises';",
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+        Ok(has_exercises > 0)
+    }
 
-    query(
-        "INSERT OR IGNORE INTO programs(id,name,description,created_at)
-         VALUES(?,'Legacy import','auto-generated',datetime('now'));",
-    )
-    .bind(LEGACY_PROG)
-    .execute(&mut *conn)
-    .await?;
+    async fn import(&self, conn: &mut sqlx::SqliteConnection) -> Result<()> {
+        /* 1. make sure a placeholder program / block exists */
+        const LEGACY_PROG: &str = "legacy-prog";
+        const LEGACY_BLOCK: &str = "legacy-block";
 
-    query(
-        "INSERT OR IGNORE INTO program_blocks(id,program_id,name)
-         VALUES(?,?, 'Imported sessions');",
-    )
-    .bind(LEGACY_BLOCK)
-    .bind(LEGACY_PROG)
-    .execute(&mut *conn)
-    .await?;
+        query(
+            "INSERT OR IGNORE INTO programs(id,name,description,created_at)
+             VALUES(?,'Legacy import','auto-generated',datetime('now'));",
+        )
+        .bind(LEGACY_PROG)
+        .execute(&mut *conn)
+        .await?;
 
-    /* 4. exercises ---------------------------------------------------- */
-    conn.execute(
-        "INSERT OR IGNORE INTO exercises(id,name,description,primary_muscle,created_at)
-         SELECT id,
-                name,
-                description,
-                CASE
-                    WHEN lower(primary_muscle) IN ('quadriceps','quad','quads')
-                    THEN 'quads'
-                    ELSE lower(primary_muscle)
-                END,
-                created_at
-         FROM old.exercises;",
-    )
-    .await?;
+        query(
+            "INSERT OR IGNORE INTO program_blocks(id,program_id,name)
+             VALUES(?,?, 'Imported sessions');",
+        )
+        .bind(LEGACY_BLOCK)
+        .bind(LEGACY_PROG)
+        .execute(&mut *conn)
+        .await?;
 
-    /* 5. sessions ----------------------------------------------------- */
-    query(
-        "INSERT OR IGNORE INTO training_sessions
-                (id, program_block_id, start_time, end_time, notes)
-         SELECT id, ?, start_time, end_time, notes
-         FROM   old.training_sessions;",
-    )
-    .bind(LEGACY_BLOCK)
-    .execute(&mut *conn)
-    .await?;
+        /* 2. exercises ------------------------------------------------ */
+        conn.execute(
+            "INSERT OR IGNORE INTO exercises(id,name,description,primary_muscle,created_at)
+             SELECT id,
+                    name,
+                    description,
+                    CASE
+                        WHEN lower(primary_muscle) IN ('quadriceps','quad','quads')
+                        THEN 'quads'
+                        ELSE lower(primary_muscle)
+                    END,
+                    created_at
+             FROM old.exercises;",
+        )
+        .await?;
 
-    /* close any legacy sessions that never got an end_time */
-    query(
-        "UPDATE training_sessions
-         SET   end_time = start_time
-         WHERE program_block_id = ?
-           AND end_time IS NULL;",
-    )
-    .bind(LEGACY_BLOCK)
-    .execute(&mut *conn)
-    .await?;
+        /* 3. sessions --------------------------------------------------- */
+        query(
+            "INSERT OR IGNORE INTO training_sessions
+                    (id, program_block_id, start_time, end_time, notes)
+             SELECT id, ?, start_time, end_time, notes
+             FROM   old.training_sessions;",
+        )
+        .bind(LEGACY_BLOCK)
+        .execute(&mut *conn)
+        .await?;
 
-    /* 6. session-exercises + sets ------------------------------------- */
-    conn.execute(
-        "INSERT OR IGNORE INTO training_session_exercises
-             (id, training_session_id, exercise_id, notes)
-         SELECT * FROM old.training_session_exercises;",
-    )
-    .await?;
+        /* close any legacy sessions that never got an end_time */
+        query(
+            "UPDATE training_sessions
+             SET   end_time = start_time
+             WHERE program_block_id = ?
+               AND end_time IS NULL;",
+        )
+        .bind(LEGACY_BLOCK)
+        .execute(&mut *conn)
+        .await?;
 
-    conn.execute(
-        "INSERT OR IGNORE INTO exercise_sets
-             (id, session_exercise_id, weight, reps, rpe, rm_percent, notes,
-              timestamp, ignore_for_one_rm, bodyweight)
-         SELECT * FROM old.exercise_sets;",
-    )
-    .await?;
+        /* 4. session-exercises + sets ------------------------------------- */
+        conn.execute(
+            "INSERT OR IGNORE INTO training_session_exercises
+                 (id, training_session_id, exercise_id, notes)
+             SELECT * FROM old.training_session_exercises;",
+        )
+        .await?;
 
-    /* 7. PERSONAL RECORDS (one best-set per day) ---------------------- */
-    conn.execute(
-        r#"
+        conn.execute(
+            "INSERT OR IGNORE INTO exercise_sets
+                 (id, session_exercise_id, weight, reps, rpe, rm_percent, notes,
+                  timestamp, ignore_for_one_rm, bodyweight)
+             SELECT * FROM old.exercise_sets;",
+        )
+        .await?;
+
+        /* 5. PERSONAL RECORDS (one best-set per day) ---------------------- */
+        conn.execute(
+            r#"
 INSERT OR REPLACE INTO personal_records
       (exercise_id, date, weight, reps, estimated_1rm)
 WITH ranked AS (
@@ -238,12 +475,12 @@ SELECT exercise_id, day, weight, reps, estimated_1rm
 FROM   ranked
 WHERE  rn = 1;
 "#,
-    )
-    .await?;
+        )
+        .await?;
 
-    /* 8. update exercises with BEST ever 1-RM ------------------------- */
-    conn.execute(
-        r#"
+        /* 6. update exercises with BEST ever 1-RM ------------------------- */
+        conn.execute(
+            r#"
 UPDATE exercises
 SET   current_pr_date  = pr.date,
       estimated_one_rm = pr.estimated_1rm
@@ -260,14 +497,57 @@ FROM (
 WHERE pr.exercise_id = exercises.id
   AND pr.rn = 1;
 "#,
-    )
-    .await?;
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Every known source schema, probed in order. Add an entry here (and a
+/// matching `LegacyImporter` impl above) to onboard a new generation or a
+/// third-party tracker's export.
+fn legacy_importers() -> Vec<Box<dyn LegacyImporter>> {
+    vec![Box::new(LazarusV0Importer)]
+}
+
+pub async fn migrate(pool: &SqlitePool, old_path: &str) -> Result<()> {
+    /* 1. always work on one physical connection */
+    let mut conn = pool.acquire().await?;
+
+    /* 2. attach the legacy file, foreign keys on so a bad importer can't
+          leave dangling references behind */
+    let attach = format!("ATTACH DATABASE '{}' AS old;", old_path.replace('\'', "''"));
+    conn.execute(&*attach).await?;
+    conn.execute("PRAGMA foreign_keys = ON;").await?;
+
+    /* 3. probe every known schema in order, stopping at the first match */
+    let importers = legacy_importers();
+    let mut tried = Vec::with_capacity(importers.len());
+    let mut matched = None;
+    for importer in &importers {
+        if importer.detect(&mut conn).await? {
+            matched = Some(importer);
+            break;
+        }
+        tried.push(importer.name());
+    }
+
+    let importer = matched.ok_or_else(|| {
+        anyhow!(
+            "`{old_path}` didn't match any known legacy schema (tried: {})",
+            tried.join(", ")
+        )
+    })?;
+
+    importer.import(&mut conn).await?;
 
-    /* 9. detach & done ------------------------------------------------ */
+    /* 4. detach & done ------------------------------------------------ */
     conn.execute("DETACH DATABASE old;").await?;
     println!(
-        "{} migration complete – legacy exercises, sessions & PRs imported",
-        "ok:".green().bold()
+        "{} migration complete via {} importer – legacy exercises, sessions & PRs imported",
+        "ok:".green().bold(),
+        importer.name()
     );
 
     Ok(())
@@ -277,8 +557,8 @@ async fn export_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
     // Fetch exercises
     let exercises = query(
         r#"
-        SELECT id, name, primary_muscle, description, created_at, 
-               estimated_one_rm, current_pr_date
+        SELECT id, name, primary_muscle, description, created_at,
+               estimated_one_rm, current_pr_date, last_updated
         FROM exercises
         "#
     )
@@ -293,6 +573,7 @@ async fn export_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
         created_at: row.get("created_at"),
         estimated_one_rm: row.get("estimated_one_rm"),
         current_pr_date: row.get("current_pr_date"),
+        last_updated: row.get("last_updated"),
     })
     .collect::<Vec<_>>();
 
@@ -324,7 +605,7 @@ async fn export_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
             let exercises = query(
                 r#"
                 SELECT id, exercise_id, sets, reps, target_rpe, target_rm_percent,
-                       notes, program_1rm, technique, technique_group, order_index
+                       notes, program_1rm, technique, technique_group, order_index, last_updated
                 FROM program_exercises
                 WHERE program_block_id = ?
                 "#
@@ -345,6 +626,7 @@ async fn export_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
                 technique: ex.get("technique"),
                 technique_group: ex.get("technique_group"),
                 order_index: ex.get("order_index"),
+                last_updated: ex.get("last_updated"),
             })
             .collect();
 
@@ -369,7 +651,7 @@ async fn export_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
     let mut sessions = Vec::new();
     let session_rows = query(
         r#"
-        SELECT id, program_block_id, start_time, end_time, notes
+        SELECT id, program_block_id, start_time, end_time, notes, last_updated
         FROM training_sessions
         "#
     )
@@ -393,7 +675,7 @@ async fn export_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
             let sets = query(
                 r#"
                 SELECT id, weight, reps, rpe, rm_percent, notes,
-                       timestamp, ignore_for_one_rm, bodyweight
+                       timestamp, ignore_for_one_rm, bodyweight, last_updated
                 FROM exercise_sets
                 WHERE session_exercise_id = ?
                 "#
@@ -412,6 +694,7 @@ async fn export_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
                 timestamp: set.get("timestamp"),
                 ignore_for_one_rm: set.get::<i32, _>("ignore_for_one_rm") != 0,
                 bodyweight: set.get::<i32, _>("bodyweight") != 0,
+                last_updated: set.get("last_updated"),
             })
             .collect();
 
@@ -430,6 +713,7 @@ async fn export_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
             end_time: sess.get("end_time"),
             notes: sess.get("notes"),
             exercises,
+            last_updated: sess.get("last_updated"),
         });
     }
 
@@ -452,12 +736,36 @@ async fn export_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
     })
     .collect::<Vec<_>>();
 
+    // Fetch body measurements
+    let measurements = query(
+        r#"
+        SELECT id, kind, value, unit, timestamp, notes
+        FROM measurements
+        "#
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| Measurement {
+        id: row.get("id"),
+        kind: row.get("kind"),
+        value: row.get("value"),
+        unit: row.get("unit"),
+        timestamp: row.get("timestamp"),
+        notes: row.get("notes"),
+    })
+    .collect::<Vec<_>>();
+
     // Create the final dump structure
+    let checksum = checksum_data(&exercises, &programs, &sessions, &personal_records, &measurements);
     let dump = DatabaseDump {
+        version: CURRENT_DUMP_VERSION,
+        checksum,
         exercises,
         programs,
         sessions,
         personal_records,
+        measurements,
     };
 
     // Write to file
@@ -467,33 +775,403 @@ async fn export_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
     Ok(())
 }
 
-async fn import_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
+/// Sets imported across devices/backups often arrive out of order and with
+/// exact repeats. Walk every set in timestamp order and flag repeats of
+/// `(exercise_id, timestamp, weight, reps, rpe)` using a FIFO-bounded
+/// seen-set: a hash set for O(1) lookup paired with an insertion-ordered
+/// queue so entries older than `window_hours` can be pruned, keeping memory
+/// bounded on large histories. Returns the ids of sets to skip plus how many
+/// were skipped.
+fn find_duplicate_sets(sessions: &[Session], window_hours: i64) -> (HashSet<String>, usize) {
+    struct FlatSet<'a> {
+        set_id: &'a str,
+        exercise_id: &'a str,
+        timestamp: &'a str,
+        weight: f64,
+        reps: i32,
+        rpe: Option<f64>,
+    }
+
+    let mut flat: Vec<FlatSet> = sessions
+        .iter()
+        .flat_map(|s| s.exercises.iter())
+        .flat_map(|ex| {
+            ex.sets.iter().map(move |set| FlatSet {
+                set_id: &set.id,
+                exercise_id: &ex.exercise_id,
+                timestamp: &set.timestamp,
+                weight: set.weight,
+                reps: set.reps,
+                rpe: set.rpe,
+            })
+        })
+        .collect();
+    flat.sort_by(|a, b| a.timestamp.cmp(b.timestamp));
+
+    let window = Duration::hours(window_hours);
+    let mut seen: HashSet<(String, String, u64, i32, u64)> = HashSet::new();
+    let mut queue: VecDeque<(DateTime<Utc>, (String, String, u64, i32, u64))> = VecDeque::new();
+    let mut duplicate_ids = HashSet::new();
+    let mut skipped = 0usize;
+
+    for item in &flat {
+        // `exercise_sets.timestamp` is written via SQLite's `datetime('now')`
+        // (`"YYYY-MM-DD HH:MM:SS"`, no offset), not RFC3339.
+        let Ok(naive) = chrono::NaiveDateTime::parse_from_str(item.timestamp, "%Y-%m-%d %H:%M:%S") else {
+            continue;
+        };
+        let ts = naive.and_utc();
+        let key = (
+            item.exercise_id.to_string(),
+            item.timestamp.to_string(),
+            item.weight.to_bits(),
+            item.reps,
+            item.rpe.unwrap_or(f64::NAN).to_bits(),
+        );
+
+        while let Some((front_ts, _)) = queue.front() {
+            if ts - *front_ts > window {
+                let (_, old_key) = queue.pop_front().unwrap();
+                seen.remove(&old_key);
+            } else {
+                break;
+            }
+        }
+
+        if seen.contains(&key) {
+            duplicate_ids.insert(item.set_id.to_string());
+            skipped += 1;
+        } else {
+            seen.insert(key.clone());
+            queue.push_back((ts, key));
+        }
+    }
+
+    (duplicate_ids, skipped)
+}
+
+/// Escapes spaces, commas and equals signs in an InfluxDB line-protocol tag
+/// value, per the line protocol spec.
+fn escape_tag(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(',', "\\,").replace(' ', "\\ ").replace('=', "\\=")
+}
+
+/// Converts an `exercise_sets.timestamp` string to nanoseconds since the
+/// epoch, the time precision InfluxDB line protocol expects. These
+/// timestamps are written via SQLite's `datetime('now')`
+/// (`"YYYY-MM-DD HH:MM:SS"`, no offset), not RFC3339, so they're parsed as
+/// naive and treated as UTC.
+fn to_unix_nanos(timestamp: &str) -> Result<i64> {
+    let naive = chrono::NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S")?;
+    let ts = naive.and_utc();
+    Ok(ts.timestamp() * 1_000_000_000 + ts.timestamp_subsec_nanos() as i64)
+}
+
+/// Exports every logged set as InfluxDB line-protocol points (`weight`,
+/// `reps`, `rpe`, `estimated_1rm`, plus a per-session-exercise `volume`
+/// point), tagged by exercise name/muscle/program, so history can be piped
+/// into InfluxDB/Grafana. Returns the number of points written.
+async fn export_metrics(pool: &SqlitePool, file_path: &str, since: Option<&str>) -> Result<usize> {
+    // `since` is RFC3339 but `exercise_sets.timestamp` is `"YYYY-MM-DD
+    // HH:MM:SS"`, so normalize it to that shape before comparing strings.
+    let since = since
+        .map(|s| DateTime::parse_from_rfc3339(s).map(|d| d.naive_utc().format("%Y-%m-%d %H:%M:%S").to_string()))
+        .transpose()?;
+
+    let rows = query(
+        r#"
+        SELECT
+            e.name           AS exercise_name,
+            e.primary_muscle AS muscle,
+            p.name           AS program_name,
+            es.weight, es.reps, es.rpe, es.timestamp
+        FROM exercise_sets es
+        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+        JOIN training_sessions ts           ON ts.id  = tse.training_session_id
+        JOIN exercises e                    ON e.id   = tse.exercise_id
+        LEFT JOIN program_blocks pb ON pb.id = ts.program_block_id
+        LEFT JOIN programs p        ON p.id  = pb.program_id
+        WHERE (?1 IS NULL OR es.timestamp >= ?1)
+        ORDER BY es.timestamp
+        "#,
+    )
+    .bind(since.as_deref())
+    .fetch_all(pool)
+    .await?;
+
+    let volume_rows = query(
+        r#"
+        SELECT
+            e.name           AS exercise_name,
+            e.primary_muscle AS muscle,
+            p.name           AS program_name,
+            SUM(es.weight * es.reps) AS volume,
+            MAX(es.timestamp)        AS timestamp
+        FROM exercise_sets es
+        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+        JOIN training_sessions ts           ON ts.id  = tse.training_session_id
+        JOIN exercises e                    ON e.id   = tse.exercise_id
+        LEFT JOIN program_blocks pb ON pb.id = ts.program_block_id
+        LEFT JOIN programs p        ON p.id  = pb.program_id
+        WHERE (?1 IS NULL OR es.timestamp >= ?1)
+        GROUP BY tse.id
+        "#,
+    )
+    .bind(since.as_deref())
+    .fetch_all(pool)
+    .await?;
+
+    let mut out = String::new();
+    let mut points = 0usize;
+
+    for row in &rows {
+        let name: String = row.get("exercise_name");
+        let muscle: String = row.get("muscle");
+        let program: Option<String> = row.get("program_name");
+        let weight: f64 = row.get("weight");
+        let reps: i32 = row.get("reps");
+        let rpe: Option<f64> = row.get("rpe");
+        let timestamp: String = row.get("timestamp");
+
+        let tags = format!(
+            "name={},muscle={},program={}",
+            escape_tag(&name),
+            escape_tag(&muscle),
+            escape_tag(program.as_deref().unwrap_or("none"))
+        );
+        let ns = to_unix_nanos(&timestamp)?;
+        let estimated_1rm = weight * (1.0 + reps as f64 / 30.0);
+
+        out.push_str(&format!("weight,{tags} value={weight} {ns}\n"));
+        out.push_str(&format!("reps,{tags} value={reps} {ns}\n"));
+        out.push_str(&format!("estimated_1rm,{tags} value={estimated_1rm} {ns}\n"));
+        points += 3;
+        if let Some(rpe) = rpe {
+            out.push_str(&format!("rpe,{tags} value={rpe} {ns}\n"));
+            points += 1;
+        }
+    }
+
+    for row in &volume_rows {
+        let name: String = row.get("exercise_name");
+        let muscle: String = row.get("muscle");
+        let program: Option<String> = row.get("program_name");
+        let volume: f64 = row.get("volume");
+        let timestamp: String = row.get("timestamp");
+
+        let tags = format!(
+            "name={},muscle={},program={}",
+            escape_tag(&name),
+            escape_tag(&muscle),
+            escape_tag(program.as_deref().unwrap_or("none"))
+        );
+        let ns = to_unix_nanos(&timestamp)?;
+
+        out.push_str(&format!("volume,{tags} value={volume} {ns}\n"));
+        points += 1;
+    }
+
+    fs::write(file_path, out)?;
+    Ok(points)
+}
+
+/// SQLite rejects a statement with more than this many bound `?` parameters.
+const SQLITE_MAX_PARAMS: usize = 999;
+
+/// How many `(?, ?, ...)` value-tuples to pack into a single multi-row
+/// `INSERT` for a table with `cols` columns — capped at 500 tuples (mirrors
+/// the what2watch importer's batch size) and further capped so the total
+/// bound-parameter count never crosses `SQLITE_MAX_PARAMS`.
+fn batch_size(cols: usize) -> usize {
+    (SQLITE_MAX_PARAMS / cols).min(500).max(1)
+}
+
+/// Flattened set ready for the batched `exercise_sets` insert: the owning
+/// `training_session_exercises.id` alongside the set itself.
+struct FlatExerciseSet<'a> {
+    session_exercise_id: &'a str,
+    set: &'a ExerciseSet,
+}
+
+async fn insert_exercises_batched(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    exercises: &[Exercise],
+    merge: bool,
+) -> Result<()> {
+    const COLS: usize = 8;
+    for chunk in exercises.chunks(batch_size(COLS)) {
+        let values = vec!["(?, ?, ?, ?, ?, ?, ?, ?)"; chunk.len()].join(",");
+        let sql = if merge {
+            format!(
+                "INSERT INTO exercises \
+                 (id, name, primary_muscle, description, created_at, estimated_one_rm, current_pr_date, last_updated) \
+                 VALUES {values} \
+                 ON CONFLICT(id) DO UPDATE SET \
+                   name = excluded.name, \
+                   primary_muscle = excluded.primary_muscle, \
+                   description = excluded.description, \
+                   created_at = excluded.created_at, \
+                   estimated_one_rm = excluded.estimated_one_rm, \
+                   current_pr_date = excluded.current_pr_date, \
+                   last_updated = excluded.last_updated \
+                 WHERE excluded.last_updated > exercises.last_updated"
+            )
+        } else {
+            format!(
+                "INSERT OR REPLACE INTO exercises \
+                 (id, name, primary_muscle, description, created_at, estimated_one_rm, current_pr_date, last_updated) \
+                 VALUES {values}"
+            )
+        };
+        let mut q = query(&sql);
+        for ex in chunk {
+            q = q
+                .bind(&ex.id)
+                .bind(&ex.name)
+                .bind(&ex.primary_muscle)
+                .bind(&ex.description)
+                .bind(&ex.created_at)
+                .bind(ex.estimated_one_rm)
+                .bind(&ex.current_pr_date)
+                .bind(ex.last_updated);
+        }
+        q.execute(&mut **tx).await?;
+    }
+    Ok(())
+}
+
+async fn insert_sets_batched(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    sets: &[FlatExerciseSet<'_>],
+    merge: bool,
+) -> Result<()> {
+    const COLS: usize = 11;
+    for chunk in sets.chunks(batch_size(COLS)) {
+        let values = vec!["(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"; chunk.len()].join(",");
+        let sql = if merge {
+            format!(
+                "INSERT INTO exercise_sets \
+                 (id, session_exercise_id, weight, reps, rpe, rm_percent, notes, \
+                  timestamp, ignore_for_one_rm, bodyweight, last_updated) \
+                 VALUES {values} \
+                 ON CONFLICT(id) DO UPDATE SET \
+                   session_exercise_id = excluded.session_exercise_id, \
+                   weight = excluded.weight, \
+                   reps = excluded.reps, \
+                   rpe = excluded.rpe, \
+                   rm_percent = excluded.rm_percent, \
+                   notes = excluded.notes, \
+                   timestamp = excluded.timestamp, \
+                   ignore_for_one_rm = excluded.ignore_for_one_rm, \
+                   bodyweight = excluded.bodyweight, \
+                   last_updated = excluded.last_updated \
+                 WHERE excluded.last_updated > exercise_sets.last_updated"
+            )
+        } else {
+            format!(
+                "INSERT OR REPLACE INTO exercise_sets \
+                 (id, session_exercise_id, weight, reps, rpe, rm_percent, notes, \
+                  timestamp, ignore_for_one_rm, bodyweight, last_updated) \
+                 VALUES {values}"
+            )
+        };
+        let mut q = query(&sql);
+        for row in chunk {
+            let set = row.set;
+            q = q
+                .bind(&set.id)
+                .bind(row.session_exercise_id)
+                .bind(set.weight)
+                .bind(set.reps)
+                .bind(set.rpe)
+                .bind(set.rm_percent)
+                .bind(&set.notes)
+                .bind(&set.timestamp)
+                .bind(set.ignore_for_one_rm as i32)
+                .bind(set.bodyweight as i32)
+                .bind(set.last_updated);
+        }
+        q.execute(&mut **tx).await?;
+    }
+    Ok(())
+}
+
+async fn insert_personal_records_batched(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    prs: &[PersonalRecord],
+) -> Result<()> {
+    const COLS: usize = 5;
+    for chunk in prs.chunks(batch_size(COLS)) {
+        let values = vec!["(?, ?, ?, ?, ?)"; chunk.len()].join(",");
+        let sql = format!(
+            "INSERT OR REPLACE INTO personal_records \
+             (exercise_id, date, weight, reps, estimated_1rm) \
+             VALUES {values}"
+        );
+        let mut q = query(&sql);
+        for pr in chunk {
+            q = q
+                .bind(&pr.exercise_id)
+                .bind(&pr.date)
+                .bind(pr.weight)
+                .bind(pr.reps)
+                .bind(pr.estimated_1rm);
+        }
+        q.execute(&mut **tx).await?;
+    }
+    Ok(())
+}
+
+async fn insert_measurements_batched(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    measurements: &[Measurement],
+) -> Result<()> {
+    const COLS: usize = 6;
+    for chunk in measurements.chunks(batch_size(COLS)) {
+        let values = vec!["(?, ?, ?, ?, ?, ?)"; chunk.len()].join(",");
+        let sql = format!(
+            "INSERT OR REPLACE INTO measurements \
+             (id, kind, value, unit, timestamp, notes) \
+             VALUES {values}"
+        );
+        let mut q = query(&sql);
+        for m in chunk {
+            q = q
+                .bind(&m.id)
+                .bind(&m.kind)
+                .bind(m.value)
+                .bind(&m.unit)
+                .bind(&m.timestamp)
+                .bind(&m.notes);
+        }
+        q.execute(&mut **tx).await?;
+    }
+    Ok(())
+}
+
+async fn import_db(
+    pool: &SqlitePool,
+    file_path: &str,
+    window_hours: i64,
+    merge: bool,
+) -> Result<usize> {
     // Read and parse the TOML file
     let toml_str = fs::read_to_string(file_path)?;
     let dump: DatabaseDump = toml::from_str(&toml_str)?;
+    verify_checksum(&dump)?;
+    let dump = migrate_dump(dump)?;
+
+    let (duplicate_set_ids, duplicates_skipped) = find_duplicate_sets(&dump.sessions, window_hours);
 
     // Start a transaction
     let mut tx = pool.begin().await?;
+    let mut timings: Vec<(&str, usize, std::time::Duration)> = Vec::new();
 
-    // Import exercises
-    for ex in dump.exercises {
-        query(
-            r#"
-            INSERT OR REPLACE INTO exercises 
-            (id, name, primary_muscle, description, created_at, estimated_one_rm, current_pr_date)
-            VALUES (?, ?, ?, ?, ?, ?, ?)
-            "#
-        )
-        .bind(&ex.id)
-        .bind(&ex.name)
-        .bind(&ex.primary_muscle)
-        .bind(&ex.description)
-        .bind(&ex.created_at)
-        .bind(ex.estimated_one_rm)
-        .bind(&ex.current_pr_date)
-        .execute(&mut *tx)
-        .await?;
-    }
+    // Import exercises, batched.
+    let t0 = Instant::now();
+    let exercise_count = dump.exercises.len();
+    insert_exercises_batched(&mut tx, &dump.exercises, merge).await?;
+    timings.push(("exercises", exercise_count, t0.elapsed()));
 
     // Import programs with their blocks and exercises
     for prog in dump.programs {
@@ -528,14 +1206,37 @@ async fn import_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
 
             // Insert program exercises
             for ex in block.exercises {
-                query(
+                let sql = if merge {
+                    r#"
+                    INSERT INTO program_exercises
+                    (id, program_block_id, exercise_id, sets, reps, target_rpe,
+                     target_rm_percent, notes, program_1rm, technique, technique_group, order_index, last_updated)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    ON CONFLICT(id) DO UPDATE SET
+                        program_block_id = excluded.program_block_id,
+                        exercise_id = excluded.exercise_id,
+                        sets = excluded.sets,
+                        reps = excluded.reps,
+                        target_rpe = excluded.target_rpe,
+                        target_rm_percent = excluded.target_rm_percent,
+                        notes = excluded.notes,
+                        program_1rm = excluded.program_1rm,
+                        technique = excluded.technique,
+                        technique_group = excluded.technique_group,
+                        order_index = excluded.order_index,
+                        last_updated = excluded.last_updated
+                    WHERE excluded.last_updated > program_exercises.last_updated
+                    "#
+                } else {
                     r#"
-                    INSERT OR REPLACE INTO program_exercises 
-                    (id, program_block_id, exercise_id, sets, reps, target_rpe, 
-                     target_rm_percent, notes, program_1rm, technique, technique_group, order_index)
-                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    INSERT OR REPLACE INTO program_exercises
+                    (id, program_block_id, exercise_id, sets, reps, target_rpe,
+                     target_rm_percent, notes, program_1rm, technique, technique_group, order_index, last_updated)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                     "#
-                )
+                };
+
+                query(sql)
                 .bind(&ex.id)
                 .bind(&block.id)
                 .bind(&ex.exercise_id)
@@ -548,32 +1249,54 @@ async fn import_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
                 .bind(&ex.technique)
                 .bind(ex.technique_group)
                 .bind(ex.order_index)
+                .bind(ex.last_updated)
                 .execute(&mut *tx)
                 .await?;
             }
         }
     }
 
-    // Import sessions with their exercises and sets
-    for sess in dump.sessions {
+    // Import sessions and their exercises (few enough rows per dump that a
+    // row-at-a-time insert is fine); flatten every set into `flat_sets` along
+    // the way so the potentially huge `exercise_sets` table can be inserted
+    // in one batched pass below instead of one `INSERT` per set.
+    let t0 = Instant::now();
+    let session_count = dump.sessions.len();
+    let mut flat_sets: Vec<FlatExerciseSet> = Vec::new();
+    for sess in &dump.sessions {
         // Insert session
-        query(
+        let sess_sql = if merge {
             r#"
-            INSERT OR REPLACE INTO training_sessions 
-            (id, program_block_id, start_time, end_time, notes)
-            VALUES (?, ?, ?, ?, ?)
+            INSERT INTO training_sessions
+            (id, program_block_id, start_time, end_time, notes, last_updated)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                program_block_id = excluded.program_block_id,
+                start_time = excluded.start_time,
+                end_time = excluded.end_time,
+                notes = excluded.notes,
+                last_updated = excluded.last_updated
+            WHERE excluded.last_updated > training_sessions.last_updated
             "#
-        )
+        } else {
+            r#"
+            INSERT OR REPLACE INTO training_sessions
+            (id, program_block_id, start_time, end_time, notes, last_updated)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#
+        };
+        query(sess_sql)
         .bind(&sess.id)
         .bind(&sess.program_block_id)
         .bind(&sess.start_time)
         .bind(&sess.end_time)
         .bind(&sess.notes)
+        .bind(sess.last_updated)
         .execute(&mut *tx)
         .await?;
 
-        // Insert session exercises and their sets
-        for ex in sess.exercises {
+        // Insert session exercises, collecting their sets for later.
+        for ex in &sess.exercises {
             query(
                 r#"
                 INSERT OR REPLACE INTO training_session_exercises
@@ -588,50 +1311,31 @@ async fn import_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
             .execute(&mut *tx)
             .await?;
 
-            // Insert sets
-            for set in ex.sets {
-                query(
-                    r#"
-                    INSERT OR REPLACE INTO exercise_sets
-                    (id, session_exercise_id, weight, reps, rpe, rm_percent, notes,
-                     timestamp, ignore_for_one_rm, bodyweight)
-                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-                    "#
-                )
-                .bind(&set.id)
-                .bind(&ex.id)
-                .bind(set.weight)
-                .bind(set.reps)
-                .bind(set.rpe)
-                .bind(set.rm_percent)
-                .bind(&set.notes)
-                .bind(&set.timestamp)
-                .bind(set.ignore_for_one_rm as i32)
-                .bind(set.bodyweight as i32)
-                .execute(&mut *tx)
-                .await?;
+            for set in &ex.sets {
+                // Skip exact repeats flagged by `find_duplicate_sets`.
+                if duplicate_set_ids.contains(&set.id) {
+                    continue;
+                }
+                flat_sets.push(FlatExerciseSet {
+                    session_exercise_id: &ex.id,
+                    set,
+                });
             }
         }
     }
+    timings.push(("sessions", session_count, t0.elapsed()));
+
+    let t0 = Instant::now();
+    let set_count = flat_sets.len();
+    insert_sets_batched(&mut tx, &flat_sets, merge).await?;
+    timings.push(("exercise_sets", set_count, t0.elapsed()));
 
     // Import personal records if there are any in the dump
+    let t0 = Instant::now();
     if !dump.personal_records.is_empty() {
-        for pr in dump.personal_records {
-            query(
-                r#"
-                INSERT OR REPLACE INTO personal_records
-                (exercise_id, date, weight, reps, estimated_1rm)
-                VALUES (?, ?, ?, ?, ?)
-                "#
-            )
-            .bind(&pr.exercise_id)
-            .bind(&pr.date)
-            .bind(pr.weight)
-            .bind(pr.reps)
-            .bind(pr.estimated_1rm)
-            .execute(&mut *tx)
-            .await?;
-        }
+        let pr_count = dump.personal_records.len();
+        insert_personal_records_batched(&mut tx, &dump.personal_records).await?;
+        timings.push(("personal_records", pr_count, t0.elapsed()));
     } else {
         // If no PRs in the dump, calculate them from session sets
         // First, clear any existing PRs
@@ -728,9 +1432,22 @@ async fn import_db(pool: &SqlitePool, file_path: &str) -> Result<()> {
         }
     }
 
+    // Import body measurements, batched
+    if !dump.measurements.is_empty() {
+        let t0 = Instant::now();
+        let measurement_count = dump.measurements.len();
+        insert_measurements_batched(&mut tx, &dump.measurements).await?;
+        timings.push(("measurements", measurement_count, t0.elapsed()));
+    }
+
     // Commit all changes
     tx.commit().await?;
 
-    Ok(())
+    println!("{}", "Import timing:".cyan().bold());
+    for (table, rows, elapsed) in timings {
+        println!("  {:<17} {:>7} rows in {:>8.2?}", table, rows, elapsed);
+    }
+
+    Ok(duplicates_skipped)
 }
 
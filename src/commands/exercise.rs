@@ -1,9 +1,15 @@
-use std::{collections::BTreeSet, path::Path};
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+};
 
 use crate::{
     OutputFmt,
     cli::ExerciseCmd,
-    types::{ALLOWED_MUSCLES, ExerciseImport, best_muscle_suggestions, cannonical_muscle, emit},
+    types::{
+        ALLOWED_MUSCLES, Config, ExerciseImport, OneRmFormula, WeightUnit, best_muscle_suggestions,
+        cannonical_muscle, emit,
+    },
 };
 use anyhow::{Context, Result};
 use colored::Colorize;
@@ -19,6 +25,37 @@ struct ExJson {
     created_at: String,
 }
 
+#[derive(Serialize)]
+struct ExShowJson {
+    name: String,
+    primary_muscle: String,
+    unit: WeightUnit,
+    formula: OneRmFormula,
+    instructions: Vec<String>,
+    pr_weight: Option<f32>,
+    pr_reps: Option<i32>,
+    pr_1rm: Option<f32>,
+    current_tonnage: Option<f64>,
+    top_sets: Vec<ExSetJson>,
+    last_sets: Vec<ExLastSetJson>,
+}
+
+#[derive(Serialize)]
+struct ExSetJson {
+    weight: f32,
+    reps: i32,
+    timestamp: String,
+}
+
+#[derive(Serialize)]
+struct ExLastSetJson {
+    timestamp: String,
+    weight: f32,
+    reps: i32,
+    rpe: Option<f32>,
+    is_pr: bool,
+}
+
 fn plain_len(s: &str) -> usize {
     let bytes = s.as_bytes();
     let mut i = 0;
@@ -40,7 +77,74 @@ fn plain_len(s: &str) -> usize {
     return count;
 }
 
-pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt) -> Result<()> {
+/// Closest exercise name to `input` (Jaro–Winkler, same thresholds as
+/// [`crate::types::best_muscle_suggestions`]), for a "did you mean?" prompt
+/// when [`resolve_exercise_id`] comes up empty.
+pub(crate) async fn suggest_exercise_name(pool: &SqlitePool, input: &str) -> Result<Option<String>> {
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM exercises")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(crate::types::closest_match(
+        input,
+        names.iter().map(|n| (n.clone(), n.as_str())),
+    ))
+}
+
+/// Prints the standard "no exercise named" error, appending a "did you
+/// mean?" suggestion when one clears the confidence threshold.
+pub(crate) async fn print_no_exercise_named(pool: &SqlitePool, exercise: &str) -> Result<()> {
+    match suggest_exercise_name(pool, exercise).await? {
+        Some(sug) => println!(
+            "{} no exercise named `{}` -- did you mean: `{}`?",
+            "error:".red().bold(),
+            exercise,
+            sug.green()
+        ),
+        None => println!("{} no exercise named `{}`", "error:".red().bold(), exercise),
+    }
+    Ok(())
+}
+
+/// Resolve an exercise index or exact name to its id.
+pub(crate) async fn resolve_exercise_id(pool: &SqlitePool, exercise: &str) -> Result<Option<String>> {
+    if let Ok(idx) = exercise.parse::<i64>() {
+        Ok(
+            sqlx::query_scalar("SELECT id FROM exercises WHERE idx = ?")
+                .bind(idx)
+                .fetch_optional(pool)
+                .await?,
+        )
+    } else {
+        Ok(
+            sqlx::query_scalar("SELECT id FROM exercises WHERE name = ?")
+                .bind(exercise)
+                .fetch_optional(pool)
+                .await?,
+        )
+    }
+}
+
+#[derive(Serialize)]
+struct RecordEventJson {
+    timestamp: String,
+    weight: f32,
+    reps: i32,
+    rep_bucket: i32,
+    previous_best: Option<f32>,
+    delta: Option<f32>,
+}
+
+#[derive(Serialize)]
+struct RecordsJson {
+    name: String,
+    unit: WeightUnit,
+    events: Vec<RecordEventJson>,
+    standing_best_by_reps: Vec<(i32, f32)>,
+    longest_pr_streak: u32,
+}
+
+pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt, cfg: &Config) -> Result<()> {
     match cmd {
         ExerciseCmd::Add { name, muscle, desc } => {
             let res = sqlx::query(
@@ -81,7 +185,122 @@ pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resu
             }
         }
 
-        ExerciseCmd::Import { file } => {
+        ExerciseCmd::Sync { source } => {
+            let toml_str = if source.starts_with("http://") || source.starts_with("https://") {
+                reqwest::get(&source)
+                    .await
+                    .with_context(|| format!("Could not fetch `{}`", source))?
+                    .text()
+                    .await
+                    .with_context(|| format!("Could not read response body from `{}`", source))?
+            } else {
+                tokio::fs::read_to_string(&source)
+                    .await
+                    .with_context(|| format!("Could not read file: `{}`", source))?
+            };
+
+            let import: ExerciseImport = toml::from_str(&toml_str)
+                .context("Failed to parse TOML: Expected `[[exercise]] entries`")?;
+
+            let mut new = 0;
+            let mut updated = 0;
+            let mut unchanged = 0;
+            let mut skipped = 0;
+            let mut unknowns: BTreeSet<String> = BTreeSet::new();
+            let allowed_muscles = cfg.allowed_muscles();
+
+            for ex in import.exercise {
+                let musc = match cannonical_muscle(&ex.primary_muscle, &allowed_muscles) {
+                    Some(m) => m,
+                    None => {
+                        println!(
+                            "{} `{}` skipped – unknown muscle `{}`",
+                            "warning:".yellow().bold(),
+                            ex.name,
+                            ex.primary_muscle
+                        );
+                        skipped += 1;
+                        unknowns.insert(ex.primary_muscle);
+                        continue;
+                    }
+                };
+
+                let desc = ex.description.unwrap_or_default();
+
+                let existing: Option<(String, String)> = sqlx::query_as(
+                    "SELECT primary_muscle, COALESCE(description, '') FROM exercises WHERE name = ?",
+                )
+                .bind(&ex.name)
+                .fetch_optional(pool)
+                .await?;
+
+                match existing {
+                    None => {
+                        sqlx::query(
+                            r#"
+                            INSERT INTO exercises
+                              (id, name, primary_muscle, description, created_at)
+                            VALUES (?1, ?2, ?3, ?4, datetime('now'))
+                            "#,
+                        )
+                        .bind(uuid::Uuid::new_v4().to_string())
+                        .bind(&ex.name)
+                        .bind(&musc)
+                        .bind(&desc)
+                        .execute(pool)
+                        .await
+                        .with_context(|| format!("DB error inserting `{}`", ex.name))?;
+
+                        new += 1;
+                        println!("{} `{}`", "new:".green().bold(), ex.name);
+                    }
+                    Some((old_muscle, old_desc)) if old_muscle != musc || old_desc != desc => {
+                        sqlx::query(
+                            "UPDATE exercises SET primary_muscle = ?, description = ?, last_updated = unixepoch() WHERE name = ?",
+                        )
+                        .bind(&musc)
+                        .bind(&desc)
+                        .bind(&ex.name)
+                        .execute(pool)
+                        .await
+                        .with_context(|| format!("DB error updating `{}`", ex.name))?;
+
+                        updated += 1;
+                        println!("{} `{}`", "updated:".blue().bold(), ex.name);
+                    }
+                    Some(_) => {
+                        unchanged += 1;
+                    }
+                }
+            }
+
+            sqlx::query(
+                r#"
+                INSERT INTO exercise_sources (name, last_sync)
+                VALUES (?1, datetime('now'))
+                ON CONFLICT(name) DO UPDATE SET last_sync = excluded.last_sync
+                "#,
+            )
+            .bind(&source)
+            .execute(pool)
+            .await?;
+
+            println!(
+                "\n{} {} new, {} updated, {} unchanged, {} skipped",
+                "Summary:".cyan().bold(),
+                new,
+                updated,
+                unchanged,
+                skipped
+            );
+
+            if !unknowns.is_empty() {
+                let bad = unknowns.into_iter().collect::<Vec<_>>().join(", ");
+                println!("{} {}", "Unknown muscles:".yellow().bold(), bad);
+            }
+        }
+
+        ExerciseCmd::Import { file, dry_run } => {
             let path = Path::new(&file);
             let toml_str = tokio::fs::read_to_string(path)
                 .await
@@ -103,10 +322,16 @@ pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resu
                 return Ok(());
             }
 
+            // Run every insert against a single transaction so a mid-file DB
+            // error rolls back cleanly instead of leaving a half-populated
+            // library. --dry-run reuses the same path but never commits.
+            let mut tx = pool.begin().await?;
+
             // Loop and insert/ignore.
             let mut inserted = 0;
             let mut skipped = 0;
             let mut unknowns: BTreeSet<String> = BTreeSet::new();
+            let allowed_muscles = cfg.allowed_muscles();
 
             for ex in import.exercise {
                 assert!(
@@ -115,13 +340,14 @@ pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resu
                 );
 
                 // Validate the `primary_muscle` field.
-                let musc = match cannonical_muscle(&ex.primary_muscle) {
+                let musc = match cannonical_muscle(&ex.primary_muscle, &allowed_muscles) {
                     Some(m) => m,
                     None => {
                         // Did you mean?
-                        if let Some(sug) =
-                            best_muscle_suggestions(&ex.primary_muscle.to_ascii_lowercase())
-                        {
+                        if let Some(sug) = best_muscle_suggestions(
+                            &ex.primary_muscle.to_ascii_lowercase(),
+                            &allowed_muscles,
+                        ) {
                             println!(
                                 "{} `{}` skipped – unknown muscle `{}` -- did you mean: `{}`?",
                                 "warning:".yellow().bold(),
@@ -145,6 +371,7 @@ pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resu
                 };
 
                 let desc = ex.description.unwrap_or_default();
+                let exercise_id = uuid::Uuid::new_v4().to_string();
 
                 let res = sqlx::query(
                     r#"
@@ -153,11 +380,11 @@ pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resu
                     VALUES (?1, ?2, ?3, ?4, datetime('now'))
                     "#,
                 )
-                .bind(uuid::Uuid::new_v4().to_string())
+                .bind(&exercise_id)
                 .bind(&ex.name)
                 .bind(&musc)
                 .bind(desc)
-                .execute(pool)
+                .execute(&mut *tx)
                 .await
                 .with_context(|| format!("DB error inserting `{}`", ex.name))?;
 
@@ -169,6 +396,22 @@ pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resu
                 );
 
                 if res.rows_affected() == 1 {
+                    for (i, step) in ex.instructions.iter().enumerate() {
+                        sqlx::query(
+                            r#"
+                            INSERT INTO exercise_instructions (id, exercise_id, step_no, text)
+                            VALUES (?1, ?2, ?3, ?4)
+                            "#,
+                        )
+                        .bind(uuid::Uuid::new_v4().to_string())
+                        .bind(&exercise_id)
+                        .bind(i as i64 + 1)
+                        .bind(step)
+                        .execute(&mut *tx)
+                        .await
+                        .with_context(|| format!("DB error inserting instructions for `{}`", ex.name))?;
+                    }
+
                     inserted += 1;
                     println!("{} `{}`", "ok:".green().bold(), ex.name);
                 } else {
@@ -177,13 +420,23 @@ pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resu
                 }
             }
 
-            // Summary.
-            println!(
-                "\n{} {} inserted, {} skipped",
-                "Summary:".cyan().bold(),
-                inserted,
-                skipped
-            );
+            if dry_run {
+                tx.rollback().await?;
+                println!(
+                    "\n{} {} would insert, {} would skip (--dry-run, nothing written)",
+                    "Summary:".cyan().bold(),
+                    inserted,
+                    skipped
+                );
+            } else {
+                tx.commit().await?;
+                println!(
+                    "\n{} {} inserted, {} skipped",
+                    "Summary:".cyan().bold(),
+                    inserted,
+                    skipped
+                );
+            }
 
             // Print allowed list if at least one exercise is unknown.
             if !unknowns.is_empty() {
@@ -326,9 +579,11 @@ pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resu
             println!("{} deleted exercise `{}`", "ok:".green().bold(), name);
         }
 
-        ExerciseCmd::Show { exercise } => {
+        ExerciseCmd::Show { exercise, unit, formula, .. } => {
+            let unit = unit.unwrap_or_else(|| cfg.weight_unit());
+            let formula = formula.unwrap_or_else(|| cfg.one_rm_formula());
             let exercise = exercise.join(" ");
-            
+
             // Resolve exercise to its ID
             let exercise_id: String = if let Ok(idx) = exercise.parse::<i64>() {
                 // User passed a number - look up by idx
@@ -352,7 +607,7 @@ pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resu
                 {
                     Some(id) => id,
                     None => {
-                        println!("{} no exercise named `{}`", "error:".red().bold(), exercise);
+                        print_no_exercise_named(pool, &exercise).await?;
                         return Ok(());
                     }
                 }
@@ -366,6 +621,14 @@ pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resu
             .fetch_one(pool)
             .await?;
 
+            // Get structured how-to steps, in order.
+            let instructions: Vec<String> = sqlx::query_scalar(
+                "SELECT text FROM exercise_instructions WHERE exercise_id = ? ORDER BY step_no",
+            )
+            .bind(&exercise_id)
+            .fetch_all(pool)
+            .await?;
+
             // Get last performed date and total sessions
             let (last_performed, total_sessions): (Option<String>, i64) = sqlx::query_as(
                 r#"
@@ -386,68 +649,64 @@ pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resu
             .fetch_one(pool)
             .await?;
 
-            // Get current PR info
-            let (pr_weight, pr_reps, pr_date, pr_1rm): (Option<f32>, Option<i32>, Option<String>, Option<f32>) = sqlx::query_as(
+            // Fetch every qualifying set's bodyweight-adjusted load; the 1RM
+            // estimate itself is computed in Rust (via `formula`) so every
+            // call site below — PR, 30-day change, top 5, last 10 — agrees.
+            let all_loads: Vec<(f32, i32, String, f32)> = sqlx::query_as(
                 r#"
-                WITH all_sets AS (
-                    SELECT 
-                        es.weight,
-                        es.reps,
-                        es.timestamp,
-                        CASE 
-                            WHEN es.bodyweight = 1 THEN 0
-                            ELSE CAST(es.weight AS REAL) * (1 + CAST(es.reps AS REAL) / 30)
-                        END as estimated_1rm
-                    FROM exercise_sets es
-                    JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
-                    WHERE tse.exercise_id = ?
-                    AND es.weight > 0
-                )
-                SELECT 
-                    weight,
-                    reps,
-                    timestamp,
-                    estimated_1rm
-                FROM all_sets
-                ORDER BY estimated_1rm DESC, weight DESC, reps DESC
-                LIMIT 1
+                SELECT
+                    es.weight,
+                    es.reps,
+                    es.timestamp,
+                    CASE
+                        WHEN es.bodyweight = 1 THEN
+                            COALESCE((
+                                SELECT m.value FROM measurements m
+                                WHERE m.kind = 'bodyweight'
+                                ORDER BY ABS(julianday(m.timestamp) - julianday(es.timestamp))
+                                LIMIT 1
+                            ), 0) + CAST(es.weight AS REAL)
+                        ELSE CAST(es.weight AS REAL)
+                    END as load
+                FROM exercise_sets es
+                JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+                WHERE tse.exercise_id = ?
+                AND (es.weight > 0 OR es.bodyweight = 1)
                 "#,
             )
             .bind(&exercise_id)
-            .fetch_optional(pool)
-            .await?
-            .unwrap_or((None, None, None, None));
+            .fetch_all(pool)
+            .await?;
+
+            // (weight, reps, timestamp, estimated_1rm), ranked best-first.
+            let mut ranked: Vec<(f32, i32, String, f32)> = all_loads
+                .iter()
+                .map(|(w, r, t, load)| (*w, *r, t.clone(), formula.estimate(*load, *r)))
+                .collect();
+            ranked.sort_by(|a, b| {
+                b.3.partial_cmp(&a.3)
+                    .unwrap()
+                    .then(b.0.partial_cmp(&a.0).unwrap())
+                    .then(b.1.cmp(&a.1))
+            });
+
+            let (pr_weight, pr_reps, pr_date, pr_1rm) = match ranked.first() {
+                Some((w, r, t, e1rm)) => (Some(*w), Some(*r), Some(t.clone()), Some(*e1rm)),
+                None => (None, None, None, None),
+            };
 
             // Get 30-day PR change
-            let (prev_pr_1rm, _prev_pr_date): (Option<f32>, Option<String>) = sqlx::query_as(
-                r#"
-                WITH all_sets AS (
-                    SELECT 
-                        es.weight,
-                        es.reps,
-                        es.timestamp,
-                        CASE 
-                            WHEN es.bodyweight = 1 THEN 0
-                            ELSE CAST(es.weight AS REAL) * (1 + CAST(es.reps AS REAL) / 30)
-                        END as estimated_1rm
-                    FROM exercise_sets es
-                    JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
-                    WHERE tse.exercise_id = ?
-                    AND es.weight > 0
-                    AND es.timestamp < datetime('now', '-30 days')
-                )
-                SELECT 
-                    estimated_1rm,
-                    timestamp
-                FROM all_sets
-                ORDER BY estimated_1rm DESC, weight DESC, reps DESC
-                LIMIT 1
-                "#,
-            )
-            .bind(&exercise_id)
-            .fetch_optional(pool)
-            .await?
-            .unwrap_or((None, None));
+            let thirty_days_ago: String =
+                sqlx::query_scalar("SELECT datetime('now', '-30 days')")
+                    .fetch_one(pool)
+                    .await?;
+            let prev_pr_1rm: Option<f32> = ranked
+                .iter()
+                .filter(|(_, _, t, _)| *t < thirty_days_ago)
+                .map(|(_, _, _, e1rm)| *e1rm)
+                .fold(None, |acc: Option<f32>, e1rm| {
+                    Some(acc.map_or(e1rm, |a| a.max(e1rm)))
+                });
 
             // Get 30-day tonnage
             let (current_tonnage, prev_tonnage): (Option<f64>, Option<f64>) = sqlx::query_as(
@@ -518,185 +777,440 @@ pub async fn handle(cmd: ExerciseCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resu
             .fetch_one(pool)
             .await?;
 
-            // Get top 5 heaviest sets
-            let top_sets: Vec<(f32, i32, String)> = sqlx::query_as(
+            // Top 5 heaviest sets, already ranked by the chosen formula.
+            let top_sets: Vec<(f32, i32, String)> = ranked
+                .iter()
+                .take(5)
+                .map(|(w, r, t, _)| (*w, *r, t.clone()))
+                .collect();
+
+            // Last 10 sets with rpe + PR flag. The global PR is whichever set
+            // produced `pr_date`/`pr_weight`/`pr_reps` above.
+            let rpe_by_timestamp: HashMap<String, Option<f32>> = sqlx::query_as::<_, (String, Option<f32>)>(
                 r#"
-                WITH set_volumes AS (
-                    SELECT 
-                        CAST(weight AS REAL) as weight,
-                        CAST(reps AS INTEGER) as reps,
-                        timestamp,
-                        CASE 
-                            WHEN bodyweight = 1 THEN 0
-                            ELSE CAST(weight AS REAL) * (1 + CAST(reps AS REAL) / 30)
-                        END as estimated_1rm
-                    FROM exercise_sets es
-                    JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
-                    WHERE tse.exercise_id = ?
-                    AND weight > 0
-                )
-                SELECT 
-                    weight,
-                    reps,
-                    timestamp
-                FROM set_volumes
-                ORDER BY estimated_1rm DESC, weight DESC, reps DESC
-                LIMIT 5
+                SELECT es.timestamp, CAST(es.rpe AS REAL) as rpe
+                FROM exercise_sets es
+                JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+                WHERE tse.exercise_id = ?
                 "#,
             )
             .bind(&exercise_id)
             .fetch_all(pool)
-            .await?;
+            .await?
+            .into_iter()
+            .collect();
+
+            let mut by_recency = all_loads.clone();
+            by_recency.sort_by(|a, b| b.2.cmp(&a.2));
+
+            let last_sets: Vec<(String, f32, i32, Option<f32>, bool)> = by_recency
+                .into_iter()
+                .take(10)
+                .map(|(w, r, t, _)| {
+                    let rpe = rpe_by_timestamp.get(&t).copied().flatten();
+                    let is_pr = pr_date.as_deref() == Some(t.as_str())
+                        && pr_weight == Some(w)
+                        && pr_reps == Some(r);
+                    (t, w, r, rpe, is_pr)
+                })
+                .collect();
+
+            // Convert every weight-bearing stat into the chosen display unit.
+            let pr_weight_disp = pr_weight.map(|w| unit.from_kg(w));
+            let pr_1rm_disp = pr_1rm.map(|w| unit.from_kg(w));
+            let current_tonnage_disp = current_tonnage.map(|t| unit.from_kg(t as f32) as f64);
+            let prev_tonnage_disp = prev_tonnage.map(|t| unit.from_kg(t as f32) as f64);
+
+            let top_sets_json: Vec<ExSetJson> = top_sets
+                .iter()
+                .map(|(w, r, t)| ExSetJson {
+                    weight: unit.from_kg(*w),
+                    reps: *r,
+                    timestamp: t.clone(),
+                })
+                .collect();
+
+            let last_sets_json: Vec<ExLastSetJson> = last_sets
+                .iter()
+                .map(|(t, w, r, rpe, is_pr)| ExLastSetJson {
+                    timestamp: t.clone(),
+                    weight: unit.from_kg(*w),
+                    reps: *r,
+                    rpe: *rpe,
+                    is_pr: *is_pr,
+                })
+                .collect();
+
+            let show_json = ExShowJson {
+                name: name.clone(),
+                primary_muscle: muscle.clone(),
+                unit,
+                formula,
+                instructions: instructions.clone(),
+                pr_weight: pr_weight_disp,
+                pr_reps,
+                pr_1rm: pr_1rm_disp,
+                current_tonnage: current_tonnage_disp,
+                top_sets: top_sets_json,
+                last_sets: last_sets_json,
+            };
+
+            let u = unit.suffix();
+
+            emit(fmt, &show_json, || {
+                // Print exercise header
+                println!(
+                    "{}: {} ({})",
+                    "Exercise".cyan().bold(),
+                    name.bold(),
+                    muscle.yellow()
+                );
+                println!(
+                    "{}: {} | {}: {} | {}: {}",
+                    "Added".dimmed(),
+                    &created_at[..10],
+                    "Last performed".dimmed(),
+                    last_performed
+                        .clone()
+                        .map_or("never".to_string(), |d| crate::types::format_date(&d[..10], cfg.time_format())),
+                    "Total sessions".dimmed(),
+                    total_sessions
+                );
+                println!("{}: {}", "1RM formula".dimmed(), formula);
+
+                if !instructions.is_empty() {
+                    println!();
+                    println!("{}", "Instructions".cyan().bold());
+                    for (i, step) in instructions.iter().enumerate() {
+                        println!("  {}. {}", i + 1, step);
+                    }
+                }
+                println!();
+
+                // Print PR info
+                if let (Some(w), Some(r), Some(d), Some(rm)) =
+                    (pr_weight_disp, pr_reps, &pr_date, pr_1rm_disp)
+                {
+                    println!(
+                        "{}: {}{} × {}  (1 RM est: {}{})  on {}",
+                        "Current PR".cyan().bold(),
+                        w,
+                        u,
+                        r,
+                        rm.round(),
+                        u,
+                        crate::types::format_date(&d[..10], cfg.time_format())
+                    );
+                }
+
+                // Print 30-day changes
+                if let Some(prev_rm) = prev_pr_1rm {
+                    let prev_rm_disp = unit.from_kg(prev_rm);
+                    let diff = pr_1rm_disp.unwrap_or(0.0) - prev_rm_disp;
+                    let pct = (diff / prev_rm_disp) * 100.0;
+                    let arrow = if diff > 0.0 { "▲" } else { "▼" };
+                    println!(
+                        "{} {} {:.1} {}  ({:+.1} %)",
+                        "30-day 1 RM change:".cyan().bold(),
+                        arrow,
+                        diff.abs(),
+                        u,
+                        pct
+                    );
+                }
+
+                if let (Some(curr), Some(prev)) = (current_tonnage_disp, prev_tonnage_disp) {
+                    println!(
+                        "{}: {:.0} {}   (prev 30 d: {:.0} {})",
+                        "30-day tonnage".cyan().bold(),
+                        curr,
+                        u,
+                        prev,
+                        u
+                    );
+                }
+                println!();
+
+                // Print lifetime stats
+                println!(
+                    "{}: {} sets  – {} reps  – {:.0} t",
+                    "Lifetime volume".cyan().bold(),
+                    total_sets,
+                    total_reps,
+                    unit.from_kg(total_tonnage as f32)
+                );
+
+                if let (Some(freq), Some(gap)) = (avg_freq, longest_gap) {
+                    println!(
+                        "{}: {:.1} sessions / week | {}: {} days",
+                        "Avg frequency (8 w)".cyan().bold(),
+                        freq,
+                        "Longest gap".cyan().bold(),
+                        gap
+                    );
+                }
+                println!();
+
+                // Print top 5 heaviest sets
+                println!("{}", "Top 5 heaviest sets".cyan().bold());
+                for (weight, reps, timestamp) in &top_sets {
+                    println!(
+                        "  {}{} × {}   {}",
+                        unit.from_kg(*weight),
+                        u,
+                        reps,
+                        crate::types::format_date(&timestamp[..10], cfg.time_format())
+                    );
+                }
+                println!();
+
+                // Print last 10 sets
+                println!("{}", "Last 10 sets".cyan().bold());
+                for (timestamp, weight, reps, rpe, is_pr) in &last_sets {
+                    let set_info = if *weight == 0.0 {
+                        format!("bw × {}", reps)
+                    } else {
+                        format!("{}{} × {}", unit.from_kg(*weight), u, reps)
+                    };
+
+                    let rpe_info = rpe.map_or(String::new(), |r| format!("   @RPE {}", r));
+                    let pr_mark = if *is_pr {
+                        "   ← PR".green().to_string()
+                    } else {
+                        String::new()
+                    };
+
+                    let set_display = if *is_pr {
+                        set_info.green().to_string()
+                    } else {
+                        set_info
+                    };
+
+                    println!(
+                        "  {}  {}{}{}",
+                        crate::types::format_date(&timestamp[..10], cfg.time_format()),
+                        set_display,
+                        rpe_info.dimmed(),
+                        pr_mark
+                    );
+                }
+            });
+        }
+
+        ExerciseCmd::Records { exercise } => {
+            let exercise = exercise.join(" ");
+            let exercise_id = match resolve_exercise_id(pool, &exercise).await? {
+                Some(id) => id,
+                None => {
+                    print_no_exercise_named(pool, &exercise).await?;
+                    return Ok(());
+                }
+            };
 
-            // Get last 10 sets with PR information
-            let last_sets: Vec<(String, f32, i32, Option<f32>, bool)> = sqlx::query_as(
+            let name: String = sqlx::query_scalar("SELECT name FROM exercises WHERE id = ?")
+                .bind(&exercise_id)
+                .fetch_one(pool)
+                .await?;
+
+            let unit = cfg.weight_unit();
+            let formula = cfg.one_rm_formula();
+
+            // Chronological load (bodyweight-adjusted) for every set.
+            let sets: Vec<(String, f32, i32, f32)> = sqlx::query_as(
                 r#"
-                WITH set_info AS (
-                    SELECT 
-                        es.timestamp,
-                        CAST(es.weight AS REAL) as weight,
-                        CAST(es.reps AS INTEGER) as reps,
-                        CAST(es.rpe AS REAL) as rpe,
-                        CASE 
-                            WHEN es.bodyweight = 1 THEN 0
-                            ELSE CAST(es.weight AS REAL) * (1 + CAST(es.reps AS REAL) / 30)
-                        END as estimated_1rm,
-                        ROW_NUMBER() OVER (
-                            ORDER BY 
-                                CAST(es.weight AS REAL) * (1 + CAST(es.reps AS REAL) / 30) DESC,
-                                es.timestamp DESC
-                        ) as set_rank
-                    FROM exercise_sets es
-                    JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
-                    WHERE tse.exercise_id = ?
-                    AND es.weight > 0
-                    ORDER BY es.timestamp DESC
-                    LIMIT 10
-                )
-                SELECT 
-                    timestamp,
-                    weight,
-                    reps,
-                    rpe,
-                    set_rank = 1 as is_pr
-                FROM set_info
-                ORDER BY timestamp DESC
+                SELECT
+                    es.timestamp,
+                    es.weight,
+                    es.reps,
+                    CASE
+                        WHEN es.bodyweight = 1 THEN
+                            COALESCE((
+                                SELECT m.value FROM measurements m
+                                WHERE m.kind = 'bodyweight'
+                                ORDER BY ABS(julianday(m.timestamp) - julianday(es.timestamp))
+                                LIMIT 1
+                            ), 0) + CAST(es.weight AS REAL)
+                        ELSE CAST(es.weight AS REAL)
+                    END as load
+                FROM exercise_sets es
+                JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+                WHERE tse.exercise_id = ?
+                AND (es.weight > 0 OR es.bodyweight = 1)
+                ORDER BY es.timestamp ASC
                 "#,
             )
             .bind(&exercise_id)
             .fetch_all(pool)
             .await?;
 
-            // Print exercise header
-            println!(
-                "{}: {} ({})",
-                "Exercise".cyan().bold(),
-                name.bold(),
-                muscle.yellow()
-            );
-            println!(
-                "{}: {} | {}: {} | {}: {}",
-                "Added".dimmed(),
-                &created_at[..10],
-                "Last performed".dimmed(),
-                last_performed.map_or("never".to_string(), |d| d[..10].to_string()),
-                "Total sessions".dimmed(),
-                total_sessions
-            );
-            println!();
+            // Walk chronologically, tracking a running best per rep count and
+            // emitting an event whenever a set beats its rep count's best.
+            let mut best_by_reps: std::collections::BTreeMap<i32, f32> = std::collections::BTreeMap::new();
+            let mut events: Vec<RecordEventJson> = Vec::new();
+            let mut pr_dates: BTreeSet<String> = BTreeSet::new();
 
-            // Print PR info
-            if let (Some(w), Some(r), Some(d), Some(rm)) = (pr_weight, pr_reps, pr_date, pr_1rm) {
-                println!(
-                    "{}: {}kg × {}  (1 RM est: {}kg)  on {}",
-                    "Current PR".cyan().bold(),
-                    w,
-                    r,
-                    rm.round(),
-                    &d[..10]
-                );
-            }
+            for (timestamp, weight, reps, load) in &sets {
+                let estimated = formula.estimate(*load, *reps);
+                let current_best = best_by_reps.get(reps).copied();
 
-            // Print 30-day changes
-            if let (Some(prev_rm), _) = (prev_pr_1rm, _prev_pr_date) {
-                let diff = pr_1rm.unwrap_or(0.0) - prev_rm;
-                let pct = (diff / prev_rm) * 100.0;
-                let arrow = if diff > 0.0 { "▲" } else { "▼" };
-                println!(
-                    "{} {} {:.1} kg  ({:+.1} %)",
-                    "30-day 1 RM change:".cyan().bold(),
-                    arrow,
-                    diff.abs(),
-                    pct
-                );
+                if current_best.map_or(true, |b| estimated > b) {
+                    events.push(RecordEventJson {
+                        timestamp: timestamp.clone(),
+                        weight: *weight,
+                        reps: *reps,
+                        rep_bucket: *reps,
+                        previous_best: current_best,
+                        delta: current_best.map(|b| estimated - b),
+                    });
+                    best_by_reps.insert(*reps, estimated);
+                    pr_dates.insert(timestamp[..10].to_string());
+                }
             }
 
-            if let (Some(curr), Some(prev)) = (current_tonnage, prev_tonnage) {
-                println!(
-                    "{}: {:.0} kg   (prev 30 d: {:.0} kg)",
-                    "30-day tonnage".cyan().bold(),
-                    curr,
-                    prev
-                );
+            // Longest streak of consecutive training sessions that each
+            // produced at least one new PR.
+            let session_dates: BTreeSet<String> = sets
+                .iter()
+                .map(|(t, ..)| t[..10].to_string())
+                .collect();
+            let (mut longest_streak, mut current_streak) = (0u32, 0u32);
+            for date in &session_dates {
+                if pr_dates.contains(date) {
+                    current_streak += 1;
+                    longest_streak = longest_streak.max(current_streak);
+                } else {
+                    current_streak = 0;
+                }
             }
-            println!();
 
-            // Print lifetime stats
-            println!(
-                "{}: {} sets  – {} reps  – {:.0} t",
-                "Lifetime volume".cyan().bold(),
-                total_sets,
-                total_reps,
-                total_tonnage
-            );
+            let standing_best_by_reps: Vec<(i32, f32)> = best_by_reps
+                .iter()
+                .map(|(r, w)| (*r, unit.from_kg(*w)))
+                .collect();
 
-            if let (Some(freq), Some(gap)) = (avg_freq, longest_gap) {
-                println!(
-                    "{}: {:.1} sessions / week | {}: {} days",
-                    "Avg frequency (8 w)".cyan().bold(),
-                    freq,
-                    "Longest gap".cyan().bold(),
-                    gap
-                );
-            }
-            println!();
+            let events_disp: Vec<RecordEventJson> = events
+                .iter()
+                .map(|e| RecordEventJson {
+                    timestamp: e.timestamp.clone(),
+                    weight: unit.from_kg(e.weight),
+                    reps: e.reps,
+                    rep_bucket: e.rep_bucket,
+                    previous_best: e.previous_best.map(|b| unit.from_kg(b)),
+                    delta: e.delta.map(|d| unit.from_kg(d)),
+                })
+                .collect();
 
-            // Print top 5 heaviest sets
-            println!("{}", "Top 5 heaviest sets".cyan().bold());
-            for (weight, reps, timestamp) in top_sets {
-                println!("  {}kg × {}   {}", weight, reps, &timestamp[..10]);
-            }
-            println!();
+            let records_json = RecordsJson {
+                name: name.clone(),
+                unit,
+                events: events_disp,
+                standing_best_by_reps,
+                longest_pr_streak: longest_streak,
+            };
 
-            // Print last 10 sets
-            println!("{}", "Last 10 sets".cyan().bold());
-            for (timestamp, weight, reps, rpe, is_pr) in last_sets {
-                let set_info = if weight == 0.0 {
-                    format!("bw × {}", reps)
-                } else {
-                    format!("{}kg × {}", weight, reps)
-                };
+            emit(fmt, &records_json, || {
+                let u = unit.suffix();
+                println!("{}: {}", "Records".cyan().bold(), name.bold());
+                println!();
 
-                let rpe_info = rpe.map_or(String::new(), |r| format!("   @RPE {}", r));
-                let pr_mark = if is_pr {
-                    "   ← PR".green().to_string()
+                if records_json.events.is_empty() {
+                    println!("{}", "No record-breaking sets yet".dimmed());
                 } else {
-                    String::new()
-                };
+                    for e in &records_json.events {
+                        let prev = match (e.previous_best, e.delta) {
+                            (Some(prev), Some(delta)) => {
+                                format!(" (was {:.1}{}, {:+.1}{})", prev, u, delta, u)
+                            }
+                            _ => " (first set at this rep count)".to_string(),
+                        };
+                        println!(
+                            "  {}  {}{} x {} rep{} est. 1RM {:.1}{}{}",
+                            crate::types::format_date(&e.timestamp[..10], cfg.time_format()),
+                            e.weight,
+                            u,
+                            e.reps,
+                            if e.reps == 1 { "" } else { "s" },
+                            formula.estimate(e.weight, e.reps),
+                            u,
+                            prev.dimmed()
+                        );
+                    }
+                }
 
-                let set_display = if is_pr {
-                    set_info.green().to_string()
-                } else {
-                    set_info
-                };
+                println!();
+                println!("{}", "Current standing bests".cyan().bold());
+                for (reps, best) in &records_json.standing_best_by_reps {
+                    println!("  {} rep{}: {:.1}{}", reps, if *reps == 1 { "" } else { "s" }, best, u);
+                }
 
+                println!();
                 println!(
-                    "  {}  {}{}{}",
-                    &timestamp[..10],
-                    set_display,
-                    rpe_info.dimmed(),
-                    pr_mark
+                    "{}: {} session{}",
+                    "Longest PR streak".cyan().bold(),
+                    records_json.longest_pr_streak,
+                    if records_json.longest_pr_streak == 1 { "" } else { "s" }
                 );
+            });
+        }
+
+        ExerciseCmd::Rating { exercise } => {
+            let exercise = exercise.join(" ");
+            let exercise_id = match resolve_exercise_id(pool, &exercise).await? {
+                Some(id) => id,
+                None => {
+                    print_no_exercise_named(pool, &exercise).await?;
+                    return Ok(());
+                }
+            };
+
+            let name: String = sqlx::query_scalar("SELECT name FROM exercises WHERE id = ?")
+                .bind(&exercise_id)
+                .fetch_one(pool)
+                .await?;
+
+            let unit = cfg.weight_unit();
+
+            println!("{}: {}", "Rating".cyan().bold(), name.bold());
+
+            match crate::rating::current(pool, &exercise_id).await? {
+                Some((rating, deviation)) => {
+                    let text = format!(
+                        "{:.1} ± {:.1}{}",
+                        unit.from_kg(rating as f32),
+                        unit.from_kg((2.0 * deviation) as f32),
+                        unit.suffix()
+                    );
+                    println!(
+                        "  {}",
+                        if deviation > crate::rating::LOW_CONFIDENCE_RD {
+                            text.dimmed().to_string()
+                        } else {
+                            text
+                        }
+                    );
+
+                    // Trend arrow from the last few completed sessions' top
+                    // sets -- newest vs. oldest in that window, same
+                    // "diff > 0 => ▲" convention as the 30-day change above.
+                    let points = crate::commands::session::exercise_history(pool, &exercise_id, 6).await?;
+                    if points.len() >= 2 {
+                        let diff = points.first().unwrap().e1rm - points.last().unwrap().e1rm;
+                        let arrow = if diff > 0.0 {
+                            "▲".green()
+                        } else if diff < 0.0 {
+                            "▼".red()
+                        } else {
+                            "→".dimmed()
+                        };
+                        println!(
+                            "  {} {} over last {} sessions",
+                            "trend:".dimmed(),
+                            arrow,
+                            points.len()
+                        );
+                    }
+                }
+                None => println!("  {}", "no rating yet -- log a set to start tracking".dimmed()),
             }
         }
     }
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{Datelike, Days, Local, NaiveDate};
+use colored::Colorize;
+use sqlx::SqlitePool;
+
+use crate::cli::HeatmapRamp;
+
+const GREEN_RAMP: [(u8, u8, u8); 5] = [
+    (22, 27, 34),
+    (14, 68, 41),
+    (0, 109, 50),
+    (38, 166, 65),
+    (57, 211, 83),
+];
+
+const RED_RAMP: [(u8, u8, u8); 5] = [
+    (27, 22, 22),
+    (68, 20, 14),
+    (130, 30, 20),
+    (196, 50, 30),
+    (255, 64, 25),
+];
+
+const WEEKDAY_LABELS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Bucket `value` into one of 5 levels (0 = no activity, 4 = heaviest) using
+/// quantile thresholds computed over the nonzero days in range.
+fn level_for(value: f64, thresholds: &[f64; 4]) -> usize {
+    if value <= 0.0 {
+        return 0;
+    }
+
+    (1 + thresholds.iter().filter(|&&t| value >= t).count()).min(4)
+}
+
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+
+    let idx = ((sorted.len() - 1) as f64 * q).round() as usize;
+    sorted[idx.min(sorted.len() - 1)]
+}
+
+pub async fn handle(pool: &SqlitePool, weeks: u32, ramp: HeatmapRamp, by_sets: bool) -> Result<()> {
+    let weeks = weeks.max(1);
+    let today = Local::now().date_naive();
+
+    // Monday of the current week.
+    let this_monday = today - Days::new(today.weekday().num_days_from_monday() as u64);
+    let start = this_monday - Days::new((weeks as u64 - 1) * 7);
+
+    let rows: Vec<(String, f64, i64)> = sqlx::query_as(
+        r#"
+        SELECT
+            date(es.timestamp) as day,
+            CAST(COALESCE(SUM(CAST(es.weight AS REAL) * CAST(es.reps AS INTEGER)), 0) AS REAL) as tonnage,
+            CAST(COUNT(*) AS INTEGER) as sets
+        FROM exercise_sets es
+        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+        WHERE date(es.timestamp) >= date(?)
+        GROUP BY day
+        "#,
+    )
+    .bind(start.format("%Y-%m-%d").to_string())
+    .fetch_all(pool)
+    .await?;
+
+    let mut by_day: HashMap<NaiveDate, f64> = HashMap::new();
+    for (day, tonnage, sets) in &rows {
+        if let Ok(d) = NaiveDate::parse_from_str(day, "%Y-%m-%d") {
+            by_day.insert(d, if by_sets { *sets as f64 } else { *tonnage });
+        }
+    }
+
+    let mut nonzero: Vec<f64> = by_day.values().copied().filter(|v| *v > 0.0).collect();
+    nonzero.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let thresholds = [
+        quantile(&nonzero, 0.20),
+        quantile(&nonzero, 0.40),
+        quantile(&nonzero, 0.60),
+        quantile(&nonzero, 0.80),
+    ];
+
+    let ramp_colors = match ramp {
+        HeatmapRamp::Green => GREEN_RAMP,
+        HeatmapRamp::Red => RED_RAMP,
+    };
+
+    // Month labels along the top, one per column, printed only when the
+    // column's Monday crosses into a new month.
+    let mut month_line = String::new();
+    let mut last_month = 0;
+    for col in 0..weeks {
+        let monday = start + Days::new((col as u64) * 7);
+        if monday.month() != last_month {
+            month_line.push_str(&format!("{:<2}", monday.format("%b")));
+            last_month = monday.month();
+        } else {
+            month_line.push_str("  ");
+        }
+    }
+    println!("    {}", month_line.dimmed());
+
+    for row in 0..7 {
+        print!("{} ", WEEKDAY_LABELS[row].dimmed());
+        for col in 0..weeks {
+            let day = start + Days::new((col as u64) * 7 + row as u64);
+            if day > today {
+                print!("  ");
+                continue;
+            }
+
+            let value = by_day.get(&day).copied().unwrap_or(0.0);
+            let lvl = level_for(value, &thresholds);
+            let (r, g, b) = ramp_colors[lvl];
+            print!("{} ", "■".truecolor(r, g, b));
+        }
+        println!();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use anyhow::Result;
+use colored::Colorize;
+use sqlx::{Row, SqlitePool};
+
+use crate::{
+    cli::{Cli, MacroCmd},
+    types::{Config, OutputFmt},
+};
+
+/// Returns the name of the macro currently being recorded, if any.
+pub async fn active_recording(pool: &SqlitePool) -> Result<Option<String>> {
+    let row = sqlx::query("SELECT name FROM macro_recording WHERE id = 1")
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(row.map(|r| r.get::<String, _>("name")))
+}
+
+/// Appends one canonicalized argument vector (post alias-rewrite, program
+/// name already stripped) to the macro currently being recorded.
+pub async fn append_step(pool: &SqlitePool, name: &str, args: &[String]) -> Result<()> {
+    let steps_json: String = sqlx::query_scalar("SELECT steps FROM macros WHERE name = ?")
+        .bind(name)
+        .fetch_one(pool)
+        .await?;
+
+    let mut steps: Vec<Vec<String>> = serde_json::from_str(&steps_json)?;
+    steps.push(args.to_vec());
+
+    sqlx::query("UPDATE macros SET steps = ? WHERE name = ?")
+        .bind(serde_json::to_string(&steps)?)
+        .bind(name)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn handle(
+    cmd: MacroCmd,
+    pool: &SqlitePool,
+    fmt: OutputFmt,
+    cfg: &Config,
+    config_path: &std::path::Path,
+) -> Result<()> {
+    match cmd {
+        MacroCmd::Record { name } => {
+            if active_recording(pool).await?.is_some() {
+                println!(
+                    "{} already recording a macro, run `macro stop` first",
+                    "warning:".yellow().bold()
+                );
+                return Ok(());
+            }
+
+            sqlx::query(
+                "INSERT OR REPLACE INTO macros(name, steps, created_at)
+                 VALUES (?, '[]', datetime('now'))",
+            )
+            .bind(&name)
+            .execute(pool)
+            .await?;
+
+            sqlx::query("INSERT OR REPLACE INTO macro_recording(id, name) VALUES (1, ?)")
+                .bind(&name)
+                .execute(pool)
+                .await?;
+
+            println!("{} recording macro `{}`", "ok:".green().bold(), name);
+        }
+
+        MacroCmd::Stop => {
+            let Some(name) = active_recording(pool).await? else {
+                println!("{} no macro is being recorded", "warning:".yellow().bold());
+                return Ok(());
+            };
+
+            sqlx::query("DELETE FROM macro_recording WHERE id = 1")
+                .execute(pool)
+                .await?;
+
+            println!("{} stopped recording `{}`", "ok:".green().bold(), name);
+        }
+
+        MacroCmd::Run { name } => {
+            let steps_json: Option<String> = sqlx::query_scalar("SELECT steps FROM macros WHERE name = ?")
+                .bind(&name)
+                .fetch_optional(pool)
+                .await?;
+
+            let Some(steps_json) = steps_json else {
+                println!("{} no macro named `{}`", "warning:".yellow().bold(), name);
+                return Ok(());
+            };
+
+            let steps: Vec<Vec<String>> = serde_json::from_str(&steps_json)?;
+            println!("{} replaying `{}` ({} step(s))", "info:".blue().bold(), name, steps.len());
+
+            for (i, step) in steps.iter().enumerate() {
+                let mut full_args = vec!["lazarus".to_string()];
+                full_args.extend(step.iter().cloned());
+
+                println!("  {} {}", format!("[{}/{}]", i + 1, steps.len()).dimmed(), step.join(" "));
+
+                let step_cli = Cli::parse_from(full_args);
+                let step_profile = step_cli.profile;
+                crate::dispatch(step_cli.cmd, pool, fmt, cfg, config_path, step_profile).await?;
+            }
+        }
+
+        MacroCmd::List => {
+            let rows = sqlx::query("SELECT name, steps, created_at FROM macros ORDER BY created_at")
+                .fetch_all(pool)
+                .await?;
+
+            if rows.is_empty() {
+                println!("{} no macros recorded", "warning:".yellow().bold());
+                return Ok(());
+            }
+
+            println!("{}", "Macros:".cyan().bold());
+            for row in rows {
+                let name: String = row.get("name");
+                let steps_json: String = row.get("steps");
+                let steps: Vec<Vec<String>> = serde_json::from_str(&steps_json)?;
+                println!("  {} ({} step(s))", name.bold(), steps.len());
+            }
+        }
+
+        MacroCmd::Delete { name } => {
+            let result = sqlx::query("DELETE FROM macros WHERE name = ?")
+                .bind(&name)
+                .execute(pool)
+                .await?;
+
+            if result.rows_affected() == 0 {
+                println!("{} no macro named `{}`", "warning:".yellow().bold(), name);
+            } else {
+                println!("{} deleted macro `{}`", "ok:".green().bold(), name);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,249 @@
+use crate::{cli::MeasureCmd, types::{emit, Config}, OutputFmt};
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use sqlx::{Row, SqlitePool};
+
+#[derive(Serialize)]
+struct MeasureJson {
+    kind: String,
+    value: f32,
+    unit: String,
+    timestamp: String,
+    notes: Option<String>,
+}
+
+/// Shared by `MeasureCmd::Add` and the `measure bodyweight` shorthand.
+async fn add_measurement(
+    pool: &SqlitePool,
+    cfg: &Config,
+    kind: String,
+    value: f32,
+    unit: Option<String>,
+    notes: Option<String>,
+) -> Result<()> {
+    let unit = unit.unwrap_or_else(|| {
+        if kind.eq_ignore_ascii_case("bodyweight") {
+            cfg.weight_unit().as_str().to_string()
+        } else {
+            "cm".to_string()
+        }
+    });
+
+    sqlx::query(
+        r#"
+        INSERT INTO measurements
+        (id, kind, value, unit, timestamp, notes)
+        VALUES (?1, ?2, ?3, ?4, datetime('now'), ?5)
+        "#,
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&kind)
+    .bind(value)
+    .bind(&unit)
+    .bind(&notes)
+    .execute(pool)
+    .await?;
+
+    println!(
+        "{} logged {} = {}{}",
+        "info:".blue().bold(),
+        kind.green(),
+        value,
+        unit
+    );
+
+    Ok(())
+}
+
+/// Most recently logged `bodyweight` measurement, in canonical kg — `None`
+/// until the user has logged at least one with `measure bodyweight`/`measure
+/// add bodyweight`. Used to price bodyweight exercise sets into the same
+/// `personal_records`/`estimated_one_rm` pipeline as weighted lifts.
+pub async fn latest_bodyweight_kg<'e, E>(executor: E) -> Result<Option<f32>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let row: Option<(f32, String)> = sqlx::query_as(
+        r#"
+        SELECT value, unit
+        FROM measurements
+        WHERE kind = 'bodyweight'
+        ORDER BY timestamp DESC
+        LIMIT 1
+        "#,
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(row.map(|(value, unit)| {
+        if unit.eq_ignore_ascii_case("lb") {
+            crate::types::WeightUnit::Lb.to_kg(value)
+        } else {
+            value
+        }
+    }))
+}
+
+/// The `bodyweight` measurement in effect at `timestamp`, in canonical kg —
+/// the most recent one logged at or before it, falling back to the earliest
+/// one logged after if none exist yet. Used to re-price historical
+/// bodyweight-flagged sets with the weight the user actually had at the time,
+/// instead of today's, when replaying PR history.
+pub async fn bodyweight_kg_as_of<'a>(
+    tx: &mut sqlx::Transaction<'a, sqlx::Sqlite>,
+    timestamp: &str,
+) -> Result<Option<f32>> {
+    let row: Option<(f32, String)> = sqlx::query_as(
+        r#"
+        SELECT value, unit
+        FROM measurements
+        WHERE kind = 'bodyweight' AND timestamp <= ?
+        ORDER BY timestamp DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(timestamp)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    // No measurement logged yet as of `timestamp` (e.g. the very first sets,
+    // logged before the user ever recorded a bodyweight) — fall back to the
+    // earliest one logged after it rather than treating bodyweight as 0.
+    let row = match row {
+        Some(r) => Some(r),
+        None => {
+            sqlx::query_as(
+                r#"
+                SELECT value, unit
+                FROM measurements
+                WHERE kind = 'bodyweight' AND timestamp > ?
+                ORDER BY timestamp ASC
+                LIMIT 1
+                "#,
+            )
+            .bind(timestamp)
+            .fetch_optional(&mut **tx)
+            .await?
+        }
+    };
+
+    Ok(row.map(|(value, unit)| {
+        if unit.eq_ignore_ascii_case("lb") {
+            crate::types::WeightUnit::Lb.to_kg(value)
+        } else {
+            value
+        }
+    }))
+}
+
+pub async fn handle(cmd: MeasureCmd, pool: &SqlitePool, fmt: OutputFmt, cfg: &Config) -> Result<()> {
+    match cmd {
+        MeasureCmd::Bodyweight { value, notes } => {
+            add_measurement(pool, cfg, "bodyweight".to_string(), value, None, notes).await?;
+        }
+
+        MeasureCmd::Add { kind, value, unit, notes } => {
+            add_measurement(pool, cfg, kind, value, unit, notes).await?;
+        }
+
+        MeasureCmd::Log { kind, limit } => {
+            let rows: Vec<(f32, String, String)> = sqlx::query_as(
+                r#"
+                SELECT value, unit, timestamp
+                FROM measurements
+                WHERE kind = ?
+                ORDER BY timestamp ASC
+                LIMIT ?
+                "#,
+            )
+            .bind(&kind)
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
+
+            if rows.is_empty() {
+                println!("{} no `{}` measurements logged", "warning:".yellow().bold(), kind);
+                return Ok(());
+            }
+
+            println!("{} {}", "Log:".cyan().bold(), kind.bold());
+            let mut prev_value: Option<f32> = None;
+            for (value, unit, timestamp) in &rows {
+                let delta = match prev_value {
+                    Some(p) => {
+                        let d = value - p;
+                        if d > 0.0 {
+                            format!(" ({})", format!("+{:.1}", d).green())
+                        } else if d < 0.0 {
+                            format!(" ({})", format!("{:.1}", d).red())
+                        } else {
+                            String::new()
+                        }
+                    }
+                    None => String::new(),
+                };
+
+                println!(
+                    " {} {}{}{}",
+                    format!("— {}", &timestamp[..10]).dimmed(),
+                    format!("{:>8}", value).yellow(),
+                    format!(" {}", unit).bold(),
+                    delta
+                );
+
+                prev_value = Some(*value);
+            }
+        }
+
+        MeasureCmd::List { kind, limit } => {
+            let base = "
+                SELECT kind, value, unit, timestamp, notes
+                FROM measurements
+            ";
+
+            let db_rows = if let Some(k) = &kind {
+                let q = format!("{base} WHERE kind = ? ORDER BY timestamp DESC LIMIT ?");
+                sqlx::query(&q).bind(k).bind(limit).fetch_all(pool).await?
+            } else {
+                let q = format!("{base} ORDER BY timestamp DESC LIMIT ?");
+                sqlx::query(&q).bind(limit).fetch_all(pool).await?
+            };
+
+            let json_rows: Vec<MeasureJson> = db_rows
+                .iter()
+                .map(|r| MeasureJson {
+                    kind: r.get("kind"),
+                    value: r.get("value"),
+                    unit: r.get("unit"),
+                    timestamp: r.get("timestamp"),
+                    notes: r.get("notes"),
+                })
+                .collect();
+
+            emit(fmt, &json_rows, || {
+                if json_rows.is_empty() {
+                    println!(
+                        "{} no measurements logged{}",
+                        "warning:".yellow().bold(),
+                        kind.map(|k| format!(" for `{}`", k)).unwrap_or_default()
+                    );
+                    return;
+                }
+
+                println!("{}", "Measurements:".cyan().bold());
+                for m in &json_rows {
+                    println!(
+                        " {} {} {}{}",
+                        format!("{:>8}", m.value).yellow(),
+                        format!("{} {}", m.kind, m.unit).bold(),
+                        format!("— {}", &m.timestamp[..10]).dimmed(),
+                        m.notes.as_deref().map(|n| format!(" ({n})").dimmed().to_string()).unwrap_or_default()
+                    );
+                }
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,12 @@
+pub mod calendar;
+pub mod config;
+pub mod db;
+pub mod exercise;
+pub mod heatmap;
+pub mod macro_;
+pub mod measure;
+pub mod preset;
+pub mod program;
+pub mod search;
+pub mod session;
+pub mod status;
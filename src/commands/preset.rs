@@ -0,0 +1,124 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::{
+    cli::PresetCmd,
+    preset::{self, PresetKind},
+    types::{OutputFmt, emit},
+};
+
+#[derive(Serialize)]
+struct PresetJson {
+    name: String,
+    kind: String,
+    scheme: String,
+}
+
+pub async fn handle(cmd: PresetCmd, pool: &SqlitePool, fmt: OutputFmt) -> Result<()> {
+    match cmd {
+        PresetCmd::Add { name, kind, scheme } => {
+            let kind = match kind {
+                crate::cli::PresetKind::Percent => PresetKind::Percent,
+                crate::cli::PresetKind::Rpe => PresetKind::Rpe,
+            };
+
+            // Validate the scheme up front so `preset add` can't leave behind
+            // a preset that silently expands to nothing.
+            if preset::expand(&scheme).is_empty() {
+                println!(
+                    "{} scheme `{}` didn't parse to any sets (expected \"value:sets,...\")",
+                    "error:".red().bold(),
+                    scheme
+                );
+                return Ok(());
+            }
+
+            let existing: Option<(String,)> =
+                sqlx::query_as("SELECT id FROM set_scheme_presets WHERE name = ?")
+                    .bind(&name)
+                    .fetch_optional(pool)
+                    .await?;
+
+            if existing.is_some() {
+                println!("{} a preset named `{}` already exists", "error:".red().bold(), name);
+                return Ok(());
+            }
+
+            sqlx::query("INSERT INTO set_scheme_presets (id, name, kind, scheme) VALUES (?, ?, ?, ?)")
+                .bind(Uuid::new_v4().to_string())
+                .bind(&name)
+                .bind(kind.as_str())
+                .bind(&scheme)
+                .execute(pool)
+                .await?;
+
+            println!("{} preset `{}` saved", "info:".blue().bold(), name.green());
+        }
+
+        PresetCmd::List => {
+            let rows: Vec<(String, String, String)> =
+                sqlx::query_as("SELECT name, kind, scheme FROM set_scheme_presets ORDER BY name")
+                    .fetch_all(pool)
+                    .await?;
+
+            let json_rows: Vec<PresetJson> = rows
+                .iter()
+                .map(|(name, kind, scheme)| PresetJson {
+                    name: name.clone(),
+                    kind: kind.clone(),
+                    scheme: scheme.clone(),
+                })
+                .collect();
+
+            emit(fmt, &json_rows, || {
+                if json_rows.is_empty() {
+                    println!("{} no presets defined", "warning:".yellow().bold());
+                    return;
+                }
+
+                println!("{}", "Presets:".cyan().bold());
+                for p in &json_rows {
+                    println!(
+                        " {} {} — {}",
+                        p.name.bold(),
+                        format!("({})", p.kind).dimmed(),
+                        p.scheme
+                    );
+                }
+            });
+        }
+
+        PresetCmd::Show { name } => {
+            let Some((kind, values)) = preset::expand_named(pool, &name).await? else {
+                println!("{} no preset named `{}`", "error:".red().bold(), name);
+                return Ok(());
+            };
+
+            println!("{} {} ({})", "Preset:".cyan().bold(), name.bold(), kind.as_str());
+            for (i, v) in values.iter().enumerate() {
+                match kind {
+                    PresetKind::Percent => println!("  {} {}%", format!("{}", i + 1).yellow(), v),
+                    PresetKind::Rpe => println!("  {} @RPE {}", format!("{}", i + 1).yellow(), v),
+                }
+            }
+        }
+
+        PresetCmd::Delete { name } => {
+            let result = sqlx::query("DELETE FROM set_scheme_presets WHERE name = ?")
+                .bind(&name)
+                .execute(pool)
+                .await?;
+
+            if result.rows_affected() == 0 {
+                println!("{} no preset named `{}`", "error:".red().bold(), name);
+            } else {
+                println!("{} preset `{}` deleted", "info:".blue().bold(), name);
+            }
+        }
+    }
+
+    Ok(())
+}
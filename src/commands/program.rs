@@ -5,15 +5,16 @@ use std::{
 
 use anyhow::Result;
 use colored::Colorize;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sqlx::{Row, SqlitePool};
 
 use crate::{
     cli::ProgramCmd,
+    profile::QueryProfiler,
     types::{OutputFmt, emit},
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 struct ProgramToml {
     name: String,
@@ -21,14 +22,14 @@ struct ProgramToml {
     blocks: Vec<BlockToml>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct BlockToml {
     name: String,
     description: Option<String>,
     exercises: Vec<BlockExerciseToml>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct BlockExerciseToml {
     name: String,
     sets: u32,
@@ -39,6 +40,14 @@ struct BlockExerciseToml {
     program_1rm: Option<f32>,
     technique: Option<String>,
     group: Option<u32>,
+    /// Optional Rhai prescription script (see `crate::scripting`). When
+    /// present it is evaluated at `session start` to produce the per-set
+    /// prescription instead of the literal `reps` strings.
+    script: Option<String>,
+    /// Name of a `preset add`-defined set scheme (see `crate::preset`). When
+    /// present and `target_rpe`/`target_rm_percent` are absent, `session
+    /// show` expands the preset's current definition instead.
+    preset: Option<String>,
 }
 
 #[derive(Debug)]
@@ -55,6 +64,118 @@ struct ProgJson {
     blocks: i64,
 }
 
+/// One exercise within a [`ProgramDetailBlockJson`] — the richer per-exercise
+/// shape `program show --format json|cbor` serializes, as opposed to
+/// `ProgJson`'s flat per-program row used by `program list`.
+#[derive(serde::Serialize)]
+struct ProgramDetailExerciseJson {
+    order: i32,
+    name: String,
+    sets: i32,
+    reps: Vec<String>,
+    target_rpe: Vec<f32>,
+    target_rm_percent: Vec<f32>,
+    notes: Option<String>,
+    program_1rm: Option<f32>,
+    technique: Option<String>,
+    technique_group: Option<i32>,
+}
+
+#[derive(serde::Serialize)]
+struct ProgramDetailBlockJson {
+    name: String,
+    description: String,
+    exercises: Vec<ProgramDetailExerciseJson>,
+}
+
+#[derive(serde::Serialize)]
+struct ProgramDetailJson {
+    name: String,
+    description: String,
+    created_at: String,
+    blocks: Vec<ProgramDetailBlockJson>,
+}
+
+/// One exercise's share of a [`ReportBlockJson`]. `tonnage` and
+/// `avg_intensity_percent` are `0`/`None` when the exercise is missing a
+/// `program_1rm` or `target_rm_percent`, per `program report`'s rules.
+#[derive(serde::Serialize)]
+struct ReportExerciseJson {
+    name: String,
+    muscle: String,
+    sets: i32,
+    tonnage: f32,
+    avg_intensity_percent: Option<f32>,
+}
+
+#[derive(serde::Serialize)]
+struct ReportBlockJson {
+    name: String,
+    sets: i32,
+    tonnage: f32,
+    avg_intensity_percent: Option<f32>,
+    exercises: Vec<ReportExerciseJson>,
+}
+
+#[derive(serde::Serialize)]
+struct ReportMuscleJson {
+    muscle: String,
+    sets: i32,
+    tonnage: f32,
+    avg_intensity_percent: Option<f32>,
+}
+
+#[derive(serde::Serialize)]
+struct ProgramReportJson {
+    name: String,
+    total_sets: i32,
+    total_tonnage: f32,
+    avg_intensity_percent: Option<f32>,
+    blocks: Vec<ReportBlockJson>,
+    by_muscle: Vec<ReportMuscleJson>,
+}
+
+/// Running totals for `program report` — sets, tonnage, and the pieces
+/// needed to average target intensity weighted by how many sets actually
+/// carried a known `%1RM` (rather than by exercise count).
+#[derive(Default, Clone)]
+struct LoadAccum {
+    sets: i32,
+    tonnage: f32,
+    intensity_sets: i32,
+    intensity_sum: f32,
+}
+
+impl LoadAccum {
+    fn add(&mut self, other: &LoadAccum) {
+        self.sets += other.sets;
+        self.tonnage += other.tonnage;
+        self.intensity_sets += other.intensity_sets;
+        self.intensity_sum += other.intensity_sum;
+    }
+
+    fn avg_intensity(&self) -> Option<f32> {
+        if self.intensity_sets > 0 {
+            Some(self.intensity_sum / self.intensity_sets as f32)
+        } else {
+            None
+        }
+    }
+}
+
+/// Takes the lower bound of a rep range like `"6-10"` (or the literal value
+/// for a fixed count like `"15"`), for a conservative tonnage estimate.
+fn rep_lower_bound(s: &str) -> Option<f32> {
+    s.split('-').next()?.trim().parse().ok()
+}
+
+/// Looks up `values[i]`, clamping to the last entry once `i` runs past a
+/// shorter list, so a CSV with fewer entries than `sets` holds its last
+/// value for the remaining sets.
+fn nth_or_last<'a>(values: &'a [&'a str], i: usize) -> Option<&'a str> {
+    if values.is_empty() { None } else { Some(values[i.min(values.len() - 1)]) }
+}
+
 fn plain_len(s: &str) -> usize {
     let mut n = 0;
     let mut esc = false;
@@ -69,10 +190,49 @@ fn plain_len(s: &str) -> usize {
     n
 }
 
-async fn blocks_by_program(pool: &SqlitePool) -> Result<HashMap<String, Vec<BlockRow>>> {
-    let rows = sqlx::query("SELECT program_id, name FROM program_blocks ORDER BY program_id, name")
-        .fetch_all(pool)
-        .await?;
+/// Resolves `program` — an index from `p list`, or an exact name — to its
+/// UUID. Shared by `show`/`delete`/`export`, which all take the same
+/// index-or-name argument. Prints its own `error:` message and returns
+/// `Ok(None)` on a miss, so callers can just early-return.
+async fn resolve_program_id(
+    pool: &SqlitePool,
+    program: &str,
+    profiler: &QueryProfiler,
+) -> Result<Option<String>> {
+    if let Ok(idx) = program.parse::<i64>() {
+        let sql = r#"
+            SELECT id
+            FROM (
+              SELECT id, ROW_NUMBER() OVER (ORDER BY name) AS rn
+              FROM programs
+            ) t
+            WHERE t.rn = ?
+            "#;
+        match profiler.record(pool, sql, sqlx::query_scalar(sql).bind(idx).fetch_one(pool)).await {
+            Ok(id) => Ok(Some(id)),
+            Err(_) => {
+                println!("{} no program at index {}", "error:".red().bold(), idx);
+                Ok(None)
+            }
+        }
+    } else {
+        let sql = "SELECT id FROM programs WHERE name = ?";
+        match profiler.record(pool, sql, sqlx::query_scalar(sql).bind(program).fetch_one(pool)).await {
+            Ok(id) => Ok(Some(id)),
+            Err(_) => {
+                println!("{} no program named `{}`", "error:".red().bold(), program);
+                Ok(None)
+            }
+        }
+    }
+}
+
+async fn blocks_by_program(
+    pool: &SqlitePool,
+    profiler: &QueryProfiler,
+) -> Result<HashMap<String, Vec<BlockRow>>> {
+    let sql = "SELECT program_id, name FROM program_blocks ORDER BY program_id, name";
+    let rows = profiler.record(pool, sql, sqlx::query(sql).fetch_all(pool)).await?;
 
     let mut map: HashMap<String, Vec<BlockRow>> = HashMap::new();
     for r in rows {
@@ -158,7 +318,180 @@ fn pretty_print(
     }
 }
 
-pub async fn handle(cmd: ProgramCmd, pool: &SqlitePool, fmt: OutputFmt) -> Result<()> {
+/// Renders everything `program show` previously dropped from a prescription
+/// — RPE/`%`1RM targets, the program's static 1RM, technique/group, and free
+/// notes — as a trailing `" @ ..."` clause, e.g. `" @ RPE 8,8,9 · 80% 1RM ·
+/// SS group 1 · \"pause at bottom\""`. Empty when none of those are set.
+fn format_prescription(ex: &ProgramDetailExerciseJson) -> String {
+    let join_f32 = |values: &[f32]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(",");
+
+    let mut segments = Vec::new();
+    if !ex.target_rpe.is_empty() {
+        segments.push(format!("RPE {}", join_f32(&ex.target_rpe)));
+    }
+    if !ex.target_rm_percent.is_empty() {
+        segments.push(format!("{}% 1RM", join_f32(&ex.target_rm_percent)));
+    }
+    if let Some(program_1rm) = ex.program_1rm {
+        segments.push(format!("1RM {program_1rm}kg"));
+    }
+    match (&ex.technique, ex.technique_group) {
+        (Some(t), Some(g)) => segments.push(format!("{t} group {g}")),
+        (Some(t), None) => segments.push(t.clone()),
+        (None, Some(g)) => segments.push(format!("group {g}")),
+        (None, None) => {}
+    }
+    if let Some(notes) = &ex.notes {
+        if !notes.is_empty() {
+            segments.push(format!("\"{notes}\""));
+        }
+    }
+
+    if segments.is_empty() {
+        String::new()
+    } else {
+        format!(" @ {}", segments.join(" · "))
+    }
+}
+
+fn pretty_print_show(detail: &ProgramDetailJson) {
+    if !detail.description.is_empty() {
+        println!(
+            "{} {} — {} (added {})",
+            "Program:".cyan().bold(),
+            detail.name.bold(),
+            detail.description.dimmed(),
+            &detail.created_at[..10]
+        );
+    } else {
+        println!(
+            "{} {} (added {})",
+            "Program:".cyan().bold(),
+            detail.name.bold(),
+            &detail.created_at[..10]
+        );
+    }
+
+    if detail.blocks.is_empty() {
+        println!("{} no blocks defined)", "warning".yellow().bold());
+        return;
+    }
+
+    println!("{}", "Blocks:".cyan().bold());
+
+    for (i, block) in detail.blocks.iter().enumerate() {
+        let idx = format!("{}", i + 1).yellow();
+        let desc = if block.description.is_empty() {
+            String::new()
+        } else {
+            format!(" — {}", block.description).dimmed().to_string()
+        };
+        println!("{} • {}{}", idx, block.name.bold(), desc);
+
+        let total = block.exercises.len();
+        for (j, ex) in block.exercises.iter().enumerate() {
+            // format the reps into a nicer "(5, 6–10, 15 reps)" if present
+            let reps_display = if ex.reps.is_empty() {
+                String::new()
+            } else {
+                format!(" ({} reps)", ex.reps.join(", "))
+            };
+
+            let grouped_with_prev = j > 0
+                && ex.technique_group.is_some()
+                && ex.technique_group == block.exercises[j - 1].technique_group;
+            let grouped_with_next = j + 1 < total
+                && ex.technique_group.is_some()
+                && ex.technique_group == block.exercises[j + 1].technique_group;
+
+            // A small vertical bracket ties together exercises sharing a
+            // `technique_group` (supersets), so a multi-move group reads as
+            // one unit instead of N unrelated lines.
+            let bracket = match (grouped_with_prev, grouped_with_next) {
+                (false, false) => ' ',
+                (false, true) => '╭',
+                (true, true) => '│',
+                (true, false) => '╰',
+            };
+
+            let connector = if ex.order + 1 == total as i32 { "└─" } else { "├─" };
+            let idx = format!("{}", ex.order + 1).yellow();
+
+            println!(
+                " {} {} {} • {} -> {} sets{}{}",
+                bracket,
+                connector,
+                idx,
+                ex.name.bold(),
+                ex.sets,
+                reps_display,
+                format_prescription(ex)
+            );
+        }
+    }
+}
+
+fn format_intensity(percent: Option<f32>) -> String {
+    match percent {
+        Some(p) => format!(", avg {:.0}% 1RM", p),
+        None => String::new(),
+    }
+}
+
+fn pretty_print_report(report: &ProgramReportJson) {
+    println!("{} {} — planned load", "Program:".cyan().bold(), report.name.bold());
+
+    if report.blocks.is_empty() {
+        println!("{} no blocks defined)", "warning".yellow().bold());
+        return;
+    }
+
+    println!("{}", "Blocks:".cyan().bold());
+    for block in &report.blocks {
+        println!(
+            " {} • {} sets, {:.0}kg tonnage{}",
+            block.name.bold(),
+            block.sets,
+            block.tonnage,
+            format_intensity(block.avg_intensity_percent)
+        );
+        for ex in &block.exercises {
+            let intensity = match ex.avg_intensity_percent {
+                Some(p) => format!(" @ {:.0}% 1RM", p),
+                None => " (no 1RM set)".dimmed().to_string(),
+            };
+            println!("   {} • {} sets, {:.0}kg{}", ex.name.bold(), ex.sets, ex.tonnage, intensity);
+        }
+    }
+
+    println!("{}", "By muscle:".cyan().bold());
+    for m in &report.by_muscle {
+        println!(
+            " {} • {} sets, {:.0}kg{}",
+            m.muscle.bold(),
+            m.sets,
+            m.tonnage,
+            format_intensity(m.avg_intensity_percent)
+        );
+    }
+
+    println!(
+        "{} {} sets, {:.0}kg tonnage{}",
+        "Total:".cyan().bold(),
+        report.total_sets,
+        report.total_tonnage,
+        format_intensity(report.avg_intensity_percent)
+    );
+}
+
+pub async fn handle(cmd: ProgramCmd, pool: &SqlitePool, fmt: OutputFmt, profile: bool) -> Result<()> {
+    let profiler = QueryProfiler::new(profile);
+    let result = handle_inner(cmd, pool, fmt, &profiler).await;
+    profiler.summarize();
+    result
+}
+
+async fn handle_inner(cmd: ProgramCmd, pool: &SqlitePool, fmt: OutputFmt, profiler: &QueryProfiler) -> Result<()> {
     match cmd {
         ProgramCmd::Import { files } => {
             if files.is_empty() {
@@ -274,7 +607,28 @@ pub async fn handle(cmd: ProgramCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resul
                                 .bind(&ex.name)
                                 .fetch_one(&mut *tx)
                                 .await?;
-                        sqlx::query("INSERT INTO program_exercises (id,program_block_id,exercise_id,sets,reps,target_rpe,target_rm_percent,notes,program_1rm,technique,technique_group,order_index) VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12)")
+
+                        let preset_id: Option<String> = match &ex.preset {
+                            Some(name) => {
+                                let id: Option<String> =
+                                    sqlx::query_scalar("SELECT id FROM set_scheme_presets WHERE name = ?")
+                                        .bind(name)
+                                        .fetch_optional(&mut *tx)
+                                        .await?;
+                                if id.is_none() {
+                                    println!(
+                                        "{} no preset named `{}` for `{}`—ignored",
+                                        "warning:".yellow().bold(),
+                                        name,
+                                        ex.name
+                                    );
+                                }
+                                id
+                            }
+                            None => None,
+                        };
+
+                        sqlx::query("INSERT INTO program_exercises (id,program_block_id,exercise_id,sets,reps,target_rpe,target_rm_percent,notes,program_1rm,technique,technique_group,order_index,script,preset_id) VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14)")
                             .bind(uuid::Uuid::new_v4().to_string())
                             .bind(&bid)
                             .bind(&ex_id)
@@ -287,6 +641,8 @@ pub async fn handle(cmd: ProgramCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resul
                             .bind(ex.technique.as_deref())
                             .bind(ex.group.map(|g|g as i32))
                             .bind(idx as i32)
+                            .bind(ex.script.as_deref())
+                            .bind(preset_id)
                             .execute(&mut *tx).await?;
                     }
                 }
@@ -300,18 +656,15 @@ pub async fn handle(cmd: ProgramCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resul
         }
 
         ProgramCmd::List => {
-            let rows = sqlx::query(
-                r#"
+            let sql = r#"
                 SELECT ROW_NUMBER() OVER (ORDER BY name) AS idx,
                        id, name,
                        COALESCE(description,'') AS description,
                        created_at
                 FROM   programs
                 ORDER  BY idx
-                "#,
-            )
-            .fetch_all(pool)
-            .await?;
+                "#;
+            let rows = profiler.record(pool, sql, sqlx::query(sql).fetch_all(pool)).await?;
 
             let mut progs = Vec::<ProgJson>::new();
             let mut idx2id = HashMap::<i64, String>::new();
@@ -327,7 +680,7 @@ pub async fn handle(cmd: ProgramCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resul
                 idx2id.insert(idx, r.get("id"));
             }
 
-            let blk_map = blocks_by_program(pool).await?;
+            let blk_map = blocks_by_program(pool, profiler).await?;
             for p in &mut progs {
                 if let Some(id) = idx2id.get(&p.idx) {
                     p.blocks = blk_map.get(id).map(|v| v.len() as i64).unwrap_or(0);
@@ -338,221 +691,379 @@ pub async fn handle(cmd: ProgramCmd, pool: &SqlitePool, fmt: OutputFmt) -> Resul
         }
 
         ProgramCmd::Show { program } => {
-            // Figure out the real UUID for this program.
-            let prog_id: String = if let Ok(idx) = program.parse::<i64>() {
-                // User passed a number - look up by row number.
-                match sqlx::query_scalar(
-                    r#"
-                SELECT id 
-                FROM (
-                  SELECT id, ROW_NUMBER() OVER (ORDER BY name) AS rn
-                  FROM programs
-                ) t
-                WHERE t.rn = ?
-                "#,
-                )
-                .bind(idx)
-                .fetch_one(pool)
-                .await {
-                    Ok(id) => id,
-                    Err(_) => {
-                        println!("{} no program at index {}", "error:".red().bold(), idx);
-                        return Ok(());
-                    }
-                }
-            } else {
-                // User passed a name - look up by exact name.
-                match sqlx::query_scalar("SELECT id FROM programs WHERE name = ?")
-                    .bind(&program)
-                    .fetch_one(pool)
-                    .await {
-                    Ok(id) => id,
-                    Err(_) => {
-                        println!("{} no program named `{}`", "error:".red().bold(), program);
-                        return Ok(());
-                    }
-                }
+            let Some(prog_id) = resolve_program_id(pool, &program, profiler).await? else {
+                return Ok(());
             };
 
             // Fetch the program's metadata.
-            let (name, desc, created) = sqlx::query_as::<_, (String, String, String)>(
-                r#"
+            let meta_sql = r#"
                 SELECT name, COALESCE(description,''), created_at
                 FROM programs
                 WHERE id = ?
-                "#,
-            )
-            .bind(&prog_id)
-            .fetch_one(pool)
-            .await?;
+                "#;
+            let (name, desc, created) = profiler
+                .record(pool, meta_sql, sqlx::query_as::<_, (String, String, String)>(meta_sql).bind(&prog_id).fetch_one(pool))
+                .await?;
 
-            if !desc.is_empty() {
-                println!(
-                    "{} {} — {} (added {})",
-                    "Program:".cyan().bold(),
-                    name.bold(),
-                    desc.dimmed(),
-                    &created[..10]
-                );
-            } else {
-                println!(
-                    "{} {} (added {})",
-                    "Program:".cyan().bold(),
-                    name.bold(),
-                    &created[..10]
-                );
+            // Fetch its blocks and their exercises up front — rather than
+            // printing as each query comes back — so the same data feeds
+            // either the pretty text below or a `--format json|cbor` dump.
+            let block_sql =
+                "SELECT name, COALESCE(description,'') FROM program_blocks WHERE program_id = ? ORDER BY name";
+            let block_rows = profiler
+                .record(pool, block_sql, sqlx::query_as::<_, (String, String)>(block_sql).bind(&prog_id).fetch_all(pool))
+                .await?;
+
+            // One join across the whole program instead of a per-block
+            // round trip with a correlated `program_blocks` subquery — the
+            // rows are bucketed by block name below, so render time no
+            // longer scales with exercise count.
+            let ex_sql = r#"
+                SELECT pb.name,
+                       pe.order_index,
+                       e.name,
+                       pe.sets,
+                       pe.reps,
+                       pe.target_rpe,
+                       pe.target_rm_percent,
+                       pe.notes,
+                       pe.program_1rm,
+                       pe.technique,
+                       pe.technique_group
+                  FROM program_exercises pe
+                  JOIN program_blocks pb
+                    ON pb.id = pe.program_block_id
+                  JOIN exercises e
+                    ON e.id = pe.exercise_id
+                 WHERE pb.program_id = ?
+                 ORDER BY pb.name, pe.order_index
+                "#;
+            let ex_rows = profiler
+                .record(
+                    pool,
+                    ex_sql,
+                    sqlx::query_as::<
+                        _,
+                        (
+                            String,
+                            i32,
+                            String,
+                            i32,
+                            Option<String>,
+                            Option<String>,
+                            Option<String>,
+                            Option<String>,
+                            Option<f32>,
+                            Option<String>,
+                            Option<i32>,
+                        ),
+                    >(ex_sql)
+                    .bind(&prog_id)
+                    .fetch_all(pool),
+                )
+                .await?;
+
+            let mut exercises_by_block: HashMap<String, Vec<ProgramDetailExerciseJson>> = HashMap::new();
+            for (
+                block_name,
+                order,
+                ex_name,
+                sets,
+                reps_csv,
+                target_rpe_csv,
+                target_rm_csv,
+                notes,
+                program_1rm,
+                technique,
+                technique_group,
+            ) in ex_rows
+            {
+                let reps = reps_csv
+                    .map(|csv| csv.split(',').map(|s| s.trim().to_string()).collect())
+                    .unwrap_or_default();
+                let target_rpe = target_rpe_csv
+                    .map(|csv| csv.split(',').filter_map(|s| s.trim().parse().ok()).collect())
+                    .unwrap_or_default();
+                let target_rm_percent = target_rm_csv
+                    .map(|csv| csv.split(',').filter_map(|s| s.trim().parse().ok()).collect())
+                    .unwrap_or_default();
+
+                exercises_by_block.entry(block_name).or_default().push(ProgramDetailExerciseJson {
+                    order,
+                    name: ex_name,
+                    sets,
+                    reps,
+                    target_rpe,
+                    target_rm_percent,
+                    notes,
+                    program_1rm,
+                    technique,
+                    technique_group,
+                });
             }
 
-            // Fetch its blocks in order.
-            let blocks = sqlx::query_as::<_, (String,String)>(
-                "SELECT name, COALESCE(description,'') FROM program_blocks WHERE program_id = ? ORDER BY name",
+            let blocks = block_rows
+                .into_iter()
+                .map(|(block_name, block_desc)| {
+                    let exercises = exercises_by_block.remove(&block_name).unwrap_or_default();
+                    ProgramDetailBlockJson { name: block_name, description: block_desc, exercises }
+                })
+                .collect();
+
+            let detail = ProgramDetailJson { name, description: desc, created_at: created, blocks };
+
+            emit(fmt, &detail, || pretty_print_show(&detail));
+        }
+
+        ProgramCmd::Delete { program } => {
+            let Some(prog_id) = resolve_program_id(pool, &program, profiler).await? else {
+                return Ok(());
+            };
+
+            // Get program name for confirmation message.
+            let name: String = sqlx::query_scalar("SELECT name FROM programs WHERE id = ?")
+                .bind(&prog_id)
+                .fetch_one(pool)
+                .await?;
+
+            // Delete the program (cascade will handle blocks and exercises as well).
+            sqlx::query("DELETE FROM programs WHERE id = ?")
+                .bind(&prog_id)
+                .execute(pool)
+                .await?;
+
+            println!("{} deleted program `{}`", "ok:".green().bold(), name);
+        }
+
+        ProgramCmd::Export { program, out } => {
+            let Some(prog_id) = resolve_program_id(pool, &program, profiler).await? else {
+                return Ok(());
+            };
+
+            let (name, description): (String, Option<String>) =
+                sqlx::query_as("SELECT name, description FROM programs WHERE id = ?")
+                    .bind(&prog_id)
+                    .fetch_one(pool)
+                    .await?;
+
+            let block_rows = sqlx::query_as::<_, (String, Option<String>)>(
+                "SELECT name, description FROM program_blocks WHERE program_id = ? ORDER BY name",
             )
             .bind(&prog_id)
             .fetch_all(pool)
             .await?;
 
-            if blocks.is_empty() {
-                println!("{} no blocks defined)", "warning".yellow().bold());
-            } else {
-                println!("{}", "Blocks:".cyan().bold());
-                
-                for (i, (block_name, block_desc)) in blocks.into_iter().enumerate() {
-                    let idx = format!("{}", i + 1).yellow();
-                    let desc = if !block_desc.is_empty() {
-                        format!(" — {}", block_desc).dimmed().to_string()
-                    } else {
-                        String::new()
-                    };
-                    println!("{} • {}{}", idx, block_name.bold(), desc);
-                    
-                    // Fetch the exercises in that block.
-                    let exs = sqlx::query_as::<_, (i32, String, i32)>(
-                        r#"
-                        SELECT pe.order_index,
-                               e.name,
-                               pe.sets
-                      FROM program_exercises pe
-                      JOIN exercises e
-                        ON e.id = pe.exercise_id
-                     WHERE pe.program_block_id = (
-                       SELECT id
-                         FROM program_blocks
-                        WHERE program_id = ? AND name = ?
-                            LIMIT 1
-                         )
-                      ORDER BY pe.order_index
-                        "#,
+            // Per-block correlated subquery — `program show` already learned
+            // not to do this (see chunk9-4); left as-is here since exports
+            // aren't on the hot path, but `--profile` will call it out too.
+            let exs_sql = r#"
+                    SELECT e.name, pe.sets, pe.reps, pe.target_rpe, pe.target_rm_percent,
+                           pe.notes, pe.program_1rm, pe.technique, pe.technique_group,
+                           pe.script, sp.name
+                    FROM program_exercises pe
+                    JOIN exercises e ON e.id = pe.exercise_id
+                    LEFT JOIN set_scheme_presets sp ON sp.id = pe.preset_id
+                    WHERE pe.program_block_id = (
+                        SELECT id FROM program_blocks WHERE program_id = ? AND name = ? LIMIT 1
                     )
-                    .bind(&prog_id)
-                    .bind(&block_name)
-                    .fetch_all(pool)
-                    .await?;
+                    ORDER BY pe.order_index
+                    "#;
 
-                    for (order, ex_name, sets) in exs.clone() {
-                        let reps_csv: Option<String> = sqlx::query_scalar(
-                            r#"
-                            SELECT reps
-                              FROM program_exercises pe
-                             WHERE pe.program_block_id = (
-                               SELECT id
-                                 FROM program_blocks
-                                WHERE program_id = ? AND name = ?
-                                LIMIT 1
-                              )
-                           AND pe.exercise_id = (
-                               SELECT e.id FROM exercises e WHERE e.name = ?
-                             )
-                            "#,
-                        )
+            let mut blocks = Vec::<BlockToml>::new();
+            for (block_name, block_desc) in block_rows {
+                let exs = profiler
+                    .record(
+                        pool,
+                        exs_sql,
+                        sqlx::query_as::<
+                            _,
+                            (
+                                String,
+                                i32,
+                                Option<String>,
+                                Option<String>,
+                                Option<String>,
+                                Option<String>,
+                                Option<f32>,
+                                Option<String>,
+                                Option<i32>,
+                                Option<String>,
+                                Option<String>,
+                            ),
+                        >(exs_sql)
                         .bind(&prog_id)
                         .bind(&block_name)
-                        .bind(&ex_name)
-                        .fetch_one(pool)
-                        .await?;
-
-                        // format the reps into a nicer "(5, 6–10, 15 reps)" if present
-                        let reps_display = reps_csv
-                            .map(|csv| {
-                                let pretty = csv
-                                    .split(',')
-                                    .map(|s| s.trim())
-                                    .collect::<Vec<_>>()
-                                    .join(", ");
-                                format!(" ({pretty} reps)")
-                            })
-                            .unwrap_or_default();
-
-                        let connector = if order + 1 == exs.len() as i32 {
-                            "└─"
-                        } else {
-                            "├─"
-                        };
-                        let idx = format!("{}", order + 1).yellow();
+                        .fetch_all(pool),
+                    )
+                    .await?;
 
-                        println!(
-                            " {} {} {} • {} -> {} sets{}",
-                            " ".repeat(2),
-                            connector,
-                            idx,
-                            ex_name.bold(),
+                let exercises = exs
+                    .into_iter()
+                    .map(
+                        |(
+                            ex_name,
                             sets,
-                            reps_display
-                        );
-                    }
+                            reps,
+                            target_rpe,
+                            target_rm_percent,
+                            notes,
+                            program_1rm,
+                            technique,
+                            group,
+                            script,
+                            preset,
+                        )| BlockExerciseToml {
+                            name: ex_name,
+                            sets: sets as u32,
+                            reps: reps.map(|csv| csv.split(',').map(|s| s.trim().to_string()).collect()),
+                            target_rpe: target_rpe
+                                .map(|csv| csv.split(',').filter_map(|s| s.trim().parse().ok()).collect()),
+                            target_rm_percent: target_rm_percent
+                                .map(|csv| csv.split(',').filter_map(|s| s.trim().parse().ok()).collect()),
+                            notes,
+                            program_1rm,
+                            technique,
+                            group: group.map(|g| g as u32),
+                            script,
+                            preset,
+                        },
+                    )
+                    .collect();
+
+                blocks.push(BlockToml { name: block_name, description: block_desc, exercises });
+            }
+
+            let rendered = toml::to_string_pretty(&ProgramToml { name, description, blocks })?;
+
+            match out {
+                Some(path) => {
+                    std::fs::write(&path, &rendered)?;
+                    println!("{} exported to `{}`", "info:".blue().bold(), path.display());
                 }
+                None => print!("{}", rendered),
             }
         }
 
-        ProgramCmd::Delete { program } => {
-            // Figure out the real UUID for this program.
-            let prog_id: String = if let Ok(idx) = program.parse::<i64>() {
-                // User passed a number - look up by row number.
-                match sqlx::query_scalar(
-                    r#"
-                SELECT id 
-                FROM (
-                  SELECT id, ROW_NUMBER() OVER (ORDER BY name) AS rn
-                  FROM programs
-                ) t
-                WHERE t.rn = ?
-                "#,
-                )
-                .bind(idx)
-                .fetch_one(pool)
-                .await {
-                    Ok(id) => id,
-                    Err(_) => {
-                        println!("{} no program at index {}", "error:".red().bold(), idx);
-                        return Ok(());
-                    }
-                }
-            } else {
-                // User passed a name - look up by exact name.
-                match sqlx::query_scalar("SELECT id FROM programs WHERE name = ?")
-                    .bind(&program)
-                    .fetch_one(pool)
-                    .await {
-                    Ok(id) => id,
-                    Err(_) => {
-                        println!("{} no program named `{}`", "error:".red().bold(), program);
-                        return Ok(());
-                    }
-                }
+        ProgramCmd::Report { program } => {
+            let Some(prog_id) = resolve_program_id(pool, &program, profiler).await? else {
+                return Ok(());
             };
 
-            // Get program name for confirmation message.
             let name: String = sqlx::query_scalar("SELECT name FROM programs WHERE id = ?")
                 .bind(&prog_id)
                 .fetch_one(pool)
                 .await?;
 
-            // Delete the program (cascade will handle blocks and exercises as well).
-            sqlx::query("DELETE FROM programs WHERE id = ?")
-                .bind(&prog_id)
-                .execute(pool)
+            // Same single-joined-query shape as `program show` (chunk9-4) —
+            // one round trip instead of a per-exercise lookup.
+            let report_sql = r#"
+                SELECT pb.name,
+                       e.name,
+                       e.primary_muscle,
+                       pe.sets,
+                       pe.reps,
+                       pe.target_rm_percent,
+                       pe.program_1rm
+                  FROM program_exercises pe
+                  JOIN program_blocks pb
+                    ON pb.id = pe.program_block_id
+                  JOIN exercises e
+                    ON e.id = pe.exercise_id
+                 WHERE pb.program_id = ?
+                 ORDER BY pb.name, pe.order_index
+                "#;
+            let rows = profiler
+                .record(
+                    pool,
+                    report_sql,
+                    sqlx::query_as::<
+                        _,
+                        (String, String, String, i32, Option<String>, Option<String>, Option<f32>),
+                    >(report_sql)
+                    .bind(&prog_id)
+                    .fetch_all(pool),
+                )
                 .await?;
 
-            println!("{} deleted program `{}`", "ok:".green().bold(), name);
+            let mut blocks = Vec::<ReportBlockJson>::new();
+            let mut block_accum = Vec::<LoadAccum>::new();
+            let mut block_idx = HashMap::<String, usize>::new();
+            let mut muscle_accum = HashMap::<String, LoadAccum>::new();
+            let mut program_total = LoadAccum::default();
+
+            for (block_name, ex_name, muscle, sets, reps_csv, pct_csv, program_1rm) in rows {
+                let reps_list: Vec<&str> =
+                    reps_csv.as_deref().map(|c| c.split(',').map(str::trim).collect()).unwrap_or_default();
+                let pct_list: Vec<&str> =
+                    pct_csv.as_deref().map(|c| c.split(',').map(str::trim).collect()).unwrap_or_default();
+
+                // Exercises missing a `program_1rm` or `target_rm_percent`
+                // still count toward sets, they just contribute no tonnage
+                // or intensity (no reliable weight to estimate from).
+                let mut ex_load = LoadAccum { sets, ..Default::default() };
+                if let Some(one_rm) = program_1rm {
+                    for i in 0..sets as usize {
+                        let Some(pct) = nth_or_last(&pct_list, i).and_then(|s| s.parse::<f32>().ok()) else {
+                            continue;
+                        };
+                        let rep = nth_or_last(&reps_list, i).and_then(rep_lower_bound).unwrap_or(0.0);
+                        ex_load.tonnage += rep * (pct / 100.0 * one_rm);
+                        ex_load.intensity_sum += pct;
+                        ex_load.intensity_sets += 1;
+                    }
+                }
+
+                muscle_accum.entry(muscle.clone()).or_default().add(&ex_load);
+                program_total.add(&ex_load);
+
+                let idx = *block_idx.entry(block_name.clone()).or_insert_with(|| {
+                    blocks.push(ReportBlockJson {
+                        name: block_name.clone(),
+                        sets: 0,
+                        tonnage: 0.0,
+                        avg_intensity_percent: None,
+                        exercises: Vec::new(),
+                    });
+                    block_accum.push(LoadAccum::default());
+                    blocks.len() - 1
+                });
+                block_accum[idx].add(&ex_load);
+                blocks[idx].exercises.push(ReportExerciseJson {
+                    name: ex_name,
+                    muscle,
+                    sets,
+                    tonnage: ex_load.tonnage,
+                    avg_intensity_percent: ex_load.avg_intensity(),
+                });
+            }
+
+            for (block, accum) in blocks.iter_mut().zip(&block_accum) {
+                block.sets = accum.sets;
+                block.tonnage = accum.tonnage;
+                block.avg_intensity_percent = accum.avg_intensity();
+            }
+
+            let mut by_muscle: Vec<ReportMuscleJson> = muscle_accum
+                .into_iter()
+                .map(|(muscle, accum)| ReportMuscleJson {
+                    muscle,
+                    sets: accum.sets,
+                    tonnage: accum.tonnage,
+                    avg_intensity_percent: accum.avg_intensity(),
+                })
+                .collect();
+            by_muscle.sort_by(|a, b| b.sets.cmp(&a.sets).then_with(|| a.muscle.cmp(&b.muscle)));
+
+            let report = ProgramReportJson {
+                name,
+                total_sets: program_total.sets,
+                total_tonnage: program_total.tonnage,
+                avg_intensity_percent: program_total.avg_intensity(),
+                blocks,
+                by_muscle,
+            };
+
+            emit(fmt, &report, || pretty_print_report(&report));
         }
     }
     Ok(())
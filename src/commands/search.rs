@@ -0,0 +1,133 @@
+use crate::{cli::ExerciseCmd, commands::exercise, types::Config, OutputFmt};
+use anyhow::Result;
+use colored::Colorize;
+use sqlx::{Row, SqlitePool};
+use strsim::levenshtein;
+
+struct Candidate {
+    name: String,
+    primary_muscle: String,
+    matched_words: usize,
+    typo_distance: usize,
+    is_prefix: bool,
+    proximity: usize,
+    exact: bool,
+}
+
+/// Cascading Meilisearch-style ranking: each criterion only breaks ties left
+/// by the previous one.
+fn rank(query_words: &[String], name: &str, primary_muscle: &str) -> Candidate {
+    let lower_name = name.to_ascii_lowercase();
+    let name_words: Vec<&str> = lower_name.split_whitespace().collect();
+
+    let mut matched_words = 0;
+    let mut typo_distance = 0;
+    let mut match_positions = Vec::new();
+
+    for (qi, qw) in query_words.iter().enumerate() {
+        // Find the name word closest to this query word.
+        let best = name_words
+            .iter()
+            .enumerate()
+            .map(|(ni, nw)| (ni, levenshtein(qw, nw)))
+            .min_by_key(|(_, d)| *d);
+
+        if let Some((ni, dist)) = best {
+            // Treat anything within ~1/3 edits of the word length as a match.
+            let threshold = (qw.len() / 3).max(1);
+            if dist <= threshold {
+                matched_words += 1;
+                typo_distance += dist;
+                match_positions.push((qi, ni));
+            }
+        }
+    }
+
+    let is_prefix = lower_name.starts_with(&query_words.join(" "));
+
+    // Proximity: how far matched query/name word indices drift apart,
+    // summed — 0 means the match follows the query's own word order exactly.
+    let proximity = match_positions
+        .windows(2)
+        .map(|w| {
+            let (q0, n0) = w[0];
+            let (q1, n1) = w[1];
+            ((q1 as isize - q0 as isize) - (n1 as isize - n0 as isize)).unsigned_abs()
+        })
+        .sum();
+
+    let exact = lower_name == query_words.join(" ");
+
+    Candidate {
+        name: name.to_string(),
+        primary_muscle: primary_muscle.to_string(),
+        matched_words,
+        typo_distance,
+        is_prefix,
+        proximity,
+        exact,
+    }
+}
+
+pub async fn handle(query: Vec<String>, pool: &SqlitePool, fmt: OutputFmt, cfg: &Config) -> Result<()> {
+    let query_str = query.join(" ");
+    let query_words: Vec<String> = query_str
+        .to_ascii_lowercase()
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+
+    if query_words.is_empty() {
+        println!("{} empty search query", "warning:".yellow().bold());
+        return Ok(());
+    }
+
+    let rows = sqlx::query("SELECT name, primary_muscle FROM exercises")
+        .fetch_all(pool)
+        .await?;
+
+    let mut candidates: Vec<Candidate> = rows
+        .iter()
+        .map(|r| {
+            let name: String = r.get("name");
+            let muscle: String = r.get("primary_muscle");
+            rank(&query_words, &name, &muscle)
+        })
+        .filter(|c| c.matched_words > 0)
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        b.matched_words
+            .cmp(&a.matched_words)
+            .then(a.typo_distance.cmp(&b.typo_distance))
+            .then(b.is_prefix.cmp(&a.is_prefix))
+            .then(a.proximity.cmp(&b.proximity))
+            .then(b.exact.cmp(&a.exact))
+            .then(a.name.cmp(&b.name))
+    });
+
+    if candidates.is_empty() {
+        println!("{} no exercises match `{}`", "warning:".yellow().bold(), query_str);
+        return Ok(());
+    }
+
+    println!("{} for `{}`", "Search results".cyan().bold(), query_str);
+    for c in candidates.iter().take(10) {
+        println!("  {} ({})", c.name.bold(), c.primary_muscle.yellow());
+    }
+
+    // Reuse the exercise Show display for the best-ranked match.
+    println!();
+    exercise::handle(
+        ExerciseCmd::Show {
+            exercise: vec![candidates[0].name.clone()],
+            graph: false,
+            unit: None,
+            formula: None,
+        },
+        pool,
+        fmt,
+        cfg,
+    )
+    .await
+}
@@ -1,13 +1,56 @@
 use anyhow::Result;
 use colored::Colorize;
 use sqlx::SqlitePool;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 use chrono::NaiveDate;
 
-use crate::cli::SessionCmd;
+use crate::{cli::SessionCmd, scripting::ScriptContext, types::{Config, OutputFmt, OutputFormat, Weight}};
+
+/// Gathers the facts a prescription script is allowed to see for `exercise_id`:
+/// its best known 1RM and its most recent session's logged sets.
+async fn build_script_context(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    exercise_id: &str,
+    week: i64,
+) -> Result<ScriptContext> {
+    let e1rm: Option<f64> = sqlx::query_scalar(
+        "SELECT estimated_1rm FROM personal_records WHERE exercise_id = ? ORDER BY estimated_1rm DESC LIMIT 1",
+    )
+    .bind(exercise_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let last_sets: Vec<(f64, i64)> = sqlx::query_as::<_, (f64, i64)>(
+        r#"
+        SELECT es.weight, es.reps
+        FROM exercise_sets es
+        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+        WHERE tse.exercise_id = ? AND es.deleted_at IS NULL
+          AND tse.training_session_id = (
+              SELECT tse2.training_session_id
+              FROM exercise_sets es2
+              JOIN training_session_exercises tse2 ON tse2.id = es2.session_exercise_id
+              WHERE tse2.exercise_id = ? AND es2.deleted_at IS NULL
+              ORDER BY es2.timestamp DESC
+              LIMIT 1
+          )
+        ORDER BY es.timestamp DESC
+        "#,
+    )
+    .bind(exercise_id)
+    .bind(exercise_id)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(ScriptContext {
+        e1rm: e1rm.unwrap_or(0.0),
+        last_sets,
+        week,
+    })
+}
 
-pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
+pub async fn handle(cmd: SessionCmd, pool: &SqlitePool, fmt: OutputFmt, cfg: &Config) -> Result<()> {
     match cmd {
         SessionCmd::Start(args) => {
             // First, resolve the program name/index to its ID
@@ -34,14 +77,21 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     }
                 }
             } else {
-                // User passed a name - look up by exact name.
-                match sqlx::query_scalar("SELECT id FROM programs WHERE name = ?")
-                    .bind(&args.program)
-                    .fetch_one(pool)
-                    .await
-                {
-                    Ok(id) => id,
-                    Err(_) => {
+                // User passed a name - resolve it by exact match, then
+                // prefix, then fuzzy subsequence, rather than hard-failing
+                // on anything short of the exact name.
+                let rows: Vec<(String, String)> =
+                    sqlx::query_as("SELECT id, name FROM programs")
+                        .fetch_all(pool)
+                        .await?;
+                let candidates: Vec<crate::resolve::Candidate<String>> = rows
+                    .into_iter()
+                    .map(|(id, name)| crate::resolve::Candidate { name, value: id })
+                    .collect();
+
+                match crate::resolve::resolve(&candidates, &args.program) {
+                    crate::resolve::Resolution::Found(id) => id,
+                    crate::resolve::Resolution::NotFound => {
                         println!(
                             "{} no program named `{}`",
                             "error:".red().bold(),
@@ -49,6 +99,17 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                         );
                         return Ok(());
                     }
+                    crate::resolve::Resolution::Ambiguous(names) => {
+                        println!(
+                            "{} `{}` matches multiple programs, be more specific:",
+                            "error:".red().bold(),
+                            args.program
+                        );
+                        for (i, name) in names.iter().enumerate() {
+                            println!("  {} {}", format!("{}", i + 1).yellow(), name);
+                        }
+                        return Ok(());
+                    }
                 }
             };
 
@@ -83,17 +144,21 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     }
                 }
             } else {
-                // User passed a name - look up by exact name.
-                match sqlx::query_scalar(
-                    "SELECT id FROM program_blocks WHERE program_id = ? AND name = ?",
-                )
-                .bind(&prog_id)
-                .bind(&args.block)
-                .fetch_one(pool)
-                .await
-                {
-                    Ok(id) => id,
-                    Err(_) => {
+                // User passed a name - resolve it the same way as the
+                // program above: exact, then prefix, then fuzzy subsequence.
+                let rows: Vec<(String, String)> =
+                    sqlx::query_as("SELECT id, name FROM program_blocks WHERE program_id = ?")
+                        .bind(&prog_id)
+                        .fetch_all(pool)
+                        .await?;
+                let candidates: Vec<crate::resolve::Candidate<String>> = rows
+                    .into_iter()
+                    .map(|(id, name)| crate::resolve::Candidate { name, value: id })
+                    .collect();
+
+                match crate::resolve::resolve(&candidates, &args.block) {
+                    crate::resolve::Resolution::Found(id) => id,
+                    crate::resolve::Resolution::NotFound => {
                         println!(
                             "{} no block named `{}` in program `{}`",
                             "error:".red().bold(),
@@ -102,6 +167,18 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                         );
                         return Ok(());
                     }
+                    crate::resolve::Resolution::Ambiguous(names) => {
+                        println!(
+                            "{} `{}` matches multiple blocks in `{}`, be more specific:",
+                            "error:".red().bold(),
+                            args.block,
+                            args.program
+                        );
+                        for (i, name) in names.iter().enumerate() {
+                            println!("  {} {}", format!("{}", i + 1).yellow(), name);
+                        }
+                        return Ok(());
+                    }
                 }
             };
 
@@ -133,9 +210,9 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
             .await?;
 
             // Get all exercises for this block.
-            let exercises = sqlx::query_as::<_, (String, String, i32, Option<String>)>(
+            let exercises = sqlx::query_as::<_, (String, String, i32, Option<String>, Option<String>)>(
                 r#"
-                SELECT e.id, e.name, pe.sets, pe.reps
+                SELECT e.id, e.name, pe.sets, pe.reps, pe.script
                 FROM program_exercises pe
                 JOIN exercises e ON e.id = pe.exercise_id
                 WHERE pe.program_block_id = ?
@@ -148,7 +225,7 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
 
             // Create session exercise records.
             println!("{}", "Exercises:".cyan().bold());
-            for (i, (ex_id, ex_name, sets, reps)) in exercises.iter().enumerate() {
+            for (i, (ex_id, ex_name, sets, reps, script)) in exercises.iter().enumerate() {
                 let session_ex_id = Uuid::new_v4().to_string();
                 sqlx::query(
                     "INSERT INTO training_session_exercises (id, training_session_id, exercise_id) VALUES (?, ?, ?)",
@@ -159,10 +236,37 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 .execute(&mut *tx)
                 .await?;
 
+                // If a prescription script is attached, run it against this
+                // exercise's known history and use its output in place of
+                // the literal `reps` column.
+                let scripted_reps = if let Some(script) = script {
+                    match build_script_context(&mut tx, ex_id, args.week.unwrap_or(0) as i64).await {
+                        Ok(ctx) => match crate::scripting::eval_prescription(script, &ctx) {
+                            Ok(sets) => Some(sets.join(", ")),
+                            Err(e) => {
+                                println!(
+                                    "{} {}: {}",
+                                    "warning:".yellow().bold(),
+                                    ex_name,
+                                    e
+                                );
+                                None
+                            }
+                        },
+                        Err(e) => {
+                            println!("{} {}: {}", "warning:".yellow().bold(), ex_name, e);
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+
                 // Print exercise info.
                 let idx = format!("{}", i + 1).yellow();
-                let reps_display = reps
+                let reps_display = scripted_reps
                     .as_deref()
+                    .or(reps.as_deref())
                     .map(|r| format!(" ({})", r))
                     .unwrap_or_default();
                 println!(
@@ -209,7 +313,11 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
             }
         }
 
-        SessionCmd::Show => {
+        SessionCmd::Show { history } => {
+            if fmt.format == OutputFormat::Csv {
+                return print_session_sets_csv(pool, cfg).await;
+            }
+
             // Get current session info
             let session: Option<(String, String, String, String)> = sqlx::query_as(
                 r#"
@@ -364,6 +472,8 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                                 JOIN training_sessions ts ON ts.id = tse.training_session_id
                                 WHERE tse.exercise_id = ?
                                 AND ts.end_time IS NOT NULL  -- Only completed sessions
+                                AND ts.deleted_at IS NULL
+                                AND es.deleted_at IS NULL
                                 AND es.weight > 0  -- Skip empty sets
                             ),
                             last_sets AS (
@@ -387,8 +497,9 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                         .fetch_optional(pool)
                         .await?;
 
+                        let unit = cfg.weight_unit();
                         let prev_info = prev_set
-                            .map(|(w, r)| format!(" - {}kg × {}", w, r))
+                            .map(|(w, r)| format!(" - {}{} × {}", unit.from_kg(w), unit.suffix(), r))
                             .unwrap_or_default();
 
                         exercise_prev_sets.push(prev_info);
@@ -443,15 +554,14 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                         .unwrap_or((None, None, None));
 
                     // Print exercise header with PR info
-                    let pr_info = if let (Some(w), Some(r)) = (pr_weight, pr_reps) {
-                        let one_rm = pr_1rm.unwrap_or_else(|| epley_1rm(w, r).round());
-                        let actual_pr = format!("{}kg × {}", w, r).red().bold().to_string();
-                        format!(" - PR: {} (1RM: {:.1}kg)", actual_pr, one_rm)
-                    } else {
-                        String::new()
-                    };
+                    let pr_info =
+                        format_pr_info(pool, ex_id, pr_weight, pr_reps, pr_1rm, cfg.weight_unit(), cfg.one_rm_formula()).await?;
 
-                    println!("{} • {}{}", idx, ex_name.bold(), pr_info.dimmed());
+                    println!("{} • {}{}", idx, ex_name.bold(), pr_info);
+
+                    if history {
+                        print_exercise_history(pool, ex_id, cfg.weight_unit()).await?;
+                    }
 
                     // Print exercise note if it exists
                     let note: Option<String> = sqlx::query_scalar(
@@ -468,16 +578,43 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     }
 
                     // Parse target values
-                    let target_rpes: Vec<f32> = _target_rpe
+                    let mut target_rpes: Vec<f32> = _target_rpe
                         .as_deref()
                         .map(|s| s.split(',').filter_map(|v| v.trim().parse().ok()).collect())
                         .unwrap_or_default();
 
-                    let target_rms: Vec<f32> = _target_rm_percent
+                    let mut target_rms: Vec<f32> = _target_rm_percent
                         .as_deref()
                         .map(|s| s.split(',').filter_map(|v| v.trim().parse().ok()).collect())
                         .unwrap_or_default();
 
+                    // No literal target columns — fall back to this
+                    // exercise's set-scheme preset, if it has one.
+                    if target_rpes.is_empty() && target_rms.is_empty() {
+                        let preset_id: Option<String> = sqlx::query_as::<_, (Option<String>,)>(
+                            r#"
+                            SELECT pe.preset_id
+                            FROM program_exercises pe
+                            WHERE pe.exercise_id = ?
+                            AND pe.program_block_id = (SELECT program_block_id FROM training_sessions WHERE id = ?)
+                            "#,
+                        )
+                        .bind(ex_id)
+                        .bind(&session_id)
+                        .fetch_optional(pool)
+                        .await?
+                        .and_then(|(id,)| id);
+
+                        if let Some(preset_id) = preset_id {
+                            if let Some((kind, values)) = crate::preset::expand_by_id(pool, &preset_id).await? {
+                                match kind {
+                                    crate::preset::PresetKind::Percent => target_rms = values,
+                                    crate::preset::PresetKind::Rpe => target_rpes = values,
+                                }
+                            }
+                        }
+                    }
+
                     // Print sets
                     let reps_display = reps
                         .as_deref()
@@ -485,18 +622,19 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                         .unwrap_or_default();
 
                     // Get all logged sets for this exercise
-                    let logged_sets_1_based_num = sqlx::query_as::<_, (i64, f32, i32, bool)>(
+                    let logged_sets_1_based_num = sqlx::query_as::<_, (i64, f32, i32, bool, Option<f32>)>(
                         r#"
                         WITH set_numbers AS (
-                            SELECT 
+                            SELECT
                                 es.*,
                                 ROW_NUMBER() OVER (PARTITION BY tse.id ORDER BY es.timestamp) as set_num -- 1-based
                             FROM exercise_sets es
                             JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
                             WHERE tse.exercise_id = ?
                             AND tse.training_session_id = ?
+                            AND es.deleted_at IS NULL
                         )
-                        SELECT set_num, weight, reps, bodyweight
+                        SELECT set_num, weight, reps, bodyweight, rpe
                         FROM set_numbers
                         ORDER BY set_num
                         "#,
@@ -507,11 +645,24 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     .await?;
 
                     // Convert to 0-based set numbers for internal processing
-                    let logged_sets_0_based_num: Vec<(i64, f32, i32, bool)> = logged_sets_1_based_num
+                    let logged_sets_0_based_num: Vec<(i64, f32, i32, bool, Option<f32>)> = logged_sets_1_based_num
                         .into_iter()
-                        .map(|(snum_1_based, w, r, b)| (snum_1_based - 1, w, r, b)) // Convert to 0-based set_num
+                        .map(|(snum_1_based, w, r, b, rpe)| (snum_1_based - 1, w, r, b, rpe)) // Convert to 0-based set_num
                         .collect();
 
+                    // Today's session e1RM, estimated from the first logged
+                    // weighted set that has an RPE attached — used to
+                    // autoregulate the remaining sets' target weights instead
+                    // of relying only on the program's static 1RM.
+                    let today_e1rm: Option<f32> = logged_sets_0_based_num
+                        .iter()
+                        .find_map(|(_, w, r, bw, set_rpe)| {
+                            if *bw || *w <= 0.0 {
+                                return None;
+                            }
+                            set_rpe.map(|rpe| *w / (percent_1rm_for_rpe(*r, rpe) / 100.0))
+                        });
+
                     // If no sets are logged yet, show the program's sets (using 0-based set_num)
                     let sets_to_show = if logged_sets_0_based_num.is_empty() {
                         (0..*sets) // Iterate 0-based
@@ -521,22 +672,21 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                         let mut all_sets = Vec::new();
                         // First add all sets up to the program's set count (using 0-based set_num)
                         for i_0_based in 0..*sets { // Iterate 0-based program sets
-                            if let Some(set) = logged_sets_0_based_num
+                            if let Some((s_0_based, w, r, b, _)) = logged_sets_0_based_num
                                 .iter()
-                                .find(|(s_0_based, _, _, _)| *s_0_based == i_0_based as i64)
+                                .find(|(s_0_based, _, _, _, _)| *s_0_based == i_0_based as i64)
                             {
-                                all_sets.push(*set); // s_0_based is already 0-based
+                                all_sets.push((*s_0_based, *w, *r, *b)); // s_0_based is already 0-based
                             } else {
                                 all_sets.push((i_0_based as i64, 0.0, 0, false)); // Placeholder with 0-based set_num
                             }
                         }
                         // Then add any additional sets beyond the program's set count (using 0-based set_num)
                         // Additional sets are those with 0-based index >= program's set count
-                        for set_to_add in logged_sets_0_based_num.iter().filter(|(s_0_based, _, _, _)| *s_0_based >= *sets as i64) {
+                        for (s_0_based, w, r, b, _) in logged_sets_0_based_num.iter().filter(|(s_0_based, _, _, _, _)| *s_0_based >= *sets as i64) {
                             // Avoid duplicating sets
-                            let s_0_based_to_add = set_to_add.0;
-                            if !all_sets.iter().any(|(added_s_0,_,_,_)| *added_s_0 == s_0_based_to_add) {
-                                all_sets.push(*set_to_add);
+                            if !all_sets.iter().any(|(added_s_0,_,_,_)| added_s_0 == s_0_based) {
+                                all_sets.push((*s_0_based, *w, *r, *b));
                             }
                         }
                         all_sets.sort_by_key(|(s_0_based, _, _, _)| *s_0_based);
@@ -546,26 +696,87 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     // Display all sets
                     for (set_num_0_based_in_loop, weight, reps, bw) in sets_to_show {
                         let set_num_usize = set_num_0_based_in_loop as usize; // 0-based for array indexing
-                        let target_info = if let Some(program_1rm) = _program_1rm {
-                            if set_num_usize < target_rpes.len() {
-                                format!(" @RPE {}", target_rpes[set_num_usize])
-                            } else if set_num_usize < target_rms.len() {
-                                let target_weight =
-                                    program_1rm * (target_rms[set_num_usize] / 100.0);
-                                format!(
-                                    " @{}% ({}kg)",
-                                    target_rms[set_num_usize],
-                                    target_weight.round()
-                                )
-                            } else {
-                                String::new()
+                        let not_yet_logged = weight <= 0.0;
+                        let set_reps_target: Option<i32> = reps_display
+                            .get(set_num_usize)
+                            .and_then(|r| r.trim().parse().ok());
+
+                        // RPE-anchored targets never depended on `program_1rm`
+                        // to begin with, so they're checked first regardless
+                        // of whether the program set a static 1RM.
+                        let unit = cfg.weight_unit();
+                        let target_info = if set_num_usize < target_rpes.len() {
+                            let rpe = target_rpes[set_num_usize];
+                            match (not_yet_logged, today_e1rm, set_reps_target) {
+                                (true, Some(e1rm), Some(target_reps)) => {
+                                    let suggested =
+                                        e1rm * (percent_1rm_for_rpe(target_reps, rpe) / 100.0);
+                                    format!(
+                                        " @RPE {} → {}{}",
+                                        rpe,
+                                        unit.from_kg(suggested).round(),
+                                        unit.suffix()
+                                    )
+                                }
+                                _ => format!(" @RPE {}", rpe),
+                            }
+                        } else if set_num_usize < target_rms.len() {
+                            // `programmed_weight` only exists for blocks with
+                            // a literal static `program_1rm`; a preset's
+                            // percent scheme has no such number of its own —
+                            // it's always relative to today's top set.
+                            let pct = target_rms[set_num_usize];
+                            let programmed_weight = _program_1rm.map(|p| p * (pct / 100.0));
+                            // Once today's e1RM is known, show the
+                            // autoregulated suggestion alongside (or instead
+                            // of) the stale programmed number rather than
+                            // letting remaining sets cling to it.
+                            match (not_yet_logged, today_e1rm, programmed_weight) {
+                                (true, Some(e1rm), Some(prog)) => format!(
+                                    " @{}% → {}{} (prog {}{})",
+                                    pct,
+                                    unit.from_kg(e1rm * (pct / 100.0)).round(),
+                                    unit.suffix(),
+                                    unit.from_kg(prog).round(),
+                                    unit.suffix()
+                                ),
+                                (true, Some(e1rm), None) => format!(
+                                    " @{}% → {}{}",
+                                    pct,
+                                    unit.from_kg(e1rm * (pct / 100.0)).round(),
+                                    unit.suffix()
+                                ),
+                                (_, _, Some(prog)) => format!(
+                                    " @{}% ({}{})",
+                                    pct,
+                                    unit.from_kg(prog).round(),
+                                    unit.suffix()
+                                ),
+                                (_, _, None) => format!(" @{}%", pct),
                             }
                         } else {
-                            if set_num_usize < target_rpes.len() {
-                                format!(" @RPE {}", target_rpes[set_num_usize])
-                            } else {
-                                String::new()
+                            String::new()
+                        };
+
+                        // This set's target load in kg, regardless of whether
+                        // it's been logged yet — feeds the intensity bar
+                        // below. Same e1RM-over-programmed priority as
+                        // `target_info` above, just the number instead of the
+                        // formatted string.
+                        let target_weight_kg: Option<f32> = if set_num_usize < target_rpes.len() {
+                            let rpe = target_rpes[set_num_usize];
+                            match (today_e1rm, set_reps_target) {
+                                (Some(e1rm), Some(target_reps)) => {
+                                    Some(e1rm * (percent_1rm_for_rpe(target_reps, rpe) / 100.0))
+                                }
+                                _ => None,
                             }
+                        } else if set_num_usize < target_rms.len() {
+                            let pct = target_rms[set_num_usize];
+                            let programmed_weight = _program_1rm.map(|p| p * (pct / 100.0));
+                            today_e1rm.map(|e1rm| e1rm * (pct / 100.0)).or(programmed_weight)
+                        } else {
+                            None
                         };
 
                         // Get previous set info from our pre-calculated list
@@ -574,8 +785,9 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                         } else {
                             "" // Empty string for additional sets beyond program's set count
                         };
+                        let (target_width, prev_width) = table_column_widths(max_prev_width);
                         let prev_column =
-                            format!("{:<width$}", prev_info, width = max_prev_width).dimmed();
+                            format!("{:<width$}", truncate_ellipsis(prev_info, prev_width), width = prev_width).dimmed();
 
                         let target_reps = if set_num_usize < reps_display.len() {
                             format!("{} reps", reps_display[set_num_usize])
@@ -583,39 +795,60 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                             String::from("do your thing")
                         };
 
-                        let target_padding = if (target_reps.len() + target_info.len()) < 25 {
-                            25 - (target_reps.len() + target_info.len())
-                        } else {
-                            0
-                        };
-
                         // Create all parts of the display separately
                         let set_num_str = format!("{}", set_num_0_based_in_loop + 1).yellow(); // Display as 1-based
                         let indent = " ".repeat(2);
+                        let target_raw = truncate_ellipsis(&format!("{}{}", target_reps, target_info), target_width);
                         let target_part = if target_reps.is_empty() {
                             String::new()
+                        } else if target_raw.chars().count() <= target_reps.chars().count() {
+                            target_raw.clone()
                         } else {
-                            format!("{}{}", target_reps, target_info.dimmed())
+                            let reps_len = target_reps.chars().count();
+                            let mut chars = target_raw.chars();
+                            let reps_part: String = chars.by_ref().take(reps_len).collect();
+                            let info_part: String = chars.collect();
+                            format!("{}{}", reps_part, info_part.dimmed())
                         };
-                        let padding = " ".repeat(target_padding);
+                        let padding = " ".repeat(target_width.saturating_sub(target_raw.chars().count()));
 
                         let current_info = if bw {
                             format!("bw × {}", reps)
                         } else if weight > 0.0 {
-                            format!("{}kg × {}", weight, reps)
+                            let unit = cfg.weight_unit();
+                            format!("{}{} × {}", unit.from_kg(weight), unit.suffix(), reps)
                         } else {
                             String::new()
                         };
 
+                        // Logged weight×reps against the prescribed target,
+                        // as a compact bar — only when there's both a logged
+                        // weighted set and a target load/rep count to compare
+                        // it against (bodyweight sets have no kg target here).
+                        let bar_part = match (bw, not_yet_logged, target_weight_kg, set_reps_target) {
+                            (false, false, Some(target_kg), Some(target_reps)) if target_kg > 0.0 => {
+                                let formula = cfg.one_rm_formula();
+                                let target_1rm = formula.estimate(target_kg, target_reps);
+                                if target_1rm > 0.0 {
+                                    let logged_1rm = formula.estimate(weight, reps);
+                                    format!(" {}", intensity_bar(logged_1rm / target_1rm))
+                                } else {
+                                    String::new()
+                                }
+                            }
+                            _ => String::new(),
+                        };
+
                         // Print with explicit parts
                         println!(
-                            " {} {} • {} {}{} | {}",
+                            " {} {} • {} {}{} | {}{}",
                             indent,
                             set_num_str,
                             target_part,
                             padding,
                             prev_column,
-                            current_info
+                            current_info,
+                            bar_part
                         );
                     }
                     println!();
@@ -629,6 +862,7 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
             exercise,
             weight,
             reps,
+            rpe,
             set,
             new,
         } => {
@@ -646,13 +880,24 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 }
             };
 
-            // Parse weight - handle bodyweight exercises
-            let (is_bodyweight, parsed_weight) = if weight.to_lowercase() == "bw" {
+            // Parse weight - handle bodyweight exercises (plain "bw", or
+            // "bw+20"/"bw+20lb" for any added weight) and kg/lb suffixes,
+            // defaulting to the configured unit when none is given.
+            let lower_weight = weight.to_lowercase();
+            let (is_bodyweight, parsed_weight) = if lower_weight == "bw" {
                 (true, None)
+            } else if let Some(added) = lower_weight.strip_prefix("bw+") {
+                match Weight::parse(added, cfg.weight_unit()) {
+                    Some(w) => (true, Some(w.kg)),
+                    None => {
+                        println!("{} invalid added weight: {}", "error:".red().bold(), weight);
+                        return Ok(());
+                    }
+                }
             } else {
-                match weight.parse::<f32>() {
-                    Ok(w) => (false, Some(w)),
-                    Err(_) => {
+                match Weight::parse(&weight, cfg.weight_unit()) {
+                    Some(w) => (false, Some(w.kg)),
+                    None => {
                         println!("{} invalid weight: {}", "error:".red().bold(), weight);
                         return Ok(());
                     }
@@ -706,7 +951,7 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     r#"
                     SELECT COUNT(*)
                     FROM exercise_sets
-                    WHERE session_exercise_id = ?
+                    WHERE session_exercise_id = ? AND deleted_at IS NULL
                     "#,
                 )
                 .bind(&session_exercise_id)
@@ -718,7 +963,7 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     r#"
                     SELECT COUNT(*)
                     FROM exercise_sets
-                    WHERE session_exercise_id = ?
+                    WHERE session_exercise_id = ? AND deleted_at IS NULL
                     "#,
                 )
                 .bind(&session_exercise_id)
@@ -739,10 +984,10 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     )
                 ),
                 set_numbers AS (
-                    SELECT 
+                    SELECT
                         ROW_NUMBER() OVER (ORDER BY timestamp) - 1 as set_num
                     FROM exercise_sets
-                    WHERE session_exercise_id = ?
+                    WHERE session_exercise_id = ? AND deleted_at IS NULL
                 ),
                 additional_sets AS (
                     SELECT COUNT(*) as extra_sets
@@ -777,12 +1022,12 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
             let existing_set: Option<(String, String)> = sqlx::query_as(
                 r#"
                 WITH set_numbers AS (
-                    SELECT 
+                    SELECT
                         es.id,
                         es.timestamp,
                         ROW_NUMBER() OVER (PARTITION BY es.session_exercise_id ORDER BY es.timestamp) as set_num
                     FROM exercise_sets es
-                    WHERE es.session_exercise_id = ?
+                    WHERE es.session_exercise_id = ? AND es.deleted_at IS NULL
                 )
                 SELECT id, timestamp
                 FROM set_numbers
@@ -800,17 +1045,14 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 sqlx::query(
                     r#"
                     UPDATE exercise_sets
-                    SET weight = ?, reps = ?, bodyweight = ?
+                    SET weight = ?, reps = ?, bodyweight = ?, rpe = ?, last_updated = unixepoch()
                     WHERE id = ?
                     "#,
                 )
-                .bind(if is_bodyweight {
-                    0.0
-                } else {
-                    parsed_weight.unwrap_or(0.0)
-                })
+                .bind(parsed_weight.unwrap_or(0.0))
                 .bind(reps)
                 .bind(is_bodyweight as i32)
+                .bind(rpe)
                 .bind(&set_id)
                 .execute(&mut *tx)
                 .await?;
@@ -824,76 +1066,76 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                         weight,
                         reps,
                         bodyweight,
+                        rpe,
                         timestamp
-                    ) VALUES (?, ?, ?, ?, ?, datetime('now'))
+                    ) VALUES (?, ?, ?, ?, ?, ?, datetime('now'))
                     "#,
                 )
                 .bind(Uuid::new_v4().to_string())
                 .bind(&session_exercise_id)
-                .bind(if is_bodyweight {
-                    0.0
-                } else {
-                    parsed_weight.unwrap_or(0.0)
-                })
+                .bind(parsed_weight.unwrap_or(0.0))
                 .bind(reps)
                 .bind(is_bodyweight as i32)
+                .bind(rpe)
                 .execute(&mut *tx)
                 .await?;
             }
 
+            // Effective load for this set: for weighted exercises it's just
+            // the logged weight; for bodyweight exercises it's the most
+            // recently logged bodyweight plus any added weight, so both kinds
+            // feed the same estimated-1RM pipeline.
+            let effective_weight = if is_bodyweight {
+                crate::commands::measure::latest_bodyweight_kg(&mut *tx).await?.unwrap_or(0.0)
+                    + parsed_weight.unwrap_or(0.0)
+            } else {
+                parsed_weight.unwrap_or(0.0)
+            };
+
+            // Snapshot the rating before folding this set in, so the printed
+            // "new personal record" note can require clearing the old upper
+            // bound (rating + 2*rd) rather than the raw all-time max — same
+            // reasoning as `SessionCmd::End`.
+            let prior_rating = crate::rating::current(&mut *tx, &exercise_id).await?;
+            let observed_1rm = epley_1rm(effective_weight, reps);
+            if effective_weight > 0.0 {
+                crate::rating::update_after_session(&mut tx, &exercise_id, effective_weight, reps).await?;
+            }
+            let updated_rating = crate::rating::current(&mut *tx, &exercise_id).await?;
+            let is_notable = match prior_rating {
+                Some((r, rd)) => observed_1rm as f64 > r + 2.0 * rd,
+                None => effective_weight > 0.0,
+            };
+
             // Check if this is a new PR
-            let is_pr = if !is_bodyweight {
-                let (pr_weight, pr_reps): (Option<f32>, Option<i32>) = sqlx::query_as(
-                    r#"
-                    SELECT weight, reps
-                    FROM personal_records
-                    WHERE exercise_id = ?
-                    ORDER BY estimated_1rm DESC
-                    LIMIT 1
-                    "#,
-                )
-                .bind(&exercise_id)
-                .fetch_optional(&mut *tx)
-                .await?
-                .unwrap_or((None, None));
+            let (pr_weight, pr_reps): (Option<f32>, Option<i32>) = sqlx::query_as(
+                r#"
+                SELECT weight, reps
+                FROM personal_records
+                WHERE exercise_id = ?
+                ORDER BY estimated_1rm DESC
+                LIMIT 1
+                "#,
+            )
+            .bind(&exercise_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .unwrap_or((None, None));
 
-                if let (Some(pr_weight), Some(pr_reps)) = (pr_weight, pr_reps) {
-                    // For non-bodyweight exercises, compare weight × reps
-                    let current_total = parsed_weight.unwrap_or(0.0) * reps as f32;
-                    let pr_total = pr_weight * pr_reps as f32;
-                    current_total > pr_total
-                } else {
-                    // No previous PR, so this is a PR
-                    true
-                }
+            let is_pr = if let (Some(pr_weight), Some(pr_reps)) = (pr_weight, pr_reps) {
+                let current_total = effective_weight * reps as f32;
+                let pr_total = pr_weight * pr_reps as f32;
+                current_total > pr_total
             } else {
-                // For bodyweight exercises, just compare reps
-                let max_reps: Option<i32> = sqlx::query_scalar(
-                    r#"
-                    SELECT reps
-                    FROM personal_records
-                    WHERE exercise_id = ? AND bodyweight = 1
-                    ORDER BY reps DESC
-                    LIMIT 1
-                    "#,
-                )
-                .bind(&exercise_id)
-                .fetch_optional(&mut *tx)
-                .await?;
-
-                match max_reps {
-                    Some(max_reps) => reps >= max_reps,
-                    None => true, // If no previous PR, this is a PR
-                }
+                // No previous PR, so this is a PR
+                true
             };
 
             if is_pr {
-                // Calculate estimated 1RM
-                let estimated_1rm = if is_bodyweight {
-                    0.0 // For bodyweight exercises, we don't calculate 1RM
-                } else {
-                    epley_1rm(parsed_weight.unwrap_or(0.0), reps)
-                };
+                // `personal_records.estimated_1rm` is the user-facing PR
+                // number, so it's estimated with their chosen formula, not
+                // the rating pipeline's fixed Epley.
+                let estimated_1rm = cfg.one_rm_formula().estimate(effective_weight, reps);
 
                 // Insert new PR
                 sqlx::query(
@@ -908,11 +1150,7 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     "#,
                 )
                 .bind(&exercise_id)
-                .bind(if is_bodyweight {
-                    0.0
-                } else {
-                    parsed_weight.unwrap_or(0.0)
-                })
+                .bind(effective_weight)
                 .bind(reps)
                 .bind(estimated_1rm)
                 .execute(&mut *tx)
@@ -923,7 +1161,8 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     r#"
                     UPDATE exercises 
                     SET current_pr_date = datetime('now'),
-                        estimated_one_rm = ?
+                        estimated_one_rm = ?,
+                        last_updated = unixepoch()
                     WHERE id = ?
                     "#,
                 )
@@ -943,9 +1182,16 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 "weighted"
             };
             let weight_display = if is_bodyweight {
-                "bodyweight".to_string()
+                let unit = cfg.weight_unit();
+                match parsed_weight {
+                    Some(added) if added > 0.0 => {
+                        format!("bodyweight + {}{}", unit.from_kg(added), unit.suffix())
+                    }
+                    _ => "bodyweight".to_string(),
+                }
             } else {
-                format!("{}kg", parsed_weight.unwrap_or(0.0))
+                let unit = cfg.weight_unit();
+                format!("{}{}", unit.from_kg(parsed_weight.unwrap_or(0.0)), unit.suffix())
             };
 
             println!(
@@ -958,7 +1204,32 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 reps
             );
 
-            if is_pr {
+            // Best-effort audit trail; a log-write failure shouldn't fail the set itself.
+            let _ = crate::eventlog::append(
+                &Uuid::new_v4().to_string(),
+                "set_logged",
+                serde_json::json!({
+                    "session_id": session_id,
+                    "exercise": exercise,
+                    "set_index": set_index,
+                    "weight_kg": parsed_weight,
+                    "reps": reps,
+                    "bodyweight": is_bodyweight,
+                }),
+                false,
+            );
+
+            if let Some((rating, deviation)) = updated_rating {
+                let unit = cfg.weight_unit();
+                println!(
+                    "  est 1RM {:.1} ± {:.1}{}",
+                    unit.from_kg(rating as f32),
+                    unit.from_kg((2.0 * deviation) as f32),
+                    unit.suffix()
+                );
+            }
+
+            if is_notable {
                 println!("{} new personal record!", "note:".yellow().bold());
             }
         }
@@ -1000,7 +1271,7 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 FROM training_session_exercises tse
                 JOIN exercises e ON e.id = tse.exercise_id
                 JOIN exercise_sets es ON es.session_exercise_id = tse.id
-                WHERE tse.training_session_id = ?
+                WHERE tse.training_session_id = ? AND es.deleted_at IS NULL
                 ORDER BY es.timestamp
                 "#,
             )
@@ -1017,65 +1288,90 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     .push((reps, weight, bw));
             }
 
+            // Bodyweight doesn't vary per exercise, so fetch it once for the
+            // whole session rather than per set.
+            let session_bodyweight_kg = crate::commands::measure::latest_bodyweight_kg(&mut *tx).await?.unwrap_or(0.0);
+
             // Process PRs and exercise stats
             let mut pr_updates = Vec::new();
+            let mut rating_intervals: HashMap<String, (f64, f64)> = HashMap::new();
             for (ex_id, sets) in &exercise_sets {
-                // Calculate estimated 1RM for each set
+                // Calculate estimated 1RM for each set. Bodyweight sets price
+                // in at session_bodyweight_kg + any added weight, so they feed
+                // the same pipeline as weighted lifts instead of being
+                // compared on reps alone.
                 let mut max_1rm = 0.0;
                 let mut pr_weight = 0.0;
                 let mut pr_reps = 0;
 
                 for (reps, weight, bw) in sets {
-                    if *bw {
-                        // For bodyweight exercises, we only track reps
-                        if *reps > pr_reps {
-                            pr_reps = *reps;
-                            pr_weight = 0.0;
-                        }
-                    } else if let Some(w) = weight {
-                        // For weighted exercises, calculate estimated 1RM
-                        let est_1rm = epley_1rm(*w, *reps);
-                        if est_1rm > max_1rm {
-                            max_1rm = est_1rm;
-                            pr_weight = *w;
-                            pr_reps = *reps;
-                        }
+                    let w = if *bw {
+                        session_bodyweight_kg + weight.unwrap_or(0.0)
+                    } else {
+                        weight.unwrap_or(0.0)
+                    };
+
+                    let est_1rm = cfg.one_rm_formula().estimate(w, *reps);
+                    if est_1rm > max_1rm {
+                        max_1rm = est_1rm;
+                        pr_weight = w;
+                        pr_reps = *reps;
                     }
                 }
 
+                // Snapshot the rating *before* folding this session in, so the
+                // "new personal record" note can require the observation to
+                // clear the old upper bound (rating + 2*rd) rather than just
+                // eking past the raw all-time max — a single lucky rep
+                // shouldn't read as a confident new PR.
+                let prior_rating = crate::rating::current(&mut *tx, ex_id).await?;
+
+                // Fold this session's best working set into the exercise's
+                // time-decayed rating, regardless of whether it's a new PR —
+                // the rating is meant to track *current* strength, not just
+                // all-time bests.
+                if max_1rm > 0.0 {
+                    crate::rating::update_after_session(&mut tx, ex_id, pr_weight, pr_reps).await?;
+                }
+
+                if let Some(updated) = crate::rating::current(&mut *tx, ex_id).await? {
+                    rating_intervals.insert(ex_id.clone(), updated);
+                }
+
                 // Check if this is a new PR
                 let is_pr = sqlx::query_scalar::<_, bool>(
                     r#"
                     WITH current_pr AS (
-                        SELECT weight, reps, estimated_1rm
+                        SELECT estimated_1rm
                         FROM personal_records
                         WHERE exercise_id = ?
                         ORDER BY estimated_1rm DESC
                         LIMIT 1
                     )
-                    SELECT 
-                        CASE 
-                            WHEN ? = 0 THEN -- Bodyweight
-                                ? > (SELECT reps FROM current_pr WHERE weight = 0)
-                            ELSE -- Weighted
-                                ? > (SELECT estimated_1rm FROM current_pr)
-                        END
+                    SELECT ? > COALESCE((SELECT estimated_1rm FROM current_pr), 0)
                     "#,
                 )
                 .bind(ex_id)
-                .bind(pr_weight)
-                .bind(pr_reps)
                 .bind(max_1rm)
                 .fetch_one(&mut *tx)
                 .await?;
 
-                if is_pr {
-                    pr_updates.push((ex_id.clone(), pr_weight, pr_reps, max_1rm));
+                if is_pr && max_1rm > 0.0 {
+                    let is_notable = match prior_rating {
+                        Some((r, rd)) => max_1rm as f64 > r + 2.0 * rd,
+                        None => true,
+                    };
+                    pr_updates.push((ex_id.clone(), pr_weight, pr_reps, max_1rm, is_notable));
                 }
             }
 
             // Apply PR updates
-            for (ex_id, pr_weight, pr_reps, max_1rm) in pr_updates {
+            let mut notable_prs: HashSet<String> = HashSet::new();
+            for (ex_id, pr_weight, pr_reps, max_1rm, is_notable) in pr_updates {
+                if is_notable {
+                    notable_prs.insert(ex_id.clone());
+                }
+
                 // Insert new PR
                 sqlx::query(
                     r#"
@@ -1100,7 +1396,8 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     r#"
                     UPDATE exercises 
                     SET current_pr_date = datetime('now'),
-                        estimated_one_rm = ?
+                        estimated_one_rm = ?,
+                        last_updated = unixepoch()
                     WHERE id = ?
                     "#,
                 )
@@ -1111,7 +1408,7 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
             }
 
             // Mark session as ended
-            sqlx::query("UPDATE training_sessions SET end_time = datetime('now') WHERE id = ?")
+            sqlx::query("UPDATE training_sessions SET end_time = datetime('now'), last_updated = unixepoch() WHERE id = ?")
                 .bind(&session_id)
                 .execute(&mut *tx)
                 .await?;
@@ -1158,18 +1455,35 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 println!("• {}", exercise_name.bold());
                 for (reps, weight, bw) in sets {
                     if *bw {
-                        println!("  - {} reps (bodyweight)", reps);
+                        let unit = cfg.weight_unit();
+                        match weight {
+                            Some(w) if *w > 0.0 => {
+                                println!("  - {} reps (bodyweight + {}{})", reps, unit.from_kg(*w), unit.suffix())
+                            }
+                            _ => println!("  - {} reps (bodyweight)", reps),
+                        }
                     } else if let Some(w) = weight {
-                        println!("  - {}kg × {}", w, reps);
+                        let unit = cfg.weight_unit();
+                        println!("  - {}{} × {}", unit.from_kg(*w), unit.suffix(), reps);
                     }
                 }
+
+                if let Some((rating, deviation)) = rating_intervals.get(ex_id) {
+                    let unit = cfg.weight_unit();
+                    println!(
+                        "  est 1RM {:.1} ± {:.1}{}",
+                        unit.from_kg(*rating as f32),
+                        unit.from_kg((2.0 * deviation) as f32),
+                        unit.suffix()
+                    );
+                }
+                if notable_prs.contains(ex_id) {
+                    println!("  {} new personal record!", "note:".yellow().bold());
+                }
             }
         }
 
-        SessionCmd::Swap {
-            exercise,
-            new_exercise,
-        } => {
+        SessionCmd::UndoSet { exercise, set } => {
             // Check if there's an active session
             let session: Option<(String,)> =
                 sqlx::query_as("SELECT id FROM current_session LIMIT 1")
@@ -1184,29 +1498,20 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 }
             };
 
-            // Get information about the current session's block
-            let program_block_id: String =
-                sqlx::query_scalar("SELECT program_block_id FROM training_sessions WHERE id = ?")
-                    .bind(&session_id)
-                    .fetch_one(pool)
-                    .await?;
-
-            // Get the exercise to replace info with its order_index
-            let old_exercise_info: Option<(String, String, String)> = sqlx::query_as(
+            // Get the exercise ID and session-exercise ID for the given index
+            let exercise_info: Option<(String, String)> = sqlx::query_as(
                 r#"
                 WITH session_exercise_order AS (
-                    -- Use SQLite rowid to maintain original insertion order
-                    SELECT 
+                    SELECT
                         tse.id as tse_id,
                         tse.exercise_id,
                         ROW_NUMBER() OVER (ORDER BY tse.rowid) as display_order
                     FROM training_session_exercises tse
                     WHERE tse.training_session_id = ?
                 )
-                SELECT tse.id, tse.exercise_id, e.name
+                SELECT tse.exercise_id, tse.id as session_exercise_id
                 FROM training_session_exercises tse
                 JOIN session_exercise_order seo ON seo.tse_id = tse.id
-                JOIN exercises e ON e.id = tse.exercise_id
                 WHERE tse.training_session_id = ?
                 ORDER BY seo.display_order
                 LIMIT 1 OFFSET ?
@@ -1218,74 +1523,258 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
             .fetch_optional(pool)
             .await?;
 
-            let (old_session_exercise_id, old_exercise_id, old_exercise_name) =
-                match old_exercise_info {
-                    Some(info) => info,
-                    None => {
-                        println!(
-                            "{} no exercise at index {} in current session",
-                            "error:".red().bold(),
-                            exercise
-                        );
-                        return Ok(());
-                    }
-                };
+            let (exercise_id, session_exercise_id) = match exercise_info {
+                Some(info) => info,
+                None => {
+                    println!(
+                        "{} no exercise at index {}",
+                        "error:".red().bold(),
+                        exercise
+                    );
+                    return Ok(());
+                }
+            };
 
-            // Get the original exercise's set count from the program
-            let original_sets: i32 = sqlx::query_scalar(
-                "SELECT COALESCE(pe.sets, 2) FROM program_exercises pe 
-                 WHERE pe.program_block_id = ? AND pe.exercise_id = ?"
-            )
-            .bind(&program_block_id)
-            .bind(&old_exercise_id)
-            .fetch_optional(pool)
-            .await?
-            .unwrap_or(2); // Default to 2 sets if not found
+            let mut tx = pool.begin().await?;
 
-            // Resolve the new exercise (by index or name)
-            let new_exercise_id: String = if let Ok(idx) = new_exercise.parse::<i64>() {
-                // User provided an index from exercise list
-                match sqlx::query_scalar::<_, String>(
-                    r#"
-                    SELECT id 
-                    FROM exercises
-                    ORDER BY idx  -- Order by the autoincrement field, not by name
-                    LIMIT 1 OFFSET ?
-                    "#,
-                )
-                .bind(idx - 1) // Convert to 0-based for SQL
-                .fetch_optional(pool)
-                .await?
-                {
-                    Some(id) => id,
-                    None => {
-                        println!("{} no exercise at index {}", "error:".red().bold(), idx);
-                        return Ok(());
-                    }
-                }
-            } else {
-                // User provided an exercise name
-                match sqlx::query_scalar::<_, String>("SELECT id FROM exercises WHERE name = ?")
-                    .bind(&new_exercise)
-                    .fetch_optional(pool)
+            // Resolve the target set — the given 1-based set number, or the
+            // last logged set when none is given.
+            let target_set_num: Option<i64> = match set {
+                Some(s) => Some(s as i64),
+                None => {
+                    sqlx::query_scalar(
+                        r#"
+                        SELECT MAX(set_num) FROM (
+                            SELECT ROW_NUMBER() OVER (ORDER BY timestamp) as set_num
+                            FROM exercise_sets
+                            WHERE session_exercise_id = ? AND deleted_at IS NULL
+                        )
+                        "#,
+                    )
+                    .bind(&session_exercise_id)
+                    .fetch_one(&mut *tx)
                     .await?
-                {
-                    Some(id) => id,
-                    None => {
-                        println!(
-                            "{} no exercise named `{}`",
-                            "error:".red().bold(),
-                            new_exercise
-                        );
-                        return Ok(());
-                    }
                 }
             };
 
-            // Get new exercise name
-            let new_exercise_name: String =
-                sqlx::query_scalar("SELECT name FROM exercises WHERE id = ?")
-                    .bind(&new_exercise_id)
+            let set_id: Option<String> = match target_set_num {
+                Some(n) => {
+                    sqlx::query_scalar(
+                        r#"
+                        WITH set_numbers AS (
+                            SELECT
+                                es.id,
+                                ROW_NUMBER() OVER (ORDER BY es.timestamp) as set_num
+                            FROM exercise_sets es
+                            WHERE es.session_exercise_id = ? AND es.deleted_at IS NULL
+                        )
+                        SELECT id FROM set_numbers WHERE set_num = ?
+                        "#,
+                    )
+                    .bind(&session_exercise_id)
+                    .bind(n)
+                    .fetch_optional(&mut *tx)
+                    .await?
+                }
+                None => None,
+            };
+
+            let set_id = match set_id {
+                Some(id) => id,
+                None => {
+                    println!("{} no logged set to undo", "error:".red().bold());
+                    return Ok(());
+                }
+            };
+
+            sqlx::query(
+                "UPDATE exercise_sets SET deleted_at = datetime('now'), last_updated = unixepoch() WHERE id = ?",
+            )
+            .bind(&set_id)
+            .execute(&mut *tx)
+            .await?;
+
+            recompute_personal_records(&mut tx, &exercise_id, cfg.one_rm_formula()).await?;
+
+            tx.commit().await?;
+
+            println!(
+                "{} undid set {} for exercise {}",
+                "ok:".green().bold(),
+                target_set_num.unwrap_or(0),
+                exercise
+            );
+        }
+
+        SessionCmd::Reopen => {
+            let last: Option<(String,)> = sqlx::query_as(
+                r#"
+                SELECT id
+                FROM training_sessions
+                WHERE end_time IS NOT NULL AND deleted_at IS NULL
+                ORDER BY end_time DESC
+                LIMIT 1
+                "#,
+            )
+            .fetch_optional(pool)
+            .await?;
+
+            let session_id = match last {
+                Some((id,)) => id,
+                None => {
+                    println!("{} no ended session to reopen", "error:".red().bold());
+                    return Ok(());
+                }
+            };
+
+            let mut tx = pool.begin().await?;
+
+            sqlx::query(
+                "UPDATE training_sessions SET end_time = NULL, last_updated = unixepoch() WHERE id = ?",
+            )
+            .bind(&session_id)
+            .execute(&mut *tx)
+            .await?;
+
+            // Recompute PRs for every exercise logged in this session — reopening
+            // it un-ends it, so any PR it produced needs re-deriving from
+            // whatever sessions are now actually completed.
+            let exercise_ids: Vec<(String,)> = sqlx::query_as(
+                r#"
+                SELECT DISTINCT tse.exercise_id
+                FROM training_session_exercises tse
+                WHERE tse.training_session_id = ?
+                "#,
+            )
+            .bind(&session_id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+            for (ex_id,) in &exercise_ids {
+                recompute_personal_records(&mut tx, ex_id, cfg.one_rm_formula()).await?;
+            }
+
+            tx.commit().await?;
+
+            println!("{} session reopened (id: {})", "ok:".green().bold(), session_id);
+        }
+
+        SessionCmd::Swap {
+            exercise,
+            new_exercise,
+        } => {
+            // Check if there's an active session
+            let session: Option<(String,)> =
+                sqlx::query_as("SELECT id FROM current_session LIMIT 1")
+                    .fetch_optional(pool)
+                    .await?;
+
+            let session_id = match session {
+                Some((id,)) => id,
+                None => {
+                    println!("{} no active session", "error:".red().bold());
+                    return Ok(());
+                }
+            };
+
+            // Get information about the current session's block
+            let program_block_id: String =
+                sqlx::query_scalar("SELECT program_block_id FROM training_sessions WHERE id = ?")
+                    .bind(&session_id)
+                    .fetch_one(pool)
+                    .await?;
+
+            // Get the exercise to replace info with its order_index
+            let old_exercise_info: Option<(String, String, String)> = sqlx::query_as(
+                r#"
+                WITH session_exercise_order AS (
+                    -- Use SQLite rowid to maintain original insertion order
+                    SELECT 
+                        tse.id as tse_id,
+                        tse.exercise_id,
+                        ROW_NUMBER() OVER (ORDER BY tse.rowid) as display_order
+                    FROM training_session_exercises tse
+                    WHERE tse.training_session_id = ?
+                )
+                SELECT tse.id, tse.exercise_id, e.name
+                FROM training_session_exercises tse
+                JOIN session_exercise_order seo ON seo.tse_id = tse.id
+                JOIN exercises e ON e.id = tse.exercise_id
+                WHERE tse.training_session_id = ?
+                ORDER BY seo.display_order
+                LIMIT 1 OFFSET ?
+                "#,
+            )
+            .bind(&session_id)
+            .bind(&session_id)
+            .bind((exercise - 1) as i64)
+            .fetch_optional(pool)
+            .await?;
+
+            let (old_session_exercise_id, old_exercise_id, old_exercise_name) =
+                match old_exercise_info {
+                    Some(info) => info,
+                    None => {
+                        println!(
+                            "{} no exercise at index {} in current session",
+                            "error:".red().bold(),
+                            exercise
+                        );
+                        return Ok(());
+                    }
+                };
+
+            // Get the original exercise's set count from the program
+            let original_sets: i32 = sqlx::query_scalar(
+                "SELECT COALESCE(pe.sets, 2) FROM program_exercises pe 
+                 WHERE pe.program_block_id = ? AND pe.exercise_id = ?"
+            )
+            .bind(&program_block_id)
+            .bind(&old_exercise_id)
+            .fetch_optional(pool)
+            .await?
+            .unwrap_or(2); // Default to 2 sets if not found
+
+            // Resolve the new exercise (by index or name)
+            let new_exercise_id: String = if let Ok(idx) = new_exercise.parse::<i64>() {
+                // User provided an index from exercise list
+                match sqlx::query_scalar::<_, String>(
+                    r#"
+                    SELECT id 
+                    FROM exercises
+                    ORDER BY idx  -- Order by the autoincrement field, not by name
+                    LIMIT 1 OFFSET ?
+                    "#,
+                )
+                .bind(idx - 1) // Convert to 0-based for SQL
+                .fetch_optional(pool)
+                .await?
+                {
+                    Some(id) => id,
+                    None => {
+                        println!("{} no exercise at index {}", "error:".red().bold(), idx);
+                        return Ok(());
+                    }
+                }
+            } else {
+                // User provided an exercise name
+                match sqlx::query_scalar::<_, String>("SELECT id FROM exercises WHERE name = ?")
+                    .bind(&new_exercise)
+                    .fetch_optional(pool)
+                    .await?
+                {
+                    Some(id) => id,
+                    None => {
+                        crate::commands::exercise::print_no_exercise_named(pool, &new_exercise).await?;
+                        return Ok(());
+                    }
+                }
+            };
+
+            // Get new exercise name
+            let new_exercise_name: String =
+                sqlx::query_scalar("SELECT name FROM exercises WHERE id = ?")
+                    .bind(&new_exercise_id)
                     .fetch_one(pool)
                     .await?;
 
@@ -1313,7 +1802,7 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
             if let Some(pe_id) = existing_program_exercise {
                 // Update existing program exercise to use the original exercise's set count
                 sqlx::query(
-                    "UPDATE program_exercises SET sets = ? WHERE id = ?"
+                    "UPDATE program_exercises SET sets = ?, last_updated = unixepoch() WHERE id = ?"
                 )
                 .bind(original_sets)
                 .bind(pe_id)
@@ -1354,6 +1843,18 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     .map(|r| format!(" of {}", r))
                     .unwrap_or_default()
             );
+
+            let _ = crate::eventlog::append(
+                &Uuid::new_v4().to_string(),
+                "exercise_swapped",
+                serde_json::json!({
+                    "session_id": session_id,
+                    "exercise": exercise,
+                    "old_exercise": old_exercise_name,
+                    "new_exercise": new_exercise_name,
+                }),
+                false,
+            );
         }
 
         SessionCmd::AddEx { exercise, sets } => {
@@ -1401,7 +1902,7 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 {
                     Some(id) => id,
                     None => {
-                        println!("{} no exercise named `{}`", "error:".red().bold(), exercise);
+                        crate::commands::exercise::print_no_exercise_named(pool, &exercise).await?;
                         return Ok(());
                     }
                 }
@@ -1474,6 +1975,179 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 "ok:".green().bold(),
                 exercise
             );
+
+            let _ = crate::eventlog::append(
+                &Uuid::new_v4().to_string(),
+                "note_added",
+                serde_json::json!({
+                    "session_id": session_id,
+                    "exercise": exercise,
+                    "note": note,
+                }),
+                false,
+            );
+        }
+
+        SessionCmd::History { exercise } => {
+            let session_id: Option<String> = sqlx::query_scalar("SELECT id FROM current_session")
+                .fetch_optional(pool)
+                .await?;
+
+            let records = crate::eventlog::replay()?;
+            let relevant: Vec<_> = records
+                .iter()
+                .filter(|r| {
+                    session_id
+                        .as_deref()
+                        .map(|sid| r.data.get("session_id").and_then(|v| v.as_str()) == Some(sid))
+                        .unwrap_or(true)
+                })
+                .filter(|r| match exercise {
+                    Some(ex) => r.data.get("exercise").and_then(|v| v.as_u64()) == Some(ex as u64),
+                    None => true,
+                })
+                .collect();
+
+            if relevant.is_empty() {
+                println!("{} no recorded history", "warning:".yellow().bold());
+                return Ok(());
+            }
+
+            println!("{}", "Session history:".cyan().bold());
+            for r in relevant {
+                println!("  {} {} {}", r.timestamp.dimmed(), r.kind.bold(), r.data);
+            }
+        }
+
+        SessionCmd::List {
+            before,
+            after,
+            program,
+            block,
+            exercise,
+            min_duration,
+            limit,
+            offset,
+            reverse,
+        } => {
+            // Build the WHERE clause incrementally so an absent filter adds
+            // no predicate at all, rather than hardcoding every combination.
+            let mut predicates = vec!["ts.end_time IS NOT NULL".to_string(), "ts.deleted_at IS NULL".to_string()];
+            if before.is_some() {
+                predicates.push("date(ts.start_time) <= date(?)".to_string());
+            }
+            if after.is_some() {
+                predicates.push("date(ts.start_time) >= date(?)".to_string());
+            }
+            if program.is_some() {
+                predicates.push("p.name = ?".to_string());
+            }
+            if block.is_some() {
+                predicates.push("pb.name = ?".to_string());
+            }
+            if exercise.is_some() {
+                predicates.push(
+                    r#"EXISTS (
+                        SELECT 1 FROM training_session_exercises tse
+                        JOIN exercises e ON e.id = tse.exercise_id
+                        WHERE tse.training_session_id = ts.id AND e.name = ?
+                    )"#
+                    .to_string(),
+                );
+            }
+            if min_duration.is_some() {
+                predicates.push("(strftime('%s', ts.end_time) - strftime('%s', ts.start_time)) >= ? * 60".to_string());
+            }
+
+            let order = if reverse { "ASC" } else { "DESC" };
+            let query_str = format!(
+                r#"
+                SELECT
+                    date(ts.start_time),
+                    pb.name,
+                    p.name,
+                    strftime('%H:%M:%S', strftime('%s', ts.end_time) - strftime('%s', ts.start_time) || ' seconds', 'unixepoch'),
+                    COALESCE((
+                        SELECT SUM(es.weight * es.reps)
+                        FROM exercise_sets es
+                        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+                        WHERE tse.training_session_id = ts.id AND es.bodyweight = 0 AND es.deleted_at IS NULL
+                    ), 0.0)
+                FROM training_sessions ts
+                JOIN program_blocks pb ON pb.id = ts.program_block_id
+                JOIN programs p ON p.id = pb.program_id
+                WHERE {}
+                ORDER BY ts.start_time {}
+                LIMIT ? OFFSET ?
+                "#,
+                predicates.join(" AND "),
+                order,
+            );
+
+            let mut q = sqlx::query_as::<_, (String, String, String, String, f64)>(&query_str);
+            if let Some(b) = &before {
+                q = q.bind(b);
+            }
+            if let Some(a) = &after {
+                q = q.bind(a);
+            }
+            if let Some(p) = &program {
+                q = q.bind(p);
+            }
+            if let Some(bl) = &block {
+                q = q.bind(bl);
+            }
+            if let Some(ex) = &exercise {
+                q = q.bind(ex);
+            }
+            if let Some(md) = min_duration {
+                q = q.bind(md);
+            }
+            q = q.bind(limit).bind(offset);
+
+            let rows = q.fetch_all(pool).await?;
+
+            if rows.is_empty() {
+                println!("{} no sessions match those filters", "warning:".yellow().bold());
+                return Ok(());
+            }
+
+            let unit = cfg.weight_unit();
+            println!("{}", "Sessions:".cyan().bold());
+            for (date, block_name, program_name, duration, tonnage) in rows {
+                println!(
+                    "  {} • {} ({}) — {} • {}{}",
+                    date.dimmed(),
+                    block_name.bold(),
+                    program_name.yellow(),
+                    duration,
+                    unit.from_kg(tonnage as f32),
+                    unit.suffix(),
+                );
+            }
+        }
+
+        SessionCmd::Last => {
+            let active: Option<(String,)> = sqlx::query_as("SELECT id FROM current_session LIMIT 1")
+                .fetch_optional(pool)
+                .await?;
+
+            if active.is_some() {
+                return Box::pin(handle(SessionCmd::Show { history: false }, pool, fmt, cfg)).await;
+            }
+
+            let last_date: Option<String> =
+                sqlx::query_scalar("SELECT date(start_time) FROM training_sessions WHERE end_time IS NOT NULL AND deleted_at IS NULL ORDER BY start_time DESC LIMIT 1")
+                    .fetch_optional(pool)
+                    .await?;
+
+            let Some(last_date) = last_date else {
+                println!("{} no completed sessions yet", "warning:".yellow().bold());
+                return Ok(());
+            };
+
+            let date = NaiveDate::parse_from_str(&last_date, "%Y-%m-%d")?.format("%d-%m-%Y").to_string();
+            return Box::pin(handle(SessionCmd::Log { date }, pool, fmt, cfg)).await;
         }
 
         SessionCmd::Log { date } => {
@@ -1488,6 +2162,7 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 JOIN program_blocks pb ON pb.id = ts.program_block_id
                 WHERE date(ts.start_time) = date(?)
                 AND ts.end_time IS NOT NULL
+                AND ts.deleted_at IS NULL
                 LIMIT 1
                 "#,
             )
@@ -1599,8 +2274,92 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
             .fetch_all(pool)
             .await?;
 
+            // If this session logged any bodyweight sets, surface the most
+            // recent bodyweight measurement alongside it — that's the figure
+            // those sets' 1RM/PR numbers were priced against.
+            let has_bodyweight_sets: bool = sqlx::query_scalar(
+                r#"
+                SELECT EXISTS(
+                    SELECT 1
+                    FROM exercise_sets es
+                    JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+                    WHERE tse.training_session_id = ? AND es.bodyweight = 1 AND es.deleted_at IS NULL
+                )
+                "#,
+            )
+            .bind(&session_id)
+            .fetch_one(pool)
+            .await?;
+
+            if has_bodyweight_sets {
+                if let Some(bw_kg) = crate::commands::measure::latest_bodyweight_kg(pool).await? {
+                    let unit = cfg.weight_unit();
+                    println!("{} bodyweight: {}{}", "info:".blue().bold(), unit.from_kg(bw_kg), unit.suffix());
+                }
+            }
+
             println!("\n{}", "Exercises:".cyan().bold());
 
+            // Bulk-fetch the most recent completed-session weight/reps for
+            // every (exercise_id, set_num) pair in this session, and every
+            // exercise's best PR, in two queries total instead of one query
+            // per set plus one per exercise.
+            let exercise_ids: Vec<&String> = exercises.iter().map(|e| &e.0).collect();
+            let marks = std::iter::repeat("?").take(exercise_ids.len()).collect::<Vec<_>>().join(",");
+
+            let prev_set_rows: Vec<(String, i64, f32, i32)> = if exercise_ids.is_empty() {
+                Vec::new()
+            } else {
+                let q = format!(
+                    r#"
+                    WITH set_numbers AS (
+                        SELECT
+                            es.weight,
+                            es.reps,
+                            es.timestamp,
+                            tse.exercise_id,
+                            ROW_NUMBER() OVER (
+                                PARTITION BY tse.exercise_id, tse.id
+                                ORDER BY es.timestamp
+                            ) - 1 as set_num
+                        FROM exercise_sets es
+                        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+                        JOIN training_sessions ts ON ts.id = tse.training_session_id
+                        WHERE tse.exercise_id IN ({marks})
+                        AND ts.end_time IS NOT NULL  -- Only completed sessions
+                        AND ts.deleted_at IS NULL
+                        AND es.deleted_at IS NULL
+                        AND es.weight > 0  -- Skip empty sets
+                    ),
+                    last_sets AS (
+                        SELECT
+                            exercise_id,
+                            set_num,
+                            weight,
+                            reps,
+                            ROW_NUMBER() OVER (
+                                PARTITION BY exercise_id, set_num
+                                ORDER BY timestamp DESC
+                            ) as rn
+                        FROM set_numbers
+                    )
+                    SELECT exercise_id, set_num, weight, reps
+                    FROM last_sets
+                    WHERE rn = 1
+                    "#
+                );
+                let mut query = sqlx::query_as::<_, (String, i64, f32, i32)>(&q);
+                for id in &exercise_ids {
+                    query = query.bind(id.as_str());
+                }
+                query.fetch_all(pool).await?
+            };
+
+            let prev_sets_by_exercise: HashMap<(String, i64), (f32, i32)> = prev_set_rows
+                .into_iter()
+                .map(|(ex_id, set_num, weight, reps)| ((ex_id, set_num), (weight, reps)))
+                .collect();
+
             // Pre-calculate all previous set information to find the maximum width
             let mut prev_sets_info = Vec::new();
             for (
@@ -1626,49 +2385,11 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
 
                 // For each set
                 for set_num in 0..*sets {
-                    // Get previous set info
-                    let prev_set: Option<(f32, i32)> = sqlx::query_as(
-                        r#"
-                        WITH set_numbers AS (
-                            SELECT 
-                                es.weight,
-                                es.reps,
-                                es.timestamp,
-                                tse.exercise_id,
-                                ROW_NUMBER() OVER (
-                                    PARTITION BY tse.exercise_id, tse.id
-                                    ORDER BY es.timestamp
-                                ) - 1 as set_num
-                            FROM exercise_sets es
-                            JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
-                            JOIN training_sessions ts ON ts.id = tse.training_session_id
-                            WHERE tse.exercise_id = ?
-                            AND ts.end_time IS NOT NULL  -- Only completed sessions
-                            AND es.weight > 0  -- Skip empty sets
-                        ),
-                        last_sets AS (
-                            SELECT 
-                                weight,
-                                reps,
-                                ROW_NUMBER() OVER (
-                                    PARTITION BY exercise_id, set_num
-                                    ORDER BY timestamp DESC
-                                ) as rn
-                            FROM set_numbers
-                            WHERE set_num = ?
-                        )
-                        SELECT weight, reps
-                        FROM last_sets
-                        WHERE rn = 1
-                        "#,
-                    )
-                    .bind(ex_id)
-                    .bind(set_num)
-                    .fetch_optional(pool)
-                    .await?;
+                    let prev_set = prev_sets_by_exercise.get(&(ex_id.clone(), set_num as i64)).copied();
 
+                    let unit = cfg.weight_unit();
                     let prev_info = prev_set
-                        .map(|(w, r)| format!(" - {}kg × {}", w, r))
+                        .map(|(w, r)| format!(" - {}{} × {}", unit.from_kg(w), unit.suffix(), r))
                         .unwrap_or_default();
 
                     exercise_prev_sets.push(prev_info);
@@ -1684,6 +2405,40 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 .max()
                 .unwrap_or(0);
 
+            // Bulk-fetch every exercise's best (highest 1RM) PR in one grouped
+            // query instead of one query per exercise.
+            let best_pr_rows: Vec<(String, Option<f32>, Option<i32>, Option<f32>)> = if exercise_ids.is_empty() {
+                Vec::new()
+            } else {
+                let q = format!(
+                    r#"
+                    WITH ranked AS (
+                        SELECT
+                            exercise_id,
+                            weight,
+                            reps,
+                            estimated_1rm,
+                            ROW_NUMBER() OVER (PARTITION BY exercise_id ORDER BY estimated_1rm DESC) as rn
+                        FROM personal_records
+                        WHERE exercise_id IN ({marks})
+                    )
+                    SELECT exercise_id, weight, reps, estimated_1rm
+                    FROM ranked
+                    WHERE rn = 1
+                    "#
+                );
+                let mut query = sqlx::query_as::<_, (String, Option<f32>, Option<i32>, Option<f32>)>(&q);
+                for id in &exercise_ids {
+                    query = query.bind(id.as_str());
+                }
+                query.fetch_all(pool).await?
+            };
+
+            let best_pr_by_exercise: HashMap<String, (Option<f32>, Option<i32>, Option<f32>)> = best_pr_rows
+                .into_iter()
+                .map(|(ex_id, weight, reps, est_1rm)| (ex_id, (weight, reps, est_1rm)))
+                .collect();
+
             // Now display everything with consistent padding
             for (
                 i,
@@ -1707,31 +2462,16 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 let idx = format!("{}", i + 1).yellow();
 
                 // Get the best (highest 1RM) PR for this exercise
-                let (pr_weight, pr_reps, pr_1rm): (Option<f32>, Option<i32>, Option<f32>) =
-                    sqlx::query_as(
-                        r#"
-                        SELECT weight, reps, estimated_1rm
-                        FROM personal_records
-                        WHERE exercise_id = ?
-                        ORDER BY estimated_1rm DESC
-                        LIMIT 1
-                        "#,
-                    )
-                    .bind(ex_id)
-                    .fetch_optional(pool)
-                    .await?
+                let (pr_weight, pr_reps, pr_1rm) = best_pr_by_exercise
+                    .get(ex_id)
+                    .copied()
                     .unwrap_or((None, None, None));
 
                 // Print exercise header with PR info
-                let pr_info = if let (Some(w), Some(r)) = (pr_weight, pr_reps) {
-                    let one_rm = pr_1rm.unwrap_or_else(|| epley_1rm(w, r).round());
-                    let actual_pr = format!("{}kg × {}", w, r).red().bold().to_string();
-                    format!(" - PR: {} (1RM: {:.1}kg)", actual_pr, one_rm)
-                } else {
-                    String::new()
-                };
+                let pr_info =
+                    format_pr_info(pool, ex_id, pr_weight, pr_reps, pr_1rm, cfg.weight_unit(), cfg.one_rm_formula()).await?;
 
-                println!("{} • {}{}", idx, ex_name.bold(), pr_info.dimmed());
+                println!("{} • {}{}", idx, ex_name.bold(), pr_info);
 
                 // Print exercise note if it exists
                 let note: Option<String> = sqlx::query_scalar(
@@ -1775,6 +2515,7 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                         JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
                         WHERE tse.exercise_id = ?
                         AND tse.training_session_id = ?
+                        AND es.deleted_at IS NULL
                     )
                     SELECT set_num, weight, reps, bodyweight
                     FROM set_numbers
@@ -1824,6 +2565,7 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                 };
 
                 // Display all sets
+                let unit = cfg.weight_unit();
                 for (set_num_0_based_in_loop, weight, reps, bw) in sets_to_show {
                     let set_num_usize = set_num_0_based_in_loop as usize; // 0-based for array indexing
                     let target_info = if let Some(program_1rm) = _program_1rm {
@@ -1833,9 +2575,10 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                             let target_weight =
                                 program_1rm * (target_rms[set_num_usize] / 100.0);
                             format!(
-                                " @{}% ({}kg)",
+                                " @{}% ({}{})",
                                 target_rms[set_num_usize],
-                                target_weight.round()
+                                unit.from_kg(target_weight).round(),
+                                unit.suffix()
                             )
                         } else {
                             String::new()
@@ -1854,8 +2597,9 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                     } else {
                         "" // Empty string for additional sets beyond program's set count
                     };
+                    let (target_width, prev_width) = table_column_widths(max_prev_width);
                     let prev_column =
-                        format!("{:<width$}", prev_info, width = max_prev_width).dimmed();
+                        format!("{:<width$}", truncate_ellipsis(prev_info, prev_width), width = prev_width).dimmed();
 
                     let target_reps = if set_num_usize < reps_display.len() {
                         format!("{} reps", reps_display[set_num_usize])
@@ -1863,49 +2607,176 @@ pub async fn handle(cmd: SessionCmd, pool: &SqlitePool) -> Result<()> {
                         String::from("do your thing")
                     };
 
-                    let target_padding = if (target_reps.len() + target_info.len()) < 25 {
-                        25 - (target_reps.len() + target_info.len())
-                    } else {
-                        0
-                    };
-
                     // Create all parts of the display separately
                     let set_num_str = format!("{}", set_num_0_based_in_loop + 1).yellow(); // Display as 1-based
                     let indent = " ".repeat(2);
+                    let target_raw = truncate_ellipsis(&format!("{}{}", target_reps, target_info), target_width);
                     let target_part = if target_reps.is_empty() {
                         String::new()
+                    } else if target_raw.chars().count() <= target_reps.chars().count() {
+                        target_raw.clone()
                     } else {
-                        format!("{}{}", target_reps, target_info.dimmed())
+                        let reps_len = target_reps.chars().count();
+                        let mut chars = target_raw.chars();
+                        let reps_part: String = chars.by_ref().take(reps_len).collect();
+                        let info_part: String = chars.collect();
+                        format!("{}{}", reps_part, info_part.dimmed())
                     };
-                    let padding = " ".repeat(target_padding);
+                    let padding = " ".repeat(target_width.saturating_sub(target_raw.chars().count()));
 
                     let current_info = if bw {
                         format!("bw × {}", reps)
                     } else if weight > 0.0 {
-                        format!("{}kg × {}", weight, reps)
+                        format!("{}{} × {}", unit.from_kg(weight), unit.suffix(), reps)
                     } else {
                         String::new()
                     };
 
+                    // No autoregulated e1RM here (this is a past date, not
+                    // "today"), so the bar only has a target to compare
+                    // against when the program set a literal static 1RM.
+                    let target_reps_num: Option<i32> =
+                        reps_display.get(set_num_usize).and_then(|r| r.trim().parse().ok());
+                    let target_weight_kg = _program_1rm.and_then(|p| {
+                        target_rms.get(set_num_usize).map(|pct| p * (pct / 100.0))
+                    });
+                    let bar_part = match (bw, weight > 0.0, target_weight_kg, target_reps_num) {
+                        (false, true, Some(target_kg), Some(target_reps)) if target_kg > 0.0 => {
+                            let formula = cfg.one_rm_formula();
+                            let target_1rm = formula.estimate(target_kg, target_reps);
+                            if target_1rm > 0.0 {
+                                format!(" {}", intensity_bar(formula.estimate(weight, reps) / target_1rm))
+                            } else {
+                                String::new()
+                            }
+                        }
+                        _ => String::new(),
+                    };
+
                     // Print with explicit parts
                     println!(
-                        " {} {} • {} {}{} | {}",
+                        " {} {} • {} {}{} | {}{}",
                         indent,
                         set_num_str,
                         target_part,
                         padding,
                         prev_column,
-                        current_info
+                        current_info,
+                        bar_part
                     );
                 }
                 println!();
             }
         }
+
+        SessionCmd::Trend { exercise, sessions } => {
+            let session: Option<(String,)> =
+                sqlx::query_as("SELECT id FROM current_session LIMIT 1")
+                    .fetch_optional(pool)
+                    .await?;
+
+            let session_id = match session {
+                Some((id,)) => id,
+                None => {
+                    println!("{} no active session", "error:".red().bold());
+                    return Ok(());
+                }
+            };
+
+            let exercise_info: Option<(String, String)> = sqlx::query_as(
+                r#"
+                WITH session_exercise_order AS (
+                    SELECT
+                        tse.id as tse_id,
+                        tse.exercise_id,
+                        ROW_NUMBER() OVER (ORDER BY tse.rowid) as display_order
+                    FROM training_session_exercises tse
+                    WHERE tse.training_session_id = ?
+                )
+                SELECT tse.exercise_id, e.name
+                FROM training_session_exercises tse
+                JOIN session_exercise_order seo ON seo.tse_id = tse.id
+                JOIN exercises e ON e.id = tse.exercise_id
+                WHERE tse.training_session_id = ?
+                ORDER BY seo.display_order
+                LIMIT 1 OFFSET ?
+                "#,
+            )
+            .bind(&session_id)
+            .bind(&session_id)
+            .bind((exercise - 1) as i64)
+            .fetch_optional(pool)
+            .await?;
+
+            let (exercise_id, exercise_name) = match exercise_info {
+                Some(v) => v,
+                None => {
+                    println!("{} no exercise at index {}", "error:".red().bold(), exercise);
+                    return Ok(());
+                }
+            };
+
+            let points = exercise_history(pool, &exercise_id, sessions).await?;
+            if points.is_empty() {
+                println!("{} no completed-session history for {}", "warning:".yellow().bold(), exercise_name);
+                return Ok(());
+            }
+
+            println!("{} {} (last {})", "Trend:".cyan().bold(), exercise_name.bold(), points.len());
+            render_history_points(&points, cfg.weight_unit());
+        }
     }
 
     Ok(())
 }
 
+/// Renders the " - PR: W{unit} × R (1RM: r ± RD{unit})" suffix shown next to
+/// an exercise's name in `session show`/`session log`, in the caller's
+/// configured weight unit. Prefers the time-decayed
+/// rating over the bare PR's 1RM when one exists, dimming it once its
+/// deviation crosses [`rating::LOW_CONFIDENCE_RD`] so a stale estimate reads
+/// as uncertain rather than authoritative.
+async fn format_pr_info(
+    pool: &SqlitePool,
+    ex_id: &str,
+    pr_weight: Option<f32>,
+    pr_reps: Option<i32>,
+    pr_1rm: Option<f32>,
+    unit: crate::types::WeightUnit,
+    formula: crate::types::OneRmFormula,
+) -> Result<String> {
+    let (Some(w), Some(r)) = (pr_weight, pr_reps) else {
+        return Ok(String::new());
+    };
+
+    let actual_pr = format!("{}{} × {}", unit.from_kg(w), unit.suffix(), r).red().bold().to_string();
+
+    let one_rm_part = match crate::rating::current(pool, ex_id).await? {
+        Some((rating, deviation)) => {
+            let text = format!(
+                "1RM: {:.1} ± {:.1}{}",
+                unit.from_kg(rating as f32),
+                unit.from_kg(deviation as f32),
+                unit.suffix()
+            );
+            if deviation > crate::rating::LOW_CONFIDENCE_RD {
+                text.dimmed().to_string()
+            } else {
+                text
+            }
+        }
+        None => {
+            // No rating yet to fall back on -- estimate with the user's
+            // chosen formula instead of assuming Epley, and note which one
+            // produced the number.
+            let one_rm = pr_1rm.unwrap_or_else(|| formula.estimate(w, r).round());
+            format!("1RM ({}): {:.1}{}", formula.name(), unit.from_kg(one_rm), unit.suffix())
+        }
+    };
+
+    Ok(format!(" - PR: {} ({})", actual_pr, one_rm_part))
+}
+
 fn epley_1rm(weight: f32, reps: i32) -> f32 {
     if reps == 0 {
         0.0
@@ -1914,3 +2785,359 @@ fn epley_1rm(weight: f32, reps: i32) -> f32 {
     }
 }
 
+/// Rebuilds `exercise_id`'s entire `personal_records` history and its
+/// `exercises.estimated_one_rm`/`current_pr_date` from every surviving
+/// (non-deleted, completed, non-deleted-session) logged set, replayed in
+/// chronological order. Call after `undo-set`/`reopen` invalidate whichever
+/// set a stored PR was derived from — soft-deleting that set, or un-ending
+/// its session, doesn't by itself retract the PR it produced.
+async fn recompute_personal_records(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    exercise_id: &str,
+    formula: crate::types::OneRmFormula,
+) -> Result<()> {
+    let sets: Vec<(String, f32, i32, bool)> = sqlx::query_as(
+        r#"
+        SELECT es.timestamp, es.weight, es.reps, es.bodyweight
+        FROM exercise_sets es
+        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+        JOIN training_sessions ts ON ts.id = tse.training_session_id
+        WHERE tse.exercise_id = ?
+          AND ts.end_time IS NOT NULL
+          AND ts.deleted_at IS NULL
+          AND es.deleted_at IS NULL
+        ORDER BY es.timestamp
+        "#,
+    )
+    .bind(exercise_id)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    sqlx::query("DELETE FROM personal_records WHERE exercise_id = ?")
+        .bind(exercise_id)
+        .execute(&mut **tx)
+        .await?;
+
+    let mut best_1rm = 0.0f32;
+    for (timestamp, weight, reps, bw) in sets {
+        let effective = if bw {
+            let bodyweight_kg = crate::commands::measure::bodyweight_kg_as_of(tx, &timestamp).await?.unwrap_or(0.0);
+            bodyweight_kg + weight
+        } else {
+            weight
+        };
+        if effective <= 0.0 {
+            continue;
+        }
+
+        let est_1rm = formula.estimate(effective, reps);
+        if est_1rm > best_1rm {
+            best_1rm = est_1rm;
+
+            sqlx::query(
+                r#"
+                INSERT INTO personal_records (exercise_id, weight, reps, estimated_1rm, date)
+                VALUES (?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(exercise_id)
+            .bind(effective)
+            .bind(reps)
+            .bind(est_1rm)
+            .bind(&timestamp)
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+
+    let latest_pr: Option<(f32, String)> = sqlx::query_as(
+        "SELECT estimated_1rm, date FROM personal_records WHERE exercise_id = ? ORDER BY estimated_1rm DESC LIMIT 1",
+    )
+    .bind(exercise_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let (one_rm, date) = match latest_pr {
+        Some((r, d)) => (Some(r), Some(d)),
+        None => (None, None),
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE exercises
+        SET estimated_one_rm = ?, current_pr_date = ?, last_updated = unixepoch()
+        WHERE id = ?
+        "#,
+    )
+    .bind(one_rm)
+    .bind(date)
+    .bind(exercise_id)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Classic reps-to-failure -> %1RM lookup, indexed by reps-to-failure 1..=12
+/// (index 0 = 1 rep to failure, i.e. 0 reps in reserve: a true max single).
+const PERCENT_BY_REPS_TO_FAILURE: [f32; 12] =
+    [100.0, 95.0, 90.0, 88.0, 86.0, 83.0, 81.0, 78.0, 76.0, 74.0, 72.0, 70.0];
+
+/// Estimates %1RM for a set of `reps` performed at `rpe` (0-10), via
+/// reps-to-failure = `reps + (10 - rpe)`, linearly interpolating
+/// [`PERCENT_BY_REPS_TO_FAILURE`] for fractional RIR (half-point RPEs).
+fn percent_1rm_for_rpe(reps: i32, rpe: f32) -> f32 {
+    let rir = (10.0 - rpe).max(0.0);
+    let reps_to_failure = (reps as f32 + rir).clamp(1.0, PERCENT_BY_REPS_TO_FAILURE.len() as f32);
+
+    let lo_idx = (reps_to_failure.floor() as usize - 1).min(PERCENT_BY_REPS_TO_FAILURE.len() - 1);
+    let hi_idx = (reps_to_failure.ceil() as usize - 1).min(PERCENT_BY_REPS_TO_FAILURE.len() - 1);
+
+    if lo_idx == hi_idx {
+        PERCENT_BY_REPS_TO_FAILURE[lo_idx]
+    } else {
+        let frac = reps_to_failure - reps_to_failure.floor();
+        PERCENT_BY_REPS_TO_FAILURE[lo_idx] * (1.0 - frac) + PERCENT_BY_REPS_TO_FAILURE[hi_idx] * frac
+    }
+}
+
+/// Truncates `s` to at most `width` characters, appending `…` in place of
+/// whatever got cut off. Used by the set-display table so an overlong
+/// target/previous-set cell truncates instead of wrapping the terminal.
+fn truncate_ellipsis(s: &str, width: usize) -> String {
+    let char_count = s.chars().count();
+    if char_count <= width {
+        s.to_string()
+    } else if width == 0 {
+        String::new()
+    } else if width == 1 {
+        "…".to_string()
+    } else {
+        format!("{}…", s.chars().take(width - 1).collect::<String>())
+    }
+}
+
+/// Cell count of the `[####----]` bar drawn by [`intensity_bar`]. Counted
+/// into `table_column_widths`'s non-negotiable width since it, like the set
+/// number and current-info, always renders at a fixed size.
+const BAR_CELLS: usize = 10;
+
+/// Renders `ratio` (a logged set's estimated 1RM over its target's) as a
+/// fixed-width `[####----] NNN%` bar. The bar itself caps visually at a full
+/// 10 cells once the target is met — overshoot and an exact hit read the
+/// same at a glance — while the trailing percentage still shows the real
+/// number, so undershoots and overshoots remain easy to tell apart.
+fn intensity_bar(ratio: f32) -> String {
+    let filled = (ratio.clamp(0.0, 1.0) * BAR_CELLS as f32).round() as usize;
+    let bar: String = (0..BAR_CELLS).map(|i| if i < filled { '#' } else { '-' }).collect();
+    format!("[{}] {:.0}%", bar, ratio * 100.0)
+}
+
+/// `(target_width, prev_width)` for the set-display table's two negotiable
+/// columns, given the longest previous-set cell actually produced
+/// (`max_prev_width`). When stdout is a real terminal, distributes whatever
+/// width remains after the non-negotiable parts of the line (indent, set
+/// number, bullets, current-info) between the two columns instead of the
+/// historical fixed 25-char target column. Falls back to the fixed widths
+/// (target 25, previous-set `max_prev_width`) when piped, so
+/// scripted/captured output stays stable regardless of the caller's
+/// terminal.
+fn table_column_widths(max_prev_width: usize) -> (usize, usize) {
+    const FIXED_TARGET_WIDTH: usize = 25;
+    // Indent, set number, " • "/" | " separators, typical current-info, and
+    // the intensity bar (`[` + cells + `] ` + 3-digit `%` ≈ 17 chars) — the
+    // part of the line that isn't the target or previous-set column.
+    const NON_NEGOTIABLE_WIDTH: usize = 20 + BAR_CELLS + 7;
+
+    let Some((terminal_size::Width(cols), _)) = terminal_size::terminal_size() else {
+        return (FIXED_TARGET_WIDTH, max_prev_width);
+    };
+
+    let available = (cols as usize).saturating_sub(NON_NEGOTIABLE_WIDTH);
+    if available == 0 {
+        return (FIXED_TARGET_WIDTH, max_prev_width);
+    }
+
+    // Previous-set info never needs more than its longest actual cell; give
+    // it that much (or a third of the available space, whichever is
+    // smaller) and hand the rest to the target column.
+    let prev_width = max_prev_width.min(available / 3);
+    let target_width = available.saturating_sub(prev_width).max(10);
+
+    (target_width, prev_width)
+}
+
+const SPARK_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Renders `values` (oldest first) as a tiny Unicode sparkline, one block char
+/// per value, scaled between the series' own min and max.
+fn sparkline(values: &[f32]) -> String {
+    if values.is_empty() {
+        return String::new();
+    }
+
+    let min = values.iter().cloned().fold(f32::INFINITY, f32::min);
+    let max = values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    let span = max - min;
+
+    values
+        .iter()
+        .map(|&v| {
+            let level = if span <= 0.0 {
+                SPARK_BLOCKS.len() - 1
+            } else {
+                (((v - min) / span) * (SPARK_BLOCKS.len() - 1) as f32).round() as usize
+            };
+            SPARK_BLOCKS[level.min(SPARK_BLOCKS.len() - 1)]
+        })
+        .collect()
+}
+
+/// One completed session's best working set for an exercise, with its
+/// estimated 1RM — the unit of `session history`'s per-session rows. Also
+/// reused by `exercise rating` to derive a short-term trend arrow.
+pub(crate) struct HistoryPoint {
+    pub(crate) day: String,
+    pub(crate) weight: f32,
+    pub(crate) reps: i32,
+    pub(crate) e1rm: f32,
+}
+
+/// Fetches the last `limit` completed sessions' top set for `exercise_id`,
+/// newest first, each session's "top set" being whichever logged set has the
+/// highest Epley-estimated 1RM that day.
+pub(crate) async fn exercise_history(pool: &SqlitePool, exercise_id: &str, limit: i64) -> Result<Vec<HistoryPoint>> {
+    let rows: Vec<(String, String, f32, i32)> = sqlx::query_as(
+        r#"
+        SELECT ts.id, date(ts.start_time), es.weight, es.reps
+        FROM exercise_sets es
+        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+        JOIN training_sessions ts ON ts.id = tse.training_session_id
+        WHERE tse.exercise_id = ?
+          AND ts.end_time IS NOT NULL
+          AND ts.deleted_at IS NULL
+          AND es.deleted_at IS NULL
+          AND es.weight > 0
+        ORDER BY ts.start_time DESC
+        "#,
+    )
+    .bind(exercise_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut order: Vec<String> = Vec::new();
+    let mut by_session: HashMap<String, HistoryPoint> = HashMap::new();
+    for (session_id, day, weight, reps) in rows {
+        let e1rm = epley_1rm(weight, reps);
+        by_session
+            .entry(session_id.clone())
+            .and_modify(|p| {
+                if e1rm > p.e1rm {
+                    p.weight = weight;
+                    p.reps = reps;
+                    p.e1rm = e1rm;
+                }
+            })
+            .or_insert_with(|| {
+                order.push(session_id.clone());
+                HistoryPoint { day, weight, reps, e1rm }
+            });
+    }
+
+    Ok(order
+        .into_iter()
+        .take(limit.max(0) as usize)
+        .map(|id| by_session.remove(&id).unwrap())
+        .collect())
+}
+
+/// Renders `points` (newest first) as one line per session plus a sparkline
+/// of their estimated 1RMs (oldest to newest). Shared by `session show
+/// --history` and `session trend`.
+fn render_history_points(points: &[HistoryPoint], unit: crate::types::WeightUnit) {
+    if points.is_empty() {
+        println!("    {}", "no completed-session history yet".dimmed());
+        return;
+    }
+
+    for p in points {
+        println!(
+            "    {} {}{} × {} {}",
+            p.day.dimmed(),
+            unit.from_kg(p.weight),
+            unit.suffix(),
+            p.reps,
+            format!("(e1RM {:.1}{})", unit.from_kg(p.e1rm), unit.suffix()).dimmed()
+        );
+    }
+
+    let trend: Vec<f32> = points.iter().rev().map(|p| p.e1rm).collect();
+    println!("    {} {}", "trend:".dimmed(), sparkline(&trend));
+}
+
+/// Fetches and prints the last `limit` completed sessions' top set for
+/// `exercise_id`. Thin wrapper around [`exercise_history`] +
+/// [`render_history_points`] for callers that just want the default depth.
+async fn print_exercise_history(pool: &SqlitePool, exercise_id: &str, unit: crate::types::WeightUnit) -> Result<()> {
+    let points = exercise_history(pool, exercise_id, 5).await?;
+    render_history_points(&points, unit);
+    Ok(())
+}
+
+/// Quotes a CSV field only when it needs it (contains a comma, quote, or
+/// newline), doubling any embedded quotes per RFC 4180.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// `session show --format csv`: every logged set of the active session as one
+/// row, for loading into a spreadsheet or pandas.
+async fn print_session_sets_csv(pool: &SqlitePool, cfg: &Config) -> Result<()> {
+    let rows: Vec<(String, String, String, i64, f64, i64, Option<f64>, String)> = sqlx::query_as(
+        r#"
+        SELECT
+            ts.id,
+            pb.name,
+            e.name,
+            ROW_NUMBER() OVER (PARTITION BY es.session_exercise_id ORDER BY es.timestamp) AS set_idx,
+            es.weight,
+            es.reps,
+            es.rpe,
+            es.timestamp
+        FROM exercise_sets es
+        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+        JOIN training_sessions ts ON ts.id = tse.training_session_id
+        JOIN program_blocks pb ON pb.id = ts.program_block_id
+        JOIN exercises e ON e.id = tse.exercise_id
+        WHERE ts.end_time IS NULL AND es.deleted_at IS NULL
+        ORDER BY e.rowid, set_idx
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    println!("session_id,program,exercise,set_idx,weight,reps,rpe,estimated_1rm,timestamp");
+    let formula = cfg.one_rm_formula();
+    for (session_id, program, exercise, set_idx, weight, reps, rpe, timestamp) in rows {
+        let estimated_1rm = formula.estimate(weight as f32, reps as i32);
+        println!(
+            "{},{},{},{},{},{},{},{:.2},{}",
+            csv_field(&session_id),
+            csv_field(&program),
+            csv_field(&exercise),
+            set_idx,
+            weight,
+            reps,
+            rpe.map(|r| r.to_string()).unwrap_or_default(),
+            estimated_1rm,
+            timestamp,
+        );
+    }
+
+    Ok(())
+}
+
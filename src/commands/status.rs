@@ -1,118 +1,802 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Days, Local, Months, Utc};
 use colored::Colorize;
+use serde::Serialize;
 use sqlx::SqlitePool;
 
-fn create_ascii_graph(data: &[(DateTime<Utc>, f32)], width: usize, height: usize, title: &str) -> Vec<String> {
+use crate::cli::{Granularity, Stat};
+use crate::types::{emit, Config, OutputFmt, OutputFormat};
+
+/// SQL expression bucketing `es.timestamp` into `granularity`-sized groups,
+/// shared by every weekly-aggregate query so day/week/month stay in sync.
+fn bucket_expr(granularity: Granularity) -> &'static str {
+    match granularity {
+        Granularity::Day => "date(es.timestamp)",
+        Granularity::Week => "date(es.timestamp, 'weekday 1', '-6 days')",
+        Granularity::Month => "strftime('%Y-%m-01', es.timestamp)",
+    }
+}
+
+/// Date format for `create_ascii_graph`'s x-axis labels, matching the bucket
+/// width so daily buckets show full dates and monthly buckets don't repeat
+/// a day-of-month that's always `01`.
+fn date_format(granularity: Granularity) -> &'static str {
+    match granularity {
+        Granularity::Day | Granularity::Week => "%Y-%m-%d",
+        Granularity::Month => "%Y-%m",
+    }
+}
+
+/// Adjective naming a `granularity` bucket for graph titles (`"Weekly
+/// Tonnage"`, `"Daily Tonnage"`, `"Monthly Tonnage"`).
+fn granularity_title(granularity: Granularity) -> &'static str {
+    match granularity {
+        Granularity::Day => "Daily",
+        Granularity::Week => "Weekly",
+        Granularity::Month => "Monthly",
+    }
+}
+
+/// Singular/plural noun for a `granularity` bucket, used to label forecast
+/// output (`"Projected tonnage in 3 {weeks,days,months}"`).
+fn bucket_noun(granularity: Granularity, count: u32) -> &'static str {
+    match (granularity, count == 1) {
+        (Granularity::Day, true) => "day",
+        (Granularity::Day, false) => "days",
+        (Granularity::Week, true) => "week",
+        (Granularity::Week, false) => "weeks",
+        (Granularity::Month, true) => "month",
+        (Granularity::Month, false) => "months",
+    }
+}
+
+/// Advances `date` by one `granularity` bucket — a calendar day, a 7-day
+/// week, or a calendar month — matching [`bucket_expr`]'s grouping.
+fn advance_bucket(date: DateTime<Utc>, granularity: Granularity) -> DateTime<Utc> {
+    match granularity {
+        Granularity::Day => date + Days::new(1),
+        Granularity::Week => date + Days::new(7),
+        Granularity::Month => date + Months::new(1),
+    }
+}
+
+/// Monday of the week `offset` weeks relative to the current week (0 =
+/// this week, -3 = three weeks ago, ...), matching `show_week_report`'s
+/// anchor math so `--offset` and `--week` agree on what "week N" means.
+fn week_anchor(offset: i32) -> chrono::NaiveDate {
+    let today = Local::now().date_naive();
+    let this_monday = today - Days::new(today.weekday().num_days_from_monday() as u64);
+    if offset >= 0 {
+        this_monday + Days::new(offset as u64 * 7)
+    } else {
+        this_monday - Days::new((-offset) as u64 * 7)
+    }
+}
+
+/// `[start, end)` date range `weeks` wide, ending at the Monday after the
+/// week anchored by `offset`. Lets `show_global_progression`/
+/// `show_muscle_progression` shift their whole query window into the past
+/// instead of always ending at today.
+fn week_window(offset: i32, weeks: u32) -> (chrono::NaiveDate, chrono::NaiveDate) {
+    let end = week_anchor(offset) + Days::new(7);
+    let start = end - Days::new(weeks as u64 * 7);
+    (start, end)
+}
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+fn median_of(sorted: &[f64]) -> f64 {
+    let n = sorted.len();
+    if n % 2 == 1 {
+        sorted[n / 2]
+    } else {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    }
+}
+
+/// Robust outlier filter: flags points whose `|x - median| / (1.4826 ·
+/// MAD)` exceeds `k`, so a single mis-logged set (a typo like 500kg)
+/// doesn't distort graph scaling or early-vs-late trend comparisons.
+/// Returns `(kept, flagged)`; a constant series (`MAD == 0`) skips
+/// filtering and returns everything as kept.
+fn filter_outliers<T: Clone>(points: &[(T, f64)], k: f64) -> (Vec<(T, f64)>, Vec<(T, f64)>) {
+    if points.len() < 2 {
+        return (points.to_vec(), Vec::new());
+    }
+
+    let mut values: Vec<f64> = points.iter().map(|(_, v)| *v).collect();
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let median = median_of(&values);
+
+    let mut abs_devs: Vec<f64> = values.iter().map(|v| (v - median).abs()).collect();
+    abs_devs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let scaled_mad = 1.4826 * median_of(&abs_devs);
+
+    if scaled_mad == 0.0 {
+        return (points.to_vec(), Vec::new());
+    }
+
+    let mut kept = Vec::new();
+    let mut flagged = Vec::new();
+    for (label, value) in points {
+        if ((value - median).abs() / scaled_mad) > k {
+            flagged.push((label.clone(), *value));
+        } else {
+            kept.push((label.clone(), *value));
+        }
+    }
+    (kept, flagged)
+}
+
+/// Collapses one bucket's raw values into the single number that gets
+/// graphed and trend-compared, per `stat`. `values` need not be sorted.
+fn aggregate_stat(values: &[f64], stat: Stat) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    match stat {
+        Stat::Mean => values.iter().sum::<f64>() / values.len() as f64,
+        Stat::Median => {
+            let mut sorted = values.to_vec();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            median_of(&sorted)
+        }
+        Stat::P90 => {
+            let mut sorted = values.to_vec();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let rank = 0.9 * (sorted.len() - 1) as f64;
+            let lo = rank.floor() as usize;
+            let hi = rank.ceil() as usize;
+            sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+        }
+        Stat::Trimmed => {
+            let mut sorted = values.to_vec();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let trim = (sorted.len() as f64 * 0.1).round() as usize;
+            let kept = if sorted.len() > 2 * trim { &sorted[trim..sorted.len() - trim] } else { &sorted[..] };
+            kept.iter().sum::<f64>() / kept.len() as f64
+        }
+        Stat::Stddev => {
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+            variance.sqrt()
+        }
+    }
+}
+
+/// Groups raw `(bucket_label, value)` rows into per-bucket value lists
+/// (preserving first-seen bucket order, which is already chronological
+/// since the source query is `ORDER BY`-ed) and collapses each list with
+/// [`aggregate_stat`].
+fn bucket_and_aggregate(rows: Vec<(String, f64)>, stat: Stat) -> Vec<(String, f64)> {
+    let mut order: Vec<String> = Vec::new();
+    let mut grouped: HashMap<String, Vec<f64>> = HashMap::new();
+    for (bucket, value) in rows {
+        if !grouped.contains_key(&bucket) {
+            order.push(bucket.clone());
+        }
+        grouped.entry(bucket).or_default().push(value);
+    }
+    order
+        .into_iter()
+        .map(|bucket| {
+            let value = aggregate_stat(&grouped[&bucket], stat);
+            (bucket, value)
+        })
+        .collect()
+}
+
+/// Reduces raw `(week_start, exercise_id, est_1rm)` rows into one
+/// improvement-percent-per-week series: first collapses each
+/// (week, exercise) bucket to a single 1RM via `stat`, converts that to a
+/// percent improvement over the exercise's pre-period baseline, then
+/// collapses the week's per-exercise improvements with `stat` again.
+fn weekly_pr_improvements(
+    raw: Vec<(String, String, f64)>,
+    baseline_1rm: &HashMap<String, f64>,
+    stat: Stat,
+) -> Vec<(String, f32)> {
+    let mut week_order: Vec<String> = Vec::new();
+    let mut seen_weeks: HashSet<String> = HashSet::new();
+    let mut per_week_exercise: HashMap<(String, String), Vec<f64>> = HashMap::new();
+    for (week, exercise_id, est_1rm) in raw {
+        if seen_weeks.insert(week.clone()) {
+            week_order.push(week.clone());
+        }
+        per_week_exercise.entry((week, exercise_id)).or_default().push(est_1rm);
+    }
+
+    let mut improvements_by_week: HashMap<String, Vec<f64>> = HashMap::new();
+    for ((week, exercise_id), values) in per_week_exercise {
+        let Some(&baseline) = baseline_1rm.get(&exercise_id) else { continue };
+        if baseline <= 0.0 {
+            continue;
+        }
+        let week_best = aggregate_stat(&values, stat);
+        let improvement = ((week_best - baseline) / baseline) * 100.0;
+        improvements_by_week.entry(week).or_default().push(improvement);
+    }
+
+    week_order
+        .into_iter()
+        .filter_map(|week| {
+            let values = improvements_by_week.get(&week)?;
+            Some((week, aggregate_stat(values, stat) as f32))
+        })
+        .collect()
+}
+
+/// Holt's linear (double exponential) smoothing. Fits a level/trend pair
+/// over `series` — `L_1 = y_1`, `b_1 = y_2 - y_1`, then for each later point
+/// `L_t = α·y_t + (1-α)·(L_{t-1}+b_{t-1})` and `b_t = β·(L_t-L_{t-1}) +
+/// (1-β)·b_{t-1}` — and returns the h-step-ahead forecasts `L_t + h·b_t`
+/// for `h` in `1..=horizon`, clamped at 0 since tonnage can't be negative.
+/// Needs at least 3 points; returns an empty vec otherwise.
+fn holt_forecast(series: &[f32], horizon: u32, alpha: f32, beta: f32) -> Vec<f32> {
+    if series.len() < 3 || horizon == 0 {
+        return Vec::new();
+    }
+
+    let mut level = series[0];
+    let mut trend = series[1] - series[0];
+    for &y in &series[1..] {
+        let prev_level = level;
+        level = alpha * y + (1.0 - alpha) * (level + trend);
+        trend = beta * (level - prev_level) + (1.0 - beta) * trend;
+    }
+
+    (1..=horizon).map(|h| (level + h as f32 * trend).max(0.0)).collect()
+}
+
+/// Runs [`holt_forecast`] over a bucketed series and pairs each projected
+/// value with the date it falls on (one `granularity` bucket past the
+/// previous point).
+fn project_weekly(
+    graph_data: &[(DateTime<Utc>, f32)],
+    series: &[f32],
+    horizon: u32,
+    alpha: f32,
+    beta: f32,
+    granularity: Granularity,
+) -> Vec<(DateTime<Utc>, f32)> {
+    let Some((last_date, _)) = graph_data.last() else {
+        return Vec::new();
+    };
+    holt_forecast(series, horizon, alpha, beta)
+        .into_iter()
+        .scan(*last_date, |date, value| {
+            *date = advance_bucket(*date, granularity);
+            Some((*date, value))
+        })
+        .collect()
+}
+
+/// Renders `data` as an ASCII line graph (`●` points joined by `·`).
+/// `outliers` are interleaved into the timeline at their real chronological
+/// position but drawn with a `!` warning glyph and excluded from axis
+/// scaling and line-drawing, rather than silently pulling the line toward
+/// them. When `forecast` isn't empty, its points continue past the last
+/// real point using a distinct `×`/`:` glyph pair, and the axis ranges are
+/// widened to fit them.
+fn create_ascii_graph(
+    data: &[(DateTime<Utc>, f32)],
+    outliers: &[(DateTime<Utc>, f32)],
+    forecast: &[(DateTime<Utc>, f32)],
+    width: usize,
+    height: usize,
+    title: &str,
+    date_fmt: &str,
+) -> Vec<String> {
     if data.is_empty() {
         return vec!["No data available".to_string()];
     }
 
-    let min_value = data.iter().map(|(_, v)| *v).fold(f32::INFINITY, f32::min);
-    let max_value = data.iter().map(|(_, v)| *v).fold(f32::NEG_INFINITY, f32::max);
+    let all_values = data.iter().chain(forecast).map(|(_, v)| *v);
+    let min_value = all_values.clone().fold(f32::INFINITY, f32::min);
+    let max_value = all_values.fold(f32::NEG_INFINITY, f32::max);
     let range = max_value - min_value;
-    
+
     if range == 0.0 {
         return vec!["No variation in data".to_string()];
     }
-    
+
+    // Clean and flagged points share one chronological timeline so x
+    // positions reflect each point's real place in time, even though
+    // outliers don't influence `min_value`/`max_value` above.
+    let mut timeline: Vec<(DateTime<Utc>, f32, bool)> =
+        data.iter().map(|&(d, v)| (d, v, false)).chain(outliers.iter().map(|&(d, v)| (d, v, true))).collect();
+    timeline.sort_by_key(|(d, _, _)| *d);
+
+    let total_points = (timeline.len() + forecast.len()).max(2);
+
     // Create the graph grid
     let mut grid = vec![vec![' '; width]; height];
-    
-    // Draw the data points and lines
-    for i in 0..data.len() {
-        let (_, value) = data[i];
-        let x = (i as f32 / (data.len() - 1) as f32 * (width - 1) as f32) as usize;
-        let y = ((value - min_value) / range * (height - 1) as f32) as usize;
-        let y = height - 1 - y; // Flip the y-axis
-        
-        if y < height && x < width {
-            grid[y][x] = '●';
-        }
 
-        // Draw connecting lines
-        if i > 0 {
-            let prev_x = ((i - 1) as f32 / (data.len() - 1) as f32 * (width - 1) as f32) as usize;
-            let prev_y = ((data[i-1].1 - min_value) / range * (height - 1) as f32) as usize;
-            let prev_y = height - 1 - prev_y;
-            
-            // Draw line between points
-            let dx = x as isize - prev_x as isize;
-            let dy = y as isize - prev_y as isize;
-            let steps = dx.abs().max(dy.abs());
-            
-            for step in 1..steps {
-                let px = prev_x as isize + (dx * step / steps);
-                let py = prev_y as isize + (dy * step / steps);
-                
-                if px >= 0 && px < width as isize && py >= 0 && py < height as isize {
-                    let px = px as usize;
-                    let py = py as usize;
-                    if grid[py][px] == ' ' {
-                        grid[py][px] = '·';
-                    }
+    let x_for = |idx: usize| -> usize { (idx as f32 / (total_points - 1) as f32 * (width - 1) as f32) as usize };
+    let y_for = |value: f32| -> usize {
+        let y = ((value - min_value) / range * (height - 1) as f32).round() as isize;
+        (height as isize - 1 - y).clamp(0, height as isize - 1) as usize
+    };
+
+    let mut draw_line = |from: (usize, usize), to: (usize, usize), glyph: char| {
+        let dx = to.0 as isize - from.0 as isize;
+        let dy = to.1 as isize - from.1 as isize;
+        let steps = dx.abs().max(dy.abs());
+        for step in 1..steps {
+            let px = from.0 as isize + (dx * step / steps);
+            let py = from.1 as isize + (dy * step / steps);
+            if px >= 0 && px < width as isize && py >= 0 && py < height as isize {
+                let (px, py) = (px as usize, py as usize);
+                if grid[py][px] == ' ' {
+                    grid[py][px] = glyph;
                 }
             }
         }
+    };
+
+    // Draw the timeline: clean points joined by `·`, outliers marked `!`
+    // and excluded from line-drawing so they don't pull the trend line.
+    let mut prev_clean: Option<(usize, usize)> = None;
+    for (i, &(_, value, is_outlier)) in timeline.iter().enumerate() {
+        let (x, y) = (x_for(i), y_for(value));
+        if x >= width {
+            continue;
+        }
+        if is_outlier {
+            grid[y][x] = '!';
+            prev_clean = None;
+        } else {
+            grid[y][x] = '●';
+            if let Some(prev) = prev_clean {
+                draw_line(prev, (x, y), '·');
+            }
+            prev_clean = Some((x, y));
+        }
+    }
+
+    // Draw the forecast points and lines, continuing from the last clean point
+    let mut prev = prev_clean;
+    for (i, &(_, value)) in forecast.iter().enumerate() {
+        let (x, y) = (x_for(timeline.len() + i), y_for(value));
+        if x >= width {
+            continue;
+        }
+        grid[y][x] = '×';
+        if let Some(p) = prev {
+            draw_line(p, (x, y), ':');
+        }
+        prev = Some((x, y));
     }
-    
+
     // Convert grid to strings with y-axis labels
     let mut result = Vec::new();
     let step = range / (height - 1) as f32;
-    
+
     // Add title
     result.push(format!("\n{} {}", title.bold(), "Progression"));
     result.push("─".repeat(width + 7));
-    
+
     // Add the graph with y-axis labels
     for (i, row) in grid.iter().enumerate() {
         let value = min_value + step * (height - 1 - i) as f32;
         let label = format!("{:4.0} │{}", value, row.iter().collect::<String>());
         result.push(label);
     }
-    
+
     // Add x-axis
     result.push(format!("     └{}", "─".repeat(width)));
-    
+
     // Add date labels
-    if !data.is_empty() {
-        let first_date = data.first().unwrap().0.format("%Y-%m-%d").to_string();
-        let last_date = data.last().unwrap().0.format("%Y-%m-%d").to_string();
-        result.push(format!("     {}  {}", first_date, last_date));
+    let first_date = timeline.first().unwrap().0.format(date_fmt).to_string();
+    let last_date =
+        forecast.last().map(|(d, _)| *d).unwrap_or(timeline.last().unwrap().0).format(date_fmt).to_string();
+    result.push(format!("     {}  {}", first_date, last_date));
+
+    if !outliers.is_empty() {
+        result.push(format!("     {} {} point(s) flagged as outliers (!)", "note:".dimmed(), outliers.len()));
     }
-    
+
     result
 }
 
-async fn show_global_progression(pool: &SqlitePool, weeks: u32, show_graph: bool) -> Result<()> {
-    // Get weekly tonnage data
-    let tonnage_data: Vec<(String, f64)> = sqlx::query_as(
-        r#"
-        WITH weekly_data AS (
-            SELECT 
-                date(es.timestamp, 'weekday 1', '-6 days') as week_start,
-                SUM(CAST(es.weight AS REAL) * CAST(es.reps AS INTEGER)) as tonnage
+/// One bucket of the global progression series, `week_start` paired with
+/// whatever tonnage/PR figures that bucket has — either can be missing
+/// since the tonnage and PR series aren't necessarily bucketed identically.
+#[derive(Serialize)]
+struct GlobalWeeklyRecord {
+    week_start: String,
+    tonnage: Option<f64>,
+    pr_improvement_percent: Option<f32>,
+}
+
+/// One bucket of a muscle group's progression series.
+#[derive(Serialize)]
+struct MuscleWeeklyRecord {
+    week_start: String,
+    sets: Option<i64>,
+    pr_improvement_percent: Option<f32>,
+}
+
+/// Quotes a CSV field only when it needs it (contains a comma, quote, or
+/// newline), doubling any embedded quotes per RFC 4180.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Outer-joins a tonnage/volume series with a PR-improvement series on
+/// `week_start`, preserving chronological order even when the two series
+/// don't share every bucket.
+fn merge_weekly<T: Copy>(series: &[(String, T)], pr: &[(String, f32)]) -> Vec<(String, Option<T>, Option<f32>)> {
+    let mut by_week: BTreeMap<String, (Option<T>, Option<f32>)> = BTreeMap::new();
+    for (week, value) in series {
+        by_week.entry(week.clone()).or_insert((None, None)).0 = Some(*value);
+    }
+    for (week, improvement) in pr {
+        by_week.entry(week.clone()).or_insert((None, None)).1 = Some(*improvement);
+    }
+    by_week.into_iter().map(|(week, (value, improvement))| (week, value, improvement)).collect()
+}
+
+/// `status --format csv`: the global weekly tonnage/PR-improvement series as
+/// one row per bucket, for loading into a spreadsheet or pandas.
+fn print_global_progression_csv(records: &[GlobalWeeklyRecord]) {
+    println!("week_start,tonnage,pr_improvement_percent");
+    for r in records {
+        println!(
+            "{},{},{}",
+            csv_field(&r.week_start),
+            r.tonnage.map(|t| format!("{:.2}", t)).unwrap_or_default(),
+            r.pr_improvement_percent.map(|p| format!("{:.2}", p)).unwrap_or_default(),
+        );
+    }
+}
+
+/// `status --muscle <m> --format csv`: the muscle group's weekly
+/// volume/PR-improvement series as one row per bucket.
+fn print_muscle_progression_csv(records: &[MuscleWeeklyRecord]) {
+    println!("week_start,sets,pr_improvement_percent");
+    for r in records {
+        println!(
+            "{},{},{}",
+            csv_field(&r.week_start),
+            r.sets.map(|s| s.to_string()).unwrap_or_default(),
+            r.pr_improvement_percent.map(|p| format!("{:.2}", p)).unwrap_or_default(),
+        );
+    }
+}
+
+/// Decouples `show_global_progression`/`show_muscle_progression`'s weekly
+/// tonnage/PR-record fetch from `&SqlitePool`, so the bucketing,
+/// outlier-filtering, and graph-building pipeline around them
+/// (`build_global_series`/`build_muscle_series`) can be driven by
+/// deterministic in-memory fixtures in tests instead of a live database.
+#[async_trait::async_trait]
+trait ProgressionProvider {
+    /// Per-set `(bucket_start, tonnage)` rows across all exercises in
+    /// `[window_start, window_end)`, pre-aggregation — collapsed into one
+    /// number per bucket by whichever `Stat` the caller picked.
+    async fn tonnage_rows(&self, bucket: &str, window_start: &str, window_end: &str) -> Result<Vec<(String, f64)>>;
+
+    /// Sets logged per bucket for `muscle` in the same window — already a
+    /// SQL `COUNT(*)`, since a raw set count has no per-set distribution
+    /// left to collapse with `stat`.
+    async fn muscle_weekly_sets(
+        &self,
+        bucket: &str,
+        muscle: &str,
+        window_start: &str,
+        window_end: &str,
+    ) -> Result<Vec<(String, i64)>>;
+
+    /// Per-set `(bucket_start, exercise_id, est_1rm)` rows in the window,
+    /// optionally restricted to `muscle`.
+    async fn pr_rows(
+        &self,
+        bucket: &str,
+        window_start: &str,
+        window_end: &str,
+        muscle: Option<&str>,
+    ) -> Result<Vec<(String, String, f64)>>;
+
+    /// Each exercise's best-ever estimated 1RM logged before `window_start`,
+    /// optionally restricted to `muscle` — the "personal record ever"
+    /// baseline `weekly_pr_improvements` compares against.
+    async fn baseline_1rm(&self, window_start: &str, muscle: Option<&str>) -> Result<Vec<(String, f64)>>;
+}
+
+/// The real backend: runs the same queries `show_global_progression`/
+/// `show_muscle_progression` always have, against the live database.
+struct SqliteProgressionProvider<'a> {
+    pool: &'a SqlitePool,
+}
+
+#[async_trait::async_trait]
+impl ProgressionProvider for SqliteProgressionProvider<'_> {
+    async fn tonnage_rows(&self, bucket: &str, window_start: &str, window_end: &str) -> Result<Vec<(String, f64)>> {
+        let rows = sqlx::query_as(&format!(
+            r#"
+            SELECT
+                {bucket} as week_start,
+                CAST(es.weight AS REAL) * CAST(es.reps AS INTEGER) as tonnage
             FROM exercise_sets es
             JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
             JOIN training_sessions ts ON ts.id = tse.training_session_id
-            WHERE es.timestamp >= datetime('now', '-' || ? || ' days')
+            WHERE es.timestamp >= ? AND es.timestamp < ?
             AND ts.end_time IS NOT NULL
             AND es.weight > 0
-            GROUP BY week_start
-            ORDER BY week_start
-        )
-        SELECT week_start, tonnage FROM weekly_data
-        "#,
-    )
-    .bind(weeks * 7)
-    .fetch_all(pool)
-    .await?;
+            ORDER BY es.timestamp
+            "#,
+        ))
+        .bind(window_start)
+        .bind(window_end)
+        .fetch_all(self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    async fn muscle_weekly_sets(
+        &self,
+        bucket: &str,
+        muscle: &str,
+        window_start: &str,
+        window_end: &str,
+    ) -> Result<Vec<(String, i64)>> {
+        let rows = sqlx::query_as(&format!(
+            r#"
+            WITH weekly_muscle_data AS (
+                SELECT
+                    {bucket} as week_start,
+                    COUNT(*) as weekly_sets
+                FROM exercise_sets es
+                JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+                JOIN training_sessions ts ON ts.id = tse.training_session_id
+                JOIN exercises e ON e.id = tse.exercise_id
+                WHERE es.timestamp >= ? AND es.timestamp < ?
+                AND ts.end_time IS NOT NULL
+                AND e.primary_muscle = ?
+                GROUP BY week_start
+                ORDER BY week_start
+            )
+            SELECT week_start, weekly_sets FROM weekly_muscle_data
+            "#,
+        ))
+        .bind(window_start)
+        .bind(window_end)
+        .bind(muscle)
+        .fetch_all(self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    async fn pr_rows(
+        &self,
+        bucket: &str,
+        window_start: &str,
+        window_end: &str,
+        muscle: Option<&str>,
+    ) -> Result<Vec<(String, String, f64)>> {
+        let rows = match muscle {
+            Some(muscle) => {
+                sqlx::query_as(&format!(
+                    r#"
+                    SELECT
+                        {bucket} as week_start,
+                        tse.exercise_id,
+                        CAST(es.weight AS REAL) * (1 + CAST(es.reps AS REAL) / 30) as est_1rm
+                    FROM exercise_sets es
+                    JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+                    JOIN training_sessions ts ON ts.id = tse.training_session_id
+                    JOIN exercises e ON e.id = tse.exercise_id
+                    WHERE es.timestamp >= ? AND es.timestamp < ?
+                    AND ts.end_time IS NOT NULL
+                    AND e.primary_muscle = ?
+                    AND es.weight > 0
+                    ORDER BY es.timestamp
+                    "#,
+                ))
+                .bind(window_start)
+                .bind(window_end)
+                .bind(muscle)
+                .fetch_all(self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query_as(&format!(
+                    r#"
+                    SELECT
+                        {bucket} as week_start,
+                        tse.exercise_id,
+                        CAST(es.weight AS REAL) * (1 + CAST(es.reps AS REAL) / 30) as est_1rm
+                    FROM exercise_sets es
+                    JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+                    JOIN training_sessions ts ON ts.id = tse.training_session_id
+                    WHERE es.timestamp >= ? AND es.timestamp < ?
+                    AND ts.end_time IS NOT NULL
+                    AND es.weight > 0
+                    ORDER BY es.timestamp
+                    "#,
+                ))
+                .bind(window_start)
+                .bind(window_end)
+                .fetch_all(self.pool)
+                .await?
+            }
+        };
+        Ok(rows)
+    }
+
+    async fn baseline_1rm(&self, window_start: &str, muscle: Option<&str>) -> Result<Vec<(String, f64)>> {
+        let rows = match muscle {
+            Some(muscle) => {
+                sqlx::query_as(
+                    r#"
+                    SELECT
+                        exercise_id,
+                        MAX(CAST(weight AS REAL) * (1 + CAST(reps AS REAL) / 30)) as baseline_1rm
+                    FROM exercise_sets es
+                    JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+                    JOIN training_sessions ts ON ts.id = tse.training_session_id
+                    JOIN exercises e ON e.id = tse.exercise_id
+                    WHERE es.timestamp < ?
+                    AND ts.end_time IS NOT NULL
+                    AND e.primary_muscle = ?
+                    AND es.weight > 0
+                    GROUP BY exercise_id
+                    "#,
+                )
+                .bind(window_start)
+                .bind(muscle)
+                .fetch_all(self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query_as(
+                    r#"
+                    SELECT
+                        exercise_id,
+                        MAX(CAST(weight AS REAL) * (1 + CAST(reps AS REAL) / 30)) as baseline_1rm
+                    FROM exercise_sets es
+                    JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+                    JOIN training_sessions ts ON ts.id = tse.training_session_id
+                    WHERE es.timestamp < ?
+                    AND ts.end_time IS NOT NULL
+                    AND es.weight > 0
+                    GROUP BY exercise_id
+                    "#,
+                )
+                .bind(window_start)
+                .fetch_all(self.pool)
+                .await?
+            }
+        };
+        Ok(rows)
+    }
+}
+
+/// The global weekly tonnage/PR series, already bucketed, outlier-filtered,
+/// and ready for `create_ascii_graph` — everything `show_global_progression`
+/// needs from the database, fetched purely through `ProgressionProvider`.
+struct GlobalSeries {
+    tonnage_data: Vec<(String, f64)>,
+    tonnage_outliers: Vec<(String, f64)>,
+    pr_progression_data: Vec<(String, f32)>,
+}
+
+async fn build_global_series(
+    provider: &impl ProgressionProvider,
+    bucket: &str,
+    window_start: &str,
+    window_end: &str,
+    outlier_threshold: f64,
+    stat: Stat,
+) -> Result<GlobalSeries> {
+    // Get each set's tonnage alongside its bucket, then collapse per bucket
+    // with the chosen `stat` (sum previously hard-coded mean-like behavior).
+    let raw_tonnage = provider.tonnage_rows(bucket, window_start, window_end).await?;
+    let tonnage_data = bucket_and_aggregate(raw_tonnage, stat);
+
+    // Exclude weeks whose tonnage is a MAD-based outlier from trend
+    // comparisons and graph scaling, but still plot them (flagged) so a
+    // one-off deload or PR week doesn't get silently erased.
+    let (tonnage_data, tonnage_outliers) = filter_outliers(&tonnage_data, outlier_threshold);
+
+    // Get raw per-set 1RM estimates for the period, bucketed per exercise,
+    // plus each exercise's pre-period baseline PR (the baseline stays a
+    // true MAX — it's "best ever before this window", not a weekly series).
+    let raw_pr_sets = provider.pr_rows(bucket, window_start, window_end, None).await?;
+    let baseline_prs = provider.baseline_1rm(window_start, None).await?;
+    let baseline_1rm: HashMap<String, f64> = baseline_prs.into_iter().collect();
+
+    // Collapse each (week, exercise) bucket with the chosen `stat`, then
+    // the same stat again across exercises to get the week's overall
+    // improvement figure (replacing the hard-coded MAX / AVG pair).
+    let pr_progression_data = weekly_pr_improvements(raw_pr_sets, &baseline_1rm, stat);
+
+    Ok(GlobalSeries { tonnage_data, tonnage_outliers, pr_progression_data })
+}
+
+/// The per-muscle weekly volume/PR series, mirroring `GlobalSeries` but with
+/// a sets-per-week count (an SQL `COUNT(*)`) in place of tonnage.
+struct MuscleSeries {
+    volume_data: Vec<(String, i64)>,
+    volume_outliers: Vec<(String, i64)>,
+    pr_progression_data: Vec<(String, f32)>,
+}
+
+async fn build_muscle_series(
+    provider: &impl ProgressionProvider,
+    muscle: &str,
+    bucket: &str,
+    window_start: &str,
+    window_end: &str,
+    outlier_threshold: f64,
+    stat: Stat,
+) -> Result<MuscleSeries> {
+    // Get weekly volume data for the muscle group
+    let muscle_volume_data = provider.muscle_weekly_sets(bucket, muscle, window_start, window_end).await?;
+
+    // Exclude weeks whose volume is a MAD-based outlier from trend
+    // comparisons and graph scaling, matching the tonnage trend above.
+    let muscle_volume_data_f64: Vec<(String, f64)> =
+        muscle_volume_data.iter().map(|(week, sets)| (week.clone(), *sets as f64)).collect();
+    let (muscle_volume_data, muscle_volume_outliers) = filter_outliers(&muscle_volume_data_f64, outlier_threshold);
+    let volume_data: Vec<(String, i64)> =
+        muscle_volume_data.into_iter().map(|(week, sets)| (week, sets as i64)).collect();
+    let volume_outliers: Vec<(String, i64)> =
+        muscle_volume_outliers.into_iter().map(|(week, sets)| (week, sets as i64)).collect();
+
+    // Get raw per-set 1RM estimates for this muscle group, same reduction
+    // as the global progression's PR series above.
+    let raw_pr_sets = provider.pr_rows(bucket, window_start, window_end, Some(muscle)).await?;
+    let baseline_prs = provider.baseline_1rm(window_start, Some(muscle)).await?;
+    let baseline_1rm: HashMap<String, f64> = baseline_prs.into_iter().collect();
+    let pr_progression_data = weekly_pr_improvements(raw_pr_sets, &baseline_1rm, stat);
+
+    Ok(MuscleSeries { volume_data, volume_outliers, pr_progression_data })
+}
+
+async fn show_global_progression(
+    pool: &SqlitePool,
+    weeks: u32,
+    show_graph: bool,
+    forecast_weeks: Option<u32>,
+    outlier_threshold: f64,
+    granularity: Granularity,
+    stat: Stat,
+    offset: i32,
+    fmt: OutputFmt,
+    cfg: &Config,
+) -> Result<()> {
+    let unit = cfg.weight_unit();
+    let bucket = bucket_expr(granularity);
+    let (window_start_date, window_end_date) = week_window(offset, weeks);
+    let window_start = window_start_date.format("%Y-%m-%d").to_string();
+    let window_end = window_end_date.format("%Y-%m-%d").to_string();
+
+    let provider = SqliteProgressionProvider { pool };
+    let GlobalSeries { tonnage_data, tonnage_outliers, pr_progression_data } =
+        build_global_series(&provider, bucket, &window_start, &window_end, outlier_threshold, stat).await?;
 
     // Get global stats
     let (total_tonnage, total_sets, total_sessions, active_exercises): (f64, i64, i64, i64) = sqlx::query_as(
         r#"
         WITH period_data AS (
-            SELECT 
+            SELECT
                 es.weight,
                 es.reps,
                 tse.exercise_id,
@@ -120,10 +804,10 @@ async fn show_global_progression(pool: &SqlitePool, weeks: u32, show_graph: bool
             FROM exercise_sets es
             JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
             JOIN training_sessions ts ON ts.id = tse.training_session_id
-            WHERE es.timestamp >= datetime('now', '-' || ? || ' days')
+            WHERE es.timestamp >= ? AND es.timestamp < ?
             AND ts.end_time IS NOT NULL
         )
-        SELECT 
+        SELECT
             COALESCE(SUM(CAST(weight AS REAL) * CAST(reps AS INTEGER)), 0) as total_tonnage,
             CAST(COUNT(*) AS INTEGER) as total_sets,
             CAST(COUNT(DISTINCT session_id) AS INTEGER) as total_sessions,
@@ -131,58 +815,26 @@ async fn show_global_progression(pool: &SqlitePool, weeks: u32, show_graph: bool
         FROM period_data
         "#,
     )
-    .bind(weeks * 7)
+    .bind(&window_start)
+    .bind(&window_end)
     .fetch_one(pool)
     .await?;
 
-    // Get PR progression data for the period
-    let pr_progression_data: Vec<(String, f32)> = sqlx::query_as(
-        r#"
-        WITH weekly_pr_data AS (
-            SELECT 
-                date(es.timestamp, 'weekday 1', '-6 days') as week_start,
-                tse.exercise_id,
-                MAX(CAST(es.weight AS REAL) * (1 + CAST(es.reps AS REAL) / 30)) as week_best_1rm
-            FROM exercise_sets es
-            JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
-            JOIN training_sessions ts ON ts.id = tse.training_session_id
-            WHERE es.timestamp >= datetime('now', '-' || ? || ' days')
-            AND ts.end_time IS NOT NULL
-            AND es.weight > 0
-            GROUP BY week_start, tse.exercise_id
-        ),
-        baseline_prs AS (
-            SELECT 
-                exercise_id,
-                MAX(CAST(weight AS REAL) * (1 + CAST(reps AS REAL) / 30)) as baseline_1rm
-            FROM exercise_sets es
-            JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
-            JOIN training_sessions ts ON ts.id = tse.training_session_id
-            WHERE es.timestamp < datetime('now', '-' || ? || ' days')
-            AND ts.end_time IS NOT NULL
-            AND es.weight > 0
-            GROUP BY exercise_id
-        ),
-        weekly_improvements AS (
-            SELECT 
-                wpd.week_start,
-                AVG(CASE 
-                    WHEN bp.baseline_1rm > 0 THEN 
-                        ((wpd.week_best_1rm - bp.baseline_1rm) / bp.baseline_1rm) * 100
-                    ELSE 0 
-                END) as avg_improvement_percent
-            FROM weekly_pr_data wpd
-            JOIN baseline_prs bp ON bp.exercise_id = wpd.exercise_id
-            GROUP BY wpd.week_start
-            ORDER BY wpd.week_start
-        )
-        SELECT week_start, avg_improvement_percent FROM weekly_improvements
-        "#,
-    )
-    .bind(weeks * 7)
-    .bind(weeks * 7)
-    .fetch_all(pool)
-    .await?;
+    // `table` (the default) keeps the summary-stats-and-graph report below;
+    // `json`/`cbor`/`csv` skip straight to the weekly aggregates themselves.
+    if fmt.format != OutputFormat::Pretty {
+        let records: Vec<GlobalWeeklyRecord> = merge_weekly(&tonnage_data, &pr_progression_data)
+            .into_iter()
+            .map(|(week_start, tonnage, pr_improvement_percent)| GlobalWeeklyRecord {
+                week_start,
+                tonnage,
+                pr_improvement_percent,
+            })
+            .collect();
+
+        emit(fmt, &records, || print_global_progression_csv(&records));
+        return Ok(());
+    }
 
     // Calculate percentage improvements
     let (early_tonnage, late_tonnage, early_sets, late_sets) = if tonnage_data.len() >= 4 {
@@ -194,48 +846,55 @@ async fn show_global_progression(pool: &SqlitePool, weeks: u32, show_graph: bool
         let early_avg_tonnage = early_weeks.iter().map(|(_, t)| *t).sum::<f64>() / early_weeks.len() as f64;
         let late_avg_tonnage = late_weeks.iter().map(|(_, t)| *t).sum::<f64>() / late_weeks.len() as f64;
         
-        // Get corresponding sets data for the same periods
-        let early_sets_data: Vec<(String, i64)> = sqlx::query_as(
+        // Get corresponding sets data for the same periods: the first and
+        // last quarter of the [window_start, window_end) range.
+        let quarter_days = (weeks as u64 / 4) * 7;
+        let early_period_end = (window_start_date + Days::new(quarter_days)).format("%Y-%m-%d").to_string();
+        let late_period_start = (window_end_date - Days::new(quarter_days)).format("%Y-%m-%d").to_string();
+
+        let early_sets_data: Vec<(String, i64)> = sqlx::query_as(&format!(
             r#"
             WITH weekly_data AS (
-                SELECT 
-                    date(es.timestamp, 'weekday 1', '-6 days') as week_start,
+                SELECT
+                    {bucket} as week_start,
                     COUNT(*) as total_sets
                 FROM exercise_sets es
                 JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
                 JOIN training_sessions ts ON ts.id = tse.training_session_id
-                WHERE es.timestamp >= datetime('now', '-' || ? || ' days')
-                AND es.timestamp < datetime('now', '-' || ? || ' days')
+                WHERE es.timestamp >= ?
+                AND es.timestamp < ?
                 AND ts.end_time IS NOT NULL
                 GROUP BY week_start
                 ORDER BY week_start
             )
             SELECT week_start, total_sets FROM weekly_data
             "#,
-        )
-        .bind(weeks * 7)
-        .bind((weeks * 3 / 4) * 7)  // Early period: from start to 3/4 point
+        ))
+        .bind(&window_start)
+        .bind(&early_period_end)
         .fetch_all(pool)
         .await?;
 
-        let late_sets_data: Vec<(String, i64)> = sqlx::query_as(
+        let late_sets_data: Vec<(String, i64)> = sqlx::query_as(&format!(
             r#"
             WITH weekly_data AS (
-                SELECT 
-                    date(es.timestamp, 'weekday 1', '-6 days') as week_start,
+                SELECT
+                    {bucket} as week_start,
                     COUNT(*) as total_sets
                 FROM exercise_sets es
                 JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
                 JOIN training_sessions ts ON ts.id = tse.training_session_id
-                WHERE es.timestamp >= datetime('now', '-' || ? || ' days')
+                WHERE es.timestamp >= ?
+                AND es.timestamp < ?
                 AND ts.end_time IS NOT NULL
                 GROUP BY week_start
                 ORDER BY week_start
             )
             SELECT week_start, total_sets FROM weekly_data
             "#,
-        )
-        .bind((weeks / 4) * 7)  // Late period: last quarter
+        ))
+        .bind(&late_period_start)
+        .bind(&window_end)
         .fetch_all(pool)
         .await?;
 
@@ -266,20 +925,29 @@ async fn show_global_progression(pool: &SqlitePool, weeks: u32, show_graph: bool
         (0.0, 0)
     };
 
-    println!("{} ({} weeks)", "Global Training Status".cyan().bold(), weeks);
+    if offset == 0 {
+        println!("{} ({} weeks)", "Global Training Status".cyan().bold(), weeks);
+    } else {
+        println!("{} ({} weeks ending {})", "Global Training Status".cyan().bold(), weeks, window_end);
+    }
     println!();
 
     // Print summary stats
-    println!("{}: {:.0} kg", "Total tonnage".cyan().bold(), total_tonnage);
+    println!("{}: {:.0} {}", "Total tonnage".cyan().bold(), unit.from_kg(total_tonnage as f32), unit.suffix());
     println!("{}: {} sets", "Total volume".cyan().bold(), total_sets);
     println!("{}: {} sessions", "Training sessions".cyan().bold(), total_sessions);
     println!("{}: {} exercises", "Active exercises".cyan().bold(), active_exercises);
-    
+
     if total_sessions > 0 {
         let avg_frequency = total_sessions as f64 / (weeks as f64);
         let avg_tonnage_per_session = total_tonnage / total_sessions as f64;
         println!("{}: {:.1} sessions/week", "Avg frequency".cyan().bold(), avg_frequency);
-        println!("{}: {:.0} kg/session", "Avg tonnage/session".cyan().bold(), avg_tonnage_per_session);
+        println!(
+            "{}: {:.0} {}/session",
+            "Avg tonnage/session".cyan().bold(),
+            unit.from_kg(avg_tonnage_per_session as f32),
+            unit.suffix()
+        );
     }
 
     // Print percentage improvements
@@ -293,13 +961,19 @@ async fn show_global_progression(pool: &SqlitePool, weeks: u32, show_graph: bool
 
         println!();
         println!("{}", "Volume trends over period:".cyan().bold());
-        
+
         let tonnage_color = if tonnage_improvement > 0.0 { "▲".green() } else { "▼".red() };
         let sets_color = if sets_improvement > 0.0 { "▲".green() } else { "▼".red() };
-        
-        println!("  {} Weekly tonnage: {:+.1}% ({:.0} → {:.0} kg)", 
-                tonnage_color, tonnage_improvement, early_tonnage, late_tonnage);
-        println!("  {} Weekly volume: {:+.1}% ({:.0} → {:.0} sets)", 
+
+        println!(
+            "  {} Weekly tonnage: {:+.1}% ({:.0} → {:.0} {})",
+            tonnage_color,
+            tonnage_improvement,
+            unit.from_kg(early_tonnage as f32),
+            unit.from_kg(late_tonnage as f32),
+            unit.suffix()
+        );
+        println!("  {} Weekly volume: {:+.1}% ({:.0} → {:.0} sets)",
                 sets_color, sets_improvement, early_sets, late_sets);
     }
 
@@ -316,18 +990,15 @@ async fn show_global_progression(pool: &SqlitePool, weeks: u32, show_graph: bool
     if show_graph {
         if !tonnage_data.is_empty() {
             // Convert tonnage data to graph format
-            let tonnage_graph_data: Vec<(DateTime<Utc>, f32)> = tonnage_data
-                .into_iter()
-                .filter_map(|(week_start, tonnage)| {
-                    // Parse the date and convert to DateTime<Utc>
-                    if let Ok(naive_date) = chrono::NaiveDate::parse_from_str(&week_start, "%Y-%m-%d") {
-                        let naive_datetime = naive_date.and_hms_opt(0, 0, 0)?;
-                        Some((naive_datetime.and_utc(), tonnage as f32))
-                    } else {
-                        None
-                    }
-                })
-                .collect();
+            let parse_week = |(week_start, tonnage): (String, f64)| -> Option<(DateTime<Utc>, f32)> {
+                let naive_date = chrono::NaiveDate::parse_from_str(&week_start, "%Y-%m-%d").ok()?;
+                let naive_datetime = naive_date.and_hms_opt(0, 0, 0)?;
+                Some((naive_datetime.and_utc(), tonnage as f32))
+            };
+            let tonnage_graph_data: Vec<(DateTime<Utc>, f32)> =
+                tonnage_data.into_iter().filter_map(parse_week).collect();
+            let tonnage_outlier_data: Vec<(DateTime<Utc>, f32)> =
+                tonnage_outliers.into_iter().filter_map(parse_week).collect();
 
             if !tonnage_graph_data.is_empty() {
                 // Get terminal size
@@ -335,10 +1006,48 @@ async fn show_global_progression(pool: &SqlitePool, weeks: u32, show_graph: bool
                 let width = (term_width / 2).min(60);
                 let height = (term_height / 2).min(15);
 
-                let graph = create_ascii_graph(&tonnage_graph_data, width, height, "Weekly Tonnage");
+                let tonnage_series: Vec<f32> = tonnage_graph_data.iter().map(|(_, v)| *v).collect();
+                let forecast_data = forecast_weeks
+                    .map(|horizon| {
+                        project_weekly(&tonnage_graph_data, &tonnage_series, horizon, 0.5, 0.3, granularity)
+                    })
+                    .unwrap_or_default();
+
+                let graph = create_ascii_graph(
+                    &tonnage_graph_data,
+                    &tonnage_outlier_data,
+                    &forecast_data,
+                    width,
+                    height,
+                    &format!("{} Tonnage", granularity_title(granularity)),
+                    date_format(granularity),
+                );
                 for line in graph {
                     println!("{}", line);
                 }
+
+                if let Some(horizon) = forecast_weeks {
+                    if let Some((_, projected)) = forecast_data.last() {
+                        println!(
+                            "{} {:.0} {}",
+                            format!(
+                                "Projected tonnage in {} {}:",
+                                horizon,
+                                bucket_noun(granularity, horizon)
+                            )
+                            .cyan()
+                            .bold(),
+                            unit.from_kg(*projected),
+                            unit.suffix()
+                        );
+                    } else {
+                        println!(
+                            "{} {}",
+                            "warning:".yellow().bold(),
+                            "need at least 3 weeks of data to forecast"
+                        );
+                    }
+                }
             }
         }
 
@@ -361,7 +1070,15 @@ async fn show_global_progression(pool: &SqlitePool, weeks: u32, show_graph: bool
                 let width = (term_width / 2).min(60);
                 let height = (term_height / 2).min(15);
 
-                let graph = create_ascii_graph(&pr_graph_data, width, height, "PR Improvement (%)");
+                let graph = create_ascii_graph(
+                    &pr_graph_data,
+                    &[],
+                    &[],
+                    width,
+                    height,
+                    "PR Improvement (%)",
+                    date_format(granularity),
+                );
                 for line in graph {
                     println!("{}", line);
                 }
@@ -372,31 +1089,28 @@ async fn show_global_progression(pool: &SqlitePool, weeks: u32, show_graph: bool
     Ok(())
 }
 
-async fn show_muscle_progression(pool: &SqlitePool, muscle: &str, weeks: u32, show_graph: bool) -> Result<()> {
-    // Get weekly volume data for the muscle group
-    let muscle_volume_data: Vec<(String, i64)> = sqlx::query_as(
-        r#"
-        WITH weekly_muscle_data AS (
-            SELECT 
-                date(es.timestamp, 'weekday 1', '-6 days') as week_start,
-                COUNT(*) as weekly_sets
-            FROM exercise_sets es
-            JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
-            JOIN training_sessions ts ON ts.id = tse.training_session_id
-            JOIN exercises e ON e.id = tse.exercise_id
-            WHERE es.timestamp >= datetime('now', '-' || ? || ' days')
-            AND ts.end_time IS NOT NULL
-            AND e.primary_muscle = ?
-            GROUP BY week_start
-            ORDER BY week_start
-        )
-        SELECT week_start, weekly_sets FROM weekly_muscle_data
-        "#,
-    )
-    .bind(weeks * 7)
-    .bind(muscle)
-    .fetch_all(pool)
-    .await?;
+async fn show_muscle_progression(
+    pool: &SqlitePool,
+    muscle: &str,
+    weeks: u32,
+    show_graph: bool,
+    forecast_weeks: Option<u32>,
+    outlier_threshold: f64,
+    granularity: Granularity,
+    stat: Stat,
+    offset: i32,
+    fmt: OutputFmt,
+    cfg: &Config,
+) -> Result<()> {
+    let unit = cfg.weight_unit();
+    let bucket = bucket_expr(granularity);
+    let (window_start_date, window_end_date) = week_window(offset, weeks);
+    let window_start = window_start_date.format("%Y-%m-%d").to_string();
+    let window_end = window_end_date.format("%Y-%m-%d").to_string();
+
+    let provider = SqliteProgressionProvider { pool };
+    let MuscleSeries { volume_data: muscle_volume_data, volume_outliers: muscle_volume_outliers, pr_progression_data } =
+        build_muscle_series(&provider, muscle, bucket, &window_start, &window_end, outlier_threshold, stat).await?;
 
     // Get muscle-specific stats
     let (muscle_tonnage, muscle_sets, active_exercises): (f64, i64, i64) = sqlx::query_as(
@@ -410,79 +1124,41 @@ async fn show_muscle_progression(pool: &SqlitePool, muscle: &str, weeks: u32, sh
             JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
             JOIN training_sessions ts ON ts.id = tse.training_session_id
             JOIN exercises e ON e.id = tse.exercise_id
-            WHERE es.timestamp >= datetime('now', '-' || ? || ' days')
+            WHERE es.timestamp >= ? AND es.timestamp < ?
             AND ts.end_time IS NOT NULL
             AND e.primary_muscle = ?
         )
-        SELECT 
+        SELECT
             COALESCE(SUM(CAST(weight AS REAL) * CAST(reps AS INTEGER)), 0) as tonnage,
             CAST(COUNT(*) AS INTEGER) as sets,
             CAST(COUNT(DISTINCT exercise_id) AS INTEGER) as exercises
         FROM period_data
         "#,
     )
-    .bind(weeks * 7)
+    .bind(&window_start)
+    .bind(&window_end)
     .bind(muscle)
     .fetch_one(pool)
     .await?;
 
-    // Get PR progression data for this muscle group
-    let pr_progression_data: Vec<(String, f32)> = sqlx::query_as(
-        r#"
-        WITH weekly_pr_data AS (
-            SELECT 
-                date(es.timestamp, 'weekday 1', '-6 days') as week_start,
-                tse.exercise_id,
-                MAX(CAST(es.weight AS REAL) * (1 + CAST(es.reps AS REAL) / 30)) as week_best_1rm
-            FROM exercise_sets es
-            JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
-            JOIN training_sessions ts ON ts.id = tse.training_session_id
-            JOIN exercises e ON e.id = tse.exercise_id
-            WHERE es.timestamp >= datetime('now', '-' || ? || ' days')
-            AND ts.end_time IS NOT NULL
-            AND e.primary_muscle = ?
-            AND es.weight > 0
-            GROUP BY week_start, tse.exercise_id
-        ),
-        baseline_prs AS (
-            SELECT 
-                exercise_id,
-                MAX(CAST(weight AS REAL) * (1 + CAST(reps AS REAL) / 30)) as baseline_1rm
-            FROM exercise_sets es
-            JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
-            JOIN training_sessions ts ON ts.id = tse.training_session_id
-            JOIN exercises e ON e.id = tse.exercise_id
-            WHERE es.timestamp < datetime('now', '-' || ? || ' days')
-            AND ts.end_time IS NOT NULL
-            AND e.primary_muscle = ?
-            AND es.weight > 0
-            GROUP BY exercise_id
-        ),
-        weekly_improvements AS (
-            SELECT 
-                wpd.week_start,
-                AVG(CASE 
-                    WHEN bp.baseline_1rm > 0 THEN 
-                        ((wpd.week_best_1rm - bp.baseline_1rm) / bp.baseline_1rm) * 100
-                    ELSE 0 
-                END) as avg_improvement_percent
-            FROM weekly_pr_data wpd
-            JOIN baseline_prs bp ON bp.exercise_id = wpd.exercise_id
-            GROUP BY wpd.week_start
-            ORDER BY wpd.week_start
-        )
-        SELECT week_start, avg_improvement_percent FROM weekly_improvements
-        "#,
-    )
-    .bind(weeks * 7)
-    .bind(muscle)
-    .bind(weeks * 7)
-    .bind(muscle)
-    .fetch_all(pool)
-    .await?;
-
-    // Get top exercises for this muscle
-    let top_exercises: Vec<(String, f64, f32)> = sqlx::query_as(
+    // `table` (the default) keeps the summary-stats-and-graph report below;
+    // `json`/`cbor`/`csv` skip straight to the weekly aggregates themselves.
+    if fmt.format != OutputFormat::Pretty {
+        let records: Vec<MuscleWeeklyRecord> = merge_weekly(&muscle_volume_data, &pr_progression_data)
+            .into_iter()
+            .map(|(week_start, sets, pr_improvement_percent)| MuscleWeeklyRecord {
+                week_start,
+                sets,
+                pr_improvement_percent,
+            })
+            .collect();
+
+        emit(fmt, &records, || print_muscle_progression_csv(&records));
+        return Ok(());
+    }
+
+    // Get top exercises for this muscle
+    let top_exercises: Vec<(String, f64, f32)> = sqlx::query_as(
         r#"
         SELECT 
             e.name,
@@ -492,7 +1168,7 @@ async fn show_muscle_progression(pool: &SqlitePool, muscle: &str, weeks: u32, sh
         JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
         JOIN training_sessions ts ON ts.id = tse.training_session_id
         JOIN exercises e ON e.id = tse.exercise_id
-        WHERE es.timestamp >= datetime('now', '-' || ? || ' days')
+        WHERE es.timestamp >= ? AND es.timestamp < ?
         AND ts.end_time IS NOT NULL
         AND e.primary_muscle = ?
         AND es.weight > 0
@@ -501,7 +1177,8 @@ async fn show_muscle_progression(pool: &SqlitePool, muscle: &str, weeks: u32, sh
         LIMIT 5
         "#,
     )
-    .bind(weeks * 7)
+    .bind(&window_start)
+    .bind(&window_end)
     .bind(muscle)
     .fetch_all(pool)
     .await?;
@@ -535,11 +1212,21 @@ async fn show_muscle_progression(pool: &SqlitePool, muscle: &str, weeks: u32, sh
         (0.0, 0)
     };
 
-    println!("{} {} ({} weeks)", "Muscle Group Progress:".cyan().bold(), muscle.bold(), weeks);
+    if offset == 0 {
+        println!("{} {} ({} weeks)", "Muscle Group Progress:".cyan().bold(), muscle.bold(), weeks);
+    } else {
+        println!(
+            "{} {} ({} weeks ending {})",
+            "Muscle Group Progress:".cyan().bold(),
+            muscle.bold(),
+            weeks,
+            window_end
+        );
+    }
     println!();
 
     // Print muscle-specific stats
-    println!("{}: {:.0} kg", "Total tonnage".cyan().bold(), muscle_tonnage);
+    println!("{}: {:.0} {}", "Total tonnage".cyan().bold(), unit.from_kg(muscle_tonnage as f32), unit.suffix());
     println!("{}: {} sets", "Total volume".cyan().bold(), muscle_sets);
     println!("{}: {} exercises", "Active exercises".cyan().bold(), active_exercises);
 
@@ -568,22 +1255,29 @@ async fn show_muscle_progression(pool: &SqlitePool, muscle: &str, weeks: u32, sh
     println!();
     println!("{}", "Top exercises by tonnage:".cyan().bold());
     for (name, tonnage, best_1rm) in top_exercises {
-        println!("  {} — {:.0} kg tonnage, {:.0} kg best 1RM", name.bold(), tonnage, best_1rm);
+        println!(
+            "  {} — {:.0} {} tonnage, {:.0} {} best 1RM",
+            name.bold(),
+            unit.from_kg(tonnage as f32),
+            unit.suffix(),
+            unit.from_kg(best_1rm as f32),
+            unit.suffix()
+        );
     }
 
     if show_graph {
         if !muscle_volume_data.is_empty() {
             // Convert muscle volume data to graph format
-            let muscle_graph_data: Vec<(DateTime<Utc>, f32)> = muscle_volume_data
+            let parse_week = |(week_start, volume): (String, i64)| -> Option<(DateTime<Utc>, f32)> {
+                let naive_date = chrono::NaiveDate::parse_from_str(&week_start, "%Y-%m-%d").ok()?;
+                let naive_datetime = naive_date.and_hms_opt(0, 0, 0)?;
+                Some((naive_datetime.and_utc(), volume as f32))
+            };
+            let muscle_graph_data: Vec<(DateTime<Utc>, f32)> =
+                muscle_volume_data.into_iter().filter_map(parse_week).collect();
+            let muscle_outlier_data: Vec<(DateTime<Utc>, f32)> = muscle_volume_outliers
                 .into_iter()
-                .filter_map(|(week_start, volume)| {
-                    if let Ok(naive_date) = chrono::NaiveDate::parse_from_str(&week_start, "%Y-%m-%d") {
-                        let naive_datetime = naive_date.and_hms_opt(0, 0, 0)?;
-                        Some((naive_datetime.and_utc(), volume as f32))
-                    } else {
-                        None
-                    }
-                })
+                .filter_map(|(week, sets)| parse_week((week, sets as i64)))
                 .collect();
 
             if !muscle_graph_data.is_empty() {
@@ -592,11 +1286,49 @@ async fn show_muscle_progression(pool: &SqlitePool, muscle: &str, weeks: u32, sh
                 let width = (term_width / 2).min(60);
                 let height = (term_height / 2).min(15);
 
-                let title = format!("{} Weekly Volume (sets)", muscle);
-                let graph = create_ascii_graph(&muscle_graph_data, width, height, &title);
+                let volume_series: Vec<f32> = muscle_graph_data.iter().map(|(_, v)| *v).collect();
+                let forecast_data = forecast_weeks
+                    .map(|horizon| {
+                        project_weekly(&muscle_graph_data, &volume_series, horizon, 0.5, 0.3, granularity)
+                    })
+                    .unwrap_or_default();
+
+                let title = format!("{} {} Volume (sets)", muscle, granularity_title(granularity));
+                let graph = create_ascii_graph(
+                    &muscle_graph_data,
+                    &muscle_outlier_data,
+                    &forecast_data,
+                    width,
+                    height,
+                    &title,
+                    date_format(granularity),
+                );
                 for line in graph {
                     println!("{}", line);
                 }
+
+                if let Some(horizon) = forecast_weeks {
+                    if let Some((_, projected)) = forecast_data.last() {
+                        println!(
+                            "{} {:.0} sets",
+                            format!(
+                                "Projected {} volume in {} {}:",
+                                granularity_title(granularity).to_lowercase(),
+                                horizon,
+                                bucket_noun(granularity, horizon)
+                            )
+                            .cyan()
+                            .bold(),
+                            projected
+                        );
+                    } else {
+                        println!(
+                            "{} {}",
+                            "warning:".yellow().bold(),
+                            "need at least 3 weeks of data to forecast"
+                        );
+                    }
+                }
             }
         }
 
@@ -620,7 +1352,8 @@ async fn show_muscle_progression(pool: &SqlitePool, muscle: &str, weeks: u32, sh
                 let height = (term_height / 2).min(15);
 
                 let title = format!("{} PR Improvement (%)", muscle);
-                let graph = create_ascii_graph(&pr_graph_data, width, height, &title);
+                let graph =
+                    create_ascii_graph(&pr_graph_data, &[], &[], width, height, &title, date_format(granularity));
                 for line in graph {
                     println!("{}", line);
                 }
@@ -631,9 +1364,714 @@ async fn show_muscle_progression(pool: &SqlitePool, muscle: &str, weeks: u32, sh
     Ok(())
 }
 
-pub async fn handle_status(muscle: Option<String>, weeks: u32, graph: bool, pool: &SqlitePool) -> Result<()> {
+/// Show a single Monday-anchored week's tonnage/sets/reps/PRs. `offset` is in
+/// weeks relative to the current week (0 = this week, -1 = last week, ...).
+async fn show_week_report(pool: &SqlitePool, offset: i32, cfg: &Config) -> Result<()> {
+    let unit = cfg.weight_unit();
+    let today = Local::now().date_naive();
+    let this_monday = today - Days::new(today.weekday().num_days_from_monday() as u64);
+    let monday = if offset >= 0 {
+        this_monday + Days::new(offset as u64 * 7)
+    } else {
+        this_monday - Days::new((-offset) as u64 * 7)
+    };
+    let sunday_end = monday + Days::new(7);
+
+    let rows: Vec<(String, f64, i64, i64)> = sqlx::query_as(
+        r#"
+        SELECT
+            date(es.timestamp) as day,
+            CAST(COALESCE(SUM(CAST(es.weight AS REAL) * CAST(es.reps AS INTEGER)), 0) AS REAL) as tonnage,
+            CAST(COUNT(*) AS INTEGER) as sets,
+            CAST(COALESCE(SUM(es.reps), 0) AS INTEGER) as reps
+        FROM exercise_sets es
+        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+        WHERE es.timestamp >= ? AND es.timestamp < ?
+        GROUP BY day
+        "#,
+    )
+    .bind(monday.format("%Y-%m-%d").to_string())
+    .bind(sunday_end.format("%Y-%m-%d").to_string())
+    .fetch_all(pool)
+    .await?;
+
+    let mut by_day = std::collections::HashMap::new();
+    for (day, tonnage, sets, reps) in &rows {
+        by_day.insert(day.clone(), (*tonnage, *sets, *reps));
+    }
+
+    let prs: Vec<(String, String, f32, i32)> = sqlx::query_as(
+        r#"
+        SELECT e.name, date(es.timestamp), es.weight, es.reps
+        FROM exercise_sets es
+        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+        JOIN exercises e ON e.id = tse.exercise_id
+        JOIN personal_records pr ON pr.exercise_id = e.id
+            AND pr.weight = es.weight
+            AND pr.reps = es.reps
+        WHERE es.timestamp >= ? AND es.timestamp < ?
+        "#,
+    )
+    .bind(monday.format("%Y-%m-%d").to_string())
+    .bind(sunday_end.format("%Y-%m-%d").to_string())
+    .fetch_all(pool)
+    .await?;
+
+    println!(
+        "{} {} → {}",
+        "Week report:".cyan().bold(),
+        monday.format("%Y-%m-%d"),
+        (sunday_end - Days::new(1)).format("%Y-%m-%d")
+    );
+    println!();
+
+    let (mut week_tonnage, mut week_sets, mut week_reps) = (0.0_f64, 0_i64, 0_i64);
+    for (i, label) in WEEKDAYS.iter().enumerate() {
+        let day = monday + Days::new(i as u64);
+        let key = day.format("%Y-%m-%d").to_string();
+        let (tonnage, sets, reps) = by_day.get(&key).copied().unwrap_or((0.0, 0, 0));
+        week_tonnage += tonnage;
+        week_sets += sets;
+        week_reps += reps;
+
+        println!(
+            "  {:<9} {:>3} sets  {:>5} reps  {:>8.0} {}",
+            label, sets, reps, unit.from_kg(tonnage as f32), unit.suffix()
+        );
+    }
+
+    println!();
+    println!(
+        "{}: {} sets  {} reps  {:.0} {}",
+        "Week total".cyan().bold(),
+        week_sets,
+        week_reps,
+        unit.from_kg(week_tonnage as f32),
+        unit.suffix()
+    );
+
+    if !prs.is_empty() {
+        println!();
+        println!("{}", "PRs set this week:".cyan().bold());
+        for (name, date, weight, reps) in prs {
+            println!(
+                "  {} — {}{} x {} on {}",
+                name.bold(),
+                unit.from_kg(weight),
+                unit.suffix(),
+                reps,
+                date
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Glyphs `show_training_calendar` shades each day cell with, from rest
+/// (0) through peak (4) tonnage quartile.
+const VOLUME_GLYPHS: [char; 5] = [' ', '·', '▪', '▫', '■'];
+
+/// Interpolated percentile of a pre-sorted slice (`p` in `0.0..=1.0`),
+/// e.g. `percentile(sorted, 0.75)` for the 75th percentile.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+/// Maps a day's tonnage to a [`VOLUME_GLYPHS`] entry: rest days are blank,
+/// trained days are bucketed against the 25th/50th/75th percentile of
+/// every nonzero day in the window rather than a fixed scale, so a light
+/// training block and a heavy one each spread across the full glyph range.
+fn glyph_for(tonnage: f64, thresholds: [f64; 3]) -> char {
+    if tonnage <= 0.0 {
+        VOLUME_GLYPHS[0]
+    } else if tonnage <= thresholds[0] {
+        VOLUME_GLYPHS[1]
+    } else if tonnage <= thresholds[1] {
+        VOLUME_GLYPHS[2]
+    } else if tonnage <= thresholds[2] {
+        VOLUME_GLYPHS[3]
+    } else {
+        VOLUME_GLYPHS[4]
+    }
+}
+
+/// Month-by-month ASCII calendar grid shading each day by that day's
+/// training tonnage with quartile-keyed glyphs, Monday-first like the
+/// rest of `status`'s weekly bucketing. Complements the line graphs by
+/// making training consistency and gaps immediately visible across a
+/// block, and unlike `calendar --heatmap` needs no truecolor terminal.
+pub async fn show_training_calendar(pool: &SqlitePool, weeks: u32) -> Result<()> {
+    let today = Local::now().date_naive();
+    let start = today - Days::new(weeks as u64 * 7);
+
+    let rows: Vec<(String, f64)> = sqlx::query_as(
+        r#"
+        SELECT
+            date(ts.start_time) as day,
+            CAST(COALESCE(SUM(CAST(es.weight AS REAL) * CAST(es.reps AS INTEGER)), 0) AS REAL) as tonnage
+        FROM training_sessions ts
+        JOIN training_session_exercises tse ON tse.training_session_id = ts.id
+        JOIN exercise_sets es ON es.session_exercise_id = tse.id
+        WHERE ts.start_time >= ? AND ts.end_time IS NOT NULL
+        GROUP BY day
+        "#,
+    )
+    .bind(start.format("%Y-%m-%d").to_string())
+    .fetch_all(pool)
+    .await?;
+
+    let mut by_day: HashMap<chrono::NaiveDate, f64> = HashMap::new();
+    for (day, tonnage) in rows {
+        if let Ok(d) = chrono::NaiveDate::parse_from_str(&day, "%Y-%m-%d") {
+            by_day.insert(d, tonnage);
+        }
+    }
+
+    let mut nonzero: Vec<f64> = by_day.values().copied().filter(|t| *t > 0.0).collect();
+    nonzero.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let thresholds = if nonzero.is_empty() {
+        [0.0, 0.0, 0.0]
+    } else {
+        [percentile(&nonzero, 0.25), percentile(&nonzero, 0.5), percentile(&nonzero, 0.75)]
+    };
+
+    println!("{} ({} weeks)", "Training Calendar".cyan().bold(), weeks);
+    println!();
+
+    let mut month_cursor = start.with_day(1).unwrap();
+    let last_month = today.with_day(1).unwrap();
+    while month_cursor <= last_month {
+        render_calendar_month(month_cursor, &by_day, thresholds, start, today);
+        month_cursor = if month_cursor.month() == 12 {
+            chrono::NaiveDate::from_ymd_opt(month_cursor.year() + 1, 1, 1).unwrap()
+        } else {
+            chrono::NaiveDate::from_ymd_opt(month_cursor.year(), month_cursor.month() + 1, 1).unwrap()
+        };
+    }
+
+    println!(
+        "{} {} rest   {} light   {} moderate   {} heavy   {} peak",
+        "Legend:".cyan().bold(),
+        VOLUME_GLYPHS[0],
+        VOLUME_GLYPHS[1],
+        VOLUME_GLYPHS[2],
+        VOLUME_GLYPHS[3],
+        VOLUME_GLYPHS[4],
+    );
+
+    Ok(())
+}
+
+/// Renders one month of [`show_training_calendar`]'s grid: a
+/// "Month YYYY" header, a Monday-first weekday row, then one row per
+/// week with each day shaded by [`glyph_for`]. Days outside
+/// `[range_start, range_end]` are left blank even if in-month.
+fn render_calendar_month(
+    month_start: chrono::NaiveDate,
+    by_day: &HashMap<chrono::NaiveDate, f64>,
+    thresholds: [f64; 3],
+    range_start: chrono::NaiveDate,
+    range_end: chrono::NaiveDate,
+) {
+    const MONTH_NAMES: [&str; 12] = [
+        "January", "February", "March", "April", "May", "June", "July", "August", "September", "October",
+        "November", "December",
+    ];
+
+    let next_month = if month_start.month() == 12 {
+        chrono::NaiveDate::from_ymd_opt(month_start.year() + 1, 1, 1).unwrap()
+    } else {
+        chrono::NaiveDate::from_ymd_opt(month_start.year(), month_start.month() + 1, 1).unwrap()
+    };
+    let days_in_month = next_month.signed_duration_since(month_start).num_days();
+
+    println!("{} {}", MONTH_NAMES[month_start.month0() as usize], month_start.year());
+    println!("Mo Tu We Th Fr Sa Su");
+
+    let lead_blanks = month_start.weekday().num_days_from_monday();
+    let mut line = "   ".repeat(lead_blanks as usize);
+    for offset in 0..days_in_month {
+        let day = month_start + Days::new(offset as u64);
+        let glyph = if day < range_start || day > range_end {
+            ' '
+        } else {
+            glyph_for(by_day.get(&day).copied().unwrap_or(0.0), thresholds)
+        };
+        line.push_str(&format!("{:>2} ", glyph));
+        if (lead_blanks + offset as u32 + 1) % 7 == 0 {
+            println!("{}", line.trim_end());
+            line.clear();
+        }
+    }
+    if !line.is_empty() {
+        println!("{}", line.trim_end());
+    }
+    println!();
+}
+
+/// Five-step dark-to-bright gradient `show_volume_heatmap` shades each
+/// day's background with, keyed by intensity level 0 (no training)
+/// through 4 — same ramp as `calendar --heatmap` and `heatmap`.
+const HEATMAP_GRADIENT: [(u8, u8, u8); 5] =
+    [(22, 27, 34), (14, 68, 41), (0, 109, 50), (38, 166, 65), (57, 211, 83)];
+
+/// `status --heatmap`: the same per-week window `show_global_progression`
+/// plots as a trend line, instead laid out as a week-by-week grid (one
+/// column per weekday, one row per week) with each day's cell shaded via
+/// an ANSI background block keyed to that day's tonnage quartile. Makes
+/// training consistency and rest-day patterns visible at a glance in a way
+/// a single line can't.
+pub async fn show_volume_heatmap(pool: &SqlitePool, weeks: u32, cfg: &Config) -> Result<()> {
+    let unit = cfg.weight_unit();
+    let today = Local::now().date_naive();
+    let this_monday = today - Days::new(today.weekday().num_days_from_monday() as u64);
+    let start = this_monday - Days::new((weeks as u64 - 1) * 7);
+
+    // Bucket sets by actual training day rather than the weekly bucket
+    // `show_global_progression` uses for its line graph.
+    let rows: Vec<(String, f64)> = sqlx::query_as(
+        r#"
+        SELECT
+            date(es.timestamp) as day,
+            CAST(COALESCE(SUM(CAST(es.weight AS REAL) * CAST(es.reps AS INTEGER)), 0) AS REAL) as tonnage
+        FROM exercise_sets es
+        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+        JOIN training_sessions ts ON ts.id = tse.training_session_id
+        WHERE es.timestamp >= datetime('now', '-' || ? || ' days')
+        AND ts.end_time IS NOT NULL
+        GROUP BY day
+        "#,
+    )
+    .bind(weeks * 7)
+    .fetch_all(pool)
+    .await?;
+
+    let mut by_day: HashMap<chrono::NaiveDate, f64> = HashMap::new();
+    for (day, tonnage) in rows {
+        if let Ok(d) = chrono::NaiveDate::parse_from_str(&day, "%Y-%m-%d") {
+            by_day.insert(d, tonnage);
+        }
+    }
+
+    let mut nonzero: Vec<f64> = by_day.values().copied().filter(|t| *t > 0.0).collect();
+    nonzero.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let thresholds = if nonzero.is_empty() {
+        [0.0, 0.0, 0.0]
+    } else {
+        [percentile(&nonzero, 0.25), percentile(&nonzero, 0.5), percentile(&nonzero, 0.75)]
+    };
+    let level_for = |tonnage: f64| -> usize {
+        if tonnage <= 0.0 {
+            0
+        } else if tonnage <= thresholds[0] {
+            1
+        } else if tonnage <= thresholds[1] {
+            2
+        } else if tonnage <= thresholds[2] {
+            3
+        } else {
+            4
+        }
+    };
+
+    println!("{} ({} weeks)", "Training Volume Heatmap".cyan().bold(), weeks);
+    println!();
+    println!("    Mo Tu We Th Fr Sa Su");
+
+    for week in 0..weeks {
+        let monday = start + Days::new(week as u64 * 7);
+        print!("{} ", monday.format("%b %d").to_string().dimmed());
+        for offset in 0..7 {
+            let day = monday + Days::new(offset);
+            if day > today {
+                print!("   ");
+                continue;
+            }
+            let tonnage = by_day.get(&day).copied().unwrap_or(0.0);
+            let (r, g, b) = HEATMAP_GRADIENT[level_for(tonnage)];
+            print!("{} ", "  ".on_truecolor(r, g, b));
+        }
+        println!();
+    }
+
+    println!();
+    let max_tonnage = nonzero.last().copied().unwrap_or(0.0);
+    println!(
+        "{} rest → peak ({:.0} {})",
+        "Legend:".cyan().bold(),
+        unit.from_kg(max_tonnage as f32),
+        unit.suffix()
+    );
+
+    Ok(())
+}
+
+/// Total tonnage/sets/distinct sessions logged on or after `since`, for
+/// [`print_rollup_header`]'s day/week/month windows.
+async fn window_totals(pool: &SqlitePool, since: chrono::NaiveDate) -> Result<(f64, i64, i64)> {
+    let row: (f64, i64, i64) = sqlx::query_as(
+        r#"
+        SELECT
+            COALESCE(SUM(CAST(es.weight AS REAL) * CAST(es.reps AS INTEGER)), 0) as tonnage,
+            CAST(COUNT(*) AS INTEGER) as sets,
+            CAST(COUNT(DISTINCT ts.id) AS INTEGER) as sessions
+        FROM exercise_sets es
+        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+        JOIN training_sessions ts ON ts.id = tse.training_session_id
+        WHERE date(es.timestamp) >= date(?)
+        AND ts.end_time IS NOT NULL
+        "#,
+    )
+    .bind(since.format("%Y-%m-%d").to_string())
+    .fetch_one(pool)
+    .await?;
+    Ok(row)
+}
+
+/// "Where am I right now": a compact today/this-week/this-month totals
+/// block prepended to `handle_status`'s usual report, borrowed from
+/// bartib's status view — an immediate snapshot so users don't have to
+/// read the trend graph just to see whether they've trained today.
+async fn print_rollup_header(pool: &SqlitePool, cfg: &Config) -> Result<()> {
+    let unit = cfg.weight_unit();
+    let today = Local::now().date_naive();
+    let week_start = today - Days::new(today.weekday().num_days_from_monday() as u64);
+    let month_start = today.with_day(1).unwrap();
+
+    let (day_tonnage, day_sets, day_sessions) = window_totals(pool, today).await?;
+    let (week_tonnage, week_sets, week_sessions) = window_totals(pool, week_start).await?;
+    let (month_tonnage, month_sets, month_sessions) = window_totals(pool, month_start).await?;
+
+    let row = |label: &str, tonnage: f64, sets: i64, sessions: i64| {
+        println!(
+            "  {:<11} {:>7.0} {} · {:>3} sets · {} session(s)",
+            label.bold(),
+            unit.from_kg(tonnage as f32),
+            unit.suffix(),
+            sets,
+            sessions
+        );
+    };
+
+    println!("{}", "Right now:".cyan().bold());
+    row("Today:", day_tonnage, day_sets, day_sessions);
+    row("This week:", week_tonnage, week_sets, week_sessions);
+    row("This month:", month_tonnage, month_sets, month_sessions);
+    println!();
+
+    Ok(())
+}
+
+pub async fn handle_status(
+    muscle: Option<String>,
+    weeks: u32,
+    graph: bool,
+    week: Option<i32>,
+    forecast: Option<u32>,
+    outlier_threshold: f64,
+    granularity: Granularity,
+    stat: Stat,
+    offset: i32,
+    fmt: OutputFmt,
+    pool: &SqlitePool,
+    cfg: &Config,
+) -> Result<()> {
+    if let Some(offset) = week {
+        return show_week_report(pool, offset, cfg).await;
+    }
+
+    if fmt.format == OutputFormat::Pretty {
+        print_rollup_header(pool, cfg).await?;
+    }
+
     match muscle {
-        Some(muscle_name) => show_muscle_progression(pool, &muscle_name, weeks, graph).await,
-        None => show_global_progression(pool, weeks, graph).await,
+        Some(muscle_name) => {
+            show_muscle_progression(
+                pool,
+                &muscle_name,
+                weeks,
+                graph,
+                forecast,
+                outlier_threshold,
+                granularity,
+                stat,
+                offset,
+                fmt,
+                cfg,
+            )
+            .await
+        }
+        None => {
+            show_global_progression(
+                pool,
+                weeks,
+                graph,
+                forecast,
+                outlier_threshold,
+                granularity,
+                stat,
+                offset,
+                fmt,
+                cfg,
+            )
+            .await
+        }
     }
-} 
\ No newline at end of file
+}
+
+/// Fits a personalized Epley-style 1RM coefficient for `exercise` from its
+/// logged sets via [`crate::types::fit_personalized_k`] and prints it
+/// alongside what it would estimate for the exercise's current best set.
+pub async fn fit_1rm_report(pool: &SqlitePool, exercise: Option<String>, cfg: &Config) -> Result<()> {
+    let Some(exercise) = exercise else {
+        println!("{} --fit-1rm requires --exercise NAME", "error:".red().bold());
+        return Ok(());
+    };
+
+    let Some(exercise_id) = crate::commands::exercise::resolve_exercise_id(pool, &exercise).await? else {
+        println!("{} no exercise matches `{}`", "warning:".yellow().bold(), exercise);
+        return Ok(());
+    };
+
+    let rows: Vec<(f64, i32)> = sqlx::query_as(
+        "SELECT weight, reps FROM exercise_sets
+         WHERE session_exercise_id IN (
+             SELECT id FROM training_session_exercises WHERE exercise_id = ?
+         )
+         AND weight > 0 AND ignore_for_one_rm = 0",
+    )
+    .bind(&exercise_id)
+    .fetch_all(pool)
+    .await?;
+
+    let sets: Vec<(f32, i32)> = rows.iter().map(|(w, r)| (*w as f32, *r)).collect();
+    let k = crate::types::fit_personalized_k(&sets);
+
+    println!("{} for `{}`", "Personalized 1RM fit".cyan().bold(), exercise);
+    println!("  {} k = {:.2}  (Epley is k = 30.00)", "fitted:".green().bold(), k);
+    println!("  {} {} set(s) used", "data:".dimmed(), sets.len());
+
+    if let Some((best_w, best_r)) = sets
+        .iter()
+        .map(|(w, r)| (*w, *r, crate::types::estimate_with_k(*w, *r, k)))
+        .max_by(|a, b| a.2.partial_cmp(&b.2).unwrap())
+        .map(|(w, r, _)| (w, r))
+    {
+        let estimate = crate::types::estimate_with_k(best_w, best_r, k);
+        let unit = cfg.weight_unit();
+        println!(
+            "  {} {:.1}{} x {} -> {:.1}{} personalized 1RM",
+            "best set:".dimmed(),
+            unit.from_kg(best_w),
+            unit.suffix(),
+            best_r,
+            unit.from_kg(estimate),
+            unit.suffix()
+        );
+    }
+
+    Ok(())
+}
+
+/// True PR-progression series for `exercise`, modeled on speedrun-style
+/// records-over-time tracking: walks every logged set in timestamp order,
+/// Epley-estimates its 1RM, and keeps a running best, emitting a point
+/// only when a set strictly exceeds it — so the plotted line is
+/// monotonically increasing and every point is a genuine record. Ties
+/// break to the earliest timestamp for free, since sets are visited in
+/// timestamp order and only a strict improvement is recorded. Warmup
+/// (`ignore_for_one_rm`) and zero-weight sets are excluded; this repo has
+/// no time-based set tracking, so bodyweight-without-added-weight sets
+/// fall out the same way zero-weight sets do.
+pub async fn show_pr_progression(pool: &SqlitePool, exercise: Option<String>, cfg: &Config) -> Result<()> {
+    let Some(exercise) = exercise else {
+        println!("{} --pr-history requires --exercise NAME", "error:".red().bold());
+        return Ok(());
+    };
+
+    let Some(exercise_id) = crate::commands::exercise::resolve_exercise_id(pool, &exercise).await? else {
+        println!("{} no exercise matches `{}`", "warning:".yellow().bold(), exercise);
+        return Ok(());
+    };
+    let unit = cfg.weight_unit();
+
+    let rows: Vec<(String, f64, i64)> = sqlx::query_as(
+        r#"
+        SELECT date(es.timestamp), CAST(es.weight AS REAL), CAST(es.reps AS INTEGER)
+        FROM exercise_sets es
+        JOIN training_session_exercises tse ON tse.id = es.session_exercise_id
+        WHERE tse.exercise_id = ?
+        AND es.weight > 0
+        AND es.ignore_for_one_rm = 0
+        ORDER BY es.timestamp ASC
+        "#,
+    )
+    .bind(&exercise_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut best = 0.0_f64;
+    let mut records: Vec<(DateTime<Utc>, f32)> = Vec::new();
+    let mut deltas: Vec<f64> = Vec::new();
+    for (day, weight, reps) in rows {
+        let e1rm = weight * (1.0 + reps as f64 / 30.0);
+        if e1rm <= best {
+            continue;
+        }
+        let Ok(naive_date) = chrono::NaiveDate::parse_from_str(&day, "%Y-%m-%d") else {
+            continue;
+        };
+        let Some(naive_datetime) = naive_date.and_hms_opt(0, 0, 0) else {
+            continue;
+        };
+        deltas.push(e1rm - best);
+        best = e1rm;
+        records.push((naive_datetime.and_utc(), e1rm as f32));
+    }
+
+    if records.is_empty() {
+        println!("{} no valid PR records found for `{}`", "warning:".yellow().bold(), exercise);
+        return Ok(());
+    }
+
+    println!("{} {} ({} records)", "PR Progression:".cyan().bold(), exercise.bold(), records.len());
+    println!();
+
+    let (term_width, term_height) = term_size::dimensions().unwrap_or((80, 24));
+    let width = (term_width / 2).min(60);
+    let height = (term_height / 2).min(15);
+    let graph = create_ascii_graph(&records, &[], &[], width, height, "Estimated 1RM PRs", "%Y-%m-%d");
+    for line in graph {
+        println!("{}", line);
+    }
+
+    println!();
+    for ((timestamp, e1rm), delta) in records.iter().zip(deltas.iter()) {
+        println!(
+            "  {} {:.1}{} {}",
+            timestamp.format("%Y-%m-%d").to_string().dimmed(),
+            unit.from_kg(*e1rm),
+            unit.suffix(),
+            format!("(+{:.1}{})", unit.from_kg(*delta as f32), unit.suffix()).green()
+        );
+    }
+
+    Ok(())
+}
+#[cfg(test)]
+mod progression_provider_tests {
+    use super::*;
+
+    /// Deterministic stand-in for `SqliteProgressionProvider` — lets the
+    /// bucketing/outlier-filtering/graph pipeline in `build_global_series`/
+    /// `build_muscle_series` be exercised without a live database.
+    struct MockProvider {
+        tonnage: Vec<(String, f64)>,
+        muscle_sets: Vec<(String, i64)>,
+        pr_sets: Vec<(String, String, f64)>,
+        baseline: Vec<(String, f64)>,
+    }
+
+    #[async_trait::async_trait]
+    impl ProgressionProvider for MockProvider {
+        async fn tonnage_rows(&self, _bucket: &str, _window_start: &str, _window_end: &str) -> Result<Vec<(String, f64)>> {
+            Ok(self.tonnage.clone())
+        }
+
+        async fn muscle_weekly_sets(
+            &self,
+            _bucket: &str,
+            _muscle: &str,
+            _window_start: &str,
+            _window_end: &str,
+        ) -> Result<Vec<(String, i64)>> {
+            Ok(self.muscle_sets.clone())
+        }
+
+        async fn pr_rows(
+            &self,
+            _bucket: &str,
+            _window_start: &str,
+            _window_end: &str,
+            _muscle: Option<&str>,
+        ) -> Result<Vec<(String, String, f64)>> {
+            Ok(self.pr_sets.clone())
+        }
+
+        async fn baseline_1rm(&self, _window_start: &str, _muscle: Option<&str>) -> Result<Vec<(String, f64)>> {
+            Ok(self.baseline.clone())
+        }
+    }
+
+    fn graph_points(series: &[(String, f32)]) -> Vec<(DateTime<Utc>, f32)> {
+        series
+            .iter()
+            .filter_map(|(week, value)| {
+                let naive_date = chrono::NaiveDate::parse_from_str(week, "%Y-%m-%d").ok()?;
+                Some((naive_date.and_hms_opt(0, 0, 0)?.and_utc(), *value))
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn build_global_series_collapses_sets_and_tracks_pr_improvement() {
+        let provider = MockProvider {
+            tonnage: vec![
+                ("2024-01-01".to_string(), 1000.0),
+                ("2024-01-01".to_string(), 1200.0),
+                ("2024-01-08".to_string(), 1500.0),
+            ],
+            muscle_sets: vec![],
+            pr_sets: vec![("2024-01-08".to_string(), "bench".to_string(), 110.0)],
+            baseline: vec![("bench".to_string(), 100.0)],
+        };
+
+        let series = build_global_series(&provider, "date(es.timestamp)", "2024-01-01", "2024-01-15", 3.5, Stat::Mean)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            series.tonnage_data,
+            vec![("2024-01-01".to_string(), 1100.0), ("2024-01-08".to_string(), 1500.0)]
+        );
+        assert!(series.tonnage_outliers.is_empty());
+        assert_eq!(series.pr_progression_data, vec![("2024-01-08".to_string(), 10.0)]);
+
+        let width_points = graph_points(
+            &series
+                .tonnage_data
+                .iter()
+                .map(|(week, tonnage)| (week.clone(), *tonnage as f32))
+                .collect::<Vec<_>>(),
+        );
+        let lines = create_ascii_graph(&width_points, &[], &[], 40, 10, "Weekly Tonnage", "%Y-%m-%d");
+        assert!(!lines.is_empty());
+        assert!(lines.iter().any(|line| line.contains("Weekly Tonnage")));
+    }
+
+    #[tokio::test]
+    async fn build_muscle_series_reports_weekly_set_counts_and_pr_improvement() {
+        let provider = MockProvider {
+            tonnage: vec![],
+            muscle_sets: vec![("2024-01-01".to_string(), 10), ("2024-01-08".to_string(), 12)],
+            pr_sets: vec![("2024-01-08".to_string(), "bench".to_string(), 110.0)],
+            baseline: vec![("bench".to_string(), 100.0)],
+        };
+
+        let series =
+            build_muscle_series(&provider, "chest", "date(es.timestamp)", "2024-01-01", "2024-01-15", 3.5, Stat::Mean)
+                .await
+                .unwrap();
+
+        assert_eq!(series.volume_data, vec![("2024-01-01".to_string(), 10), ("2024-01-08".to_string(), 12)]);
+        assert!(series.volume_outliers.is_empty());
+        assert_eq!(series.pr_progression_data, vec![("2024-01-08".to_string(), 10.0)]);
+
+        let lines = create_ascii_graph(&graph_points(&series.pr_progression_data), &[], &[], 40, 10, "chest PRs", "%Y-%m-%d");
+        assert!(!lines.is_empty());
+    }
+}
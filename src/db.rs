@@ -1,8 +1,8 @@
-use std::str::FromStr;
+use std::{future::Future, pin::Pin, str::FromStr};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use sqlx::{
-    SqlitePool,
+    Sqlite, SqlitePool, Transaction,
     sqlite::{SqliteConnectOptions, SqlitePoolOptions},
 };
 
@@ -16,6 +16,60 @@ pub async fn open(path: &str) -> Result<DB> {
         .connect_with(opts)
         .await?;
 
+    // `sqlx::migrate!()` brings the schema (tables/columns) up to date from
+    // `migrations/*.sql`. `run_app_migrations` is a second, narrower layer on
+    // top of that for *data* migrations — backfills and derived rows that
+    // depend on app logic rather than plain DDL — tracked in their own
+    // `meta.database_version` row instead of sqlx's `_sqlx_migrations`.
     sqlx::migrate!().run(&pool).await?;
+    run_app_migrations(&pool).await?;
     Ok(pool)
 }
+
+/// Bumped whenever an [`APP_MIGRATIONS`] entry is added. A database whose
+/// stored `database_version` exceeds this was created by a newer build.
+const CURRENT_APP_VERSION: i64 = 1;
+
+type AppMigrationFn = for<'c> fn(&'c mut Transaction<'_, Sqlite>) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'c>>;
+
+/// v0 -> v1: establishes the meta table itself (already created by the
+/// `meta.sql` schema migration); no data to backfill yet.
+fn migrate_to_v1(_tx: &mut Transaction<'_, Sqlite>) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+    Box::pin(async { Ok(()) })
+}
+
+/// Ordered data migrations, one per version bump. Index 0 brings a database
+/// from version 0 to version 1, and so on — `run_app_migrations` slices from
+/// the stored version and runs the rest in order.
+const APP_MIGRATIONS: &[AppMigrationFn] = &[migrate_to_v1];
+
+/// Applies any [`APP_MIGRATIONS`] the database hasn't seen yet, each in its
+/// own transaction, bumping `meta.database_version` as it goes. Refuses to
+/// run at all if the stored version is ahead of what this binary knows —
+/// that means the database was last touched by a newer build, and blindly
+/// continuing could corrupt data this binary doesn't understand.
+async fn run_app_migrations(pool: &SqlitePool) -> Result<()> {
+    let stored: Option<String> = sqlx::query_scalar("SELECT value FROM meta WHERE key = 'database_version'")
+        .fetch_optional(pool)
+        .await?;
+    let mut version: i64 = stored.as_deref().unwrap_or("0").parse().unwrap_or(0);
+
+    if version > CURRENT_APP_VERSION {
+        return Err(anyhow!(
+            "database schema version {version} is newer than this binary understands (up to {CURRENT_APP_VERSION}) — refusing to run to avoid data loss; upgrade lazaro first"
+        ));
+    }
+
+    for migration in &APP_MIGRATIONS[version as usize..] {
+        let mut tx = pool.begin().await?;
+        migration(&mut tx).await?;
+        version += 1;
+        sqlx::query("INSERT INTO meta (key, value) VALUES ('database_version', ?) ON CONFLICT(key) DO UPDATE SET value = excluded.value")
+            .bind(version.to_string())
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+    }
+
+    Ok(())
+}
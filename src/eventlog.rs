@@ -0,0 +1,76 @@
+//! Append-only, soft-delete session event log.
+//!
+//! Alongside the SQLite store this keeps one JSON record per line under the
+//! config dir for every session mutation (set logged, set edited, exercise
+//! swapped, note added, ...). A correction or deletion is expressed by
+//! appending a *new* record that supersedes an earlier `id` rather than
+//! rewriting a line in place, so the log stays a durable, concatenable audit
+//! trail — and, eventually, a sync primitive: two machines can reconcile by
+//! concatenating their logs and replaying.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Record {
+    pub id: String,
+    pub timestamp: String,
+    pub kind: String,
+    pub data: serde_json::Value,
+    #[serde(default)]
+    pub tombstone: bool,
+}
+
+fn log_path() -> Result<PathBuf> {
+    Ok(dirs::config_dir().context("no config dir")?.join("lazarus").join("events.log"))
+}
+
+/// Appends a record describing a session mutation. Pass a fresh uuid in
+/// `record_id` for a brand-new fact, or an earlier record's id to correct or
+/// (with `tombstone = true`) delete it.
+pub fn append(record_id: &str, kind: &str, data: serde_json::Value, tombstone: bool) -> Result<()> {
+    let path = log_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let record = Record {
+        id: record_id.to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        kind: kind.to_string(),
+        data,
+        tombstone,
+    };
+
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(&record)?)?;
+    Ok(())
+}
+
+/// Folds the log into current state: for each `id`, the last record wins; a
+/// tombstone record removes that id entirely. Returned in chronological order.
+pub fn replay() -> Result<Vec<Record>> {
+    let path = log_path()?;
+    let Ok(file) = File::open(&path) else {
+        return Ok(Vec::new());
+    };
+
+    let mut latest: std::collections::HashMap<String, Record> = std::collections::HashMap::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: Record = serde_json::from_str(&line)?;
+        latest.insert(record.id.clone(), record);
+    }
+
+    let mut records: Vec<Record> = latest.into_values().filter(|r| !r.tombstone).collect();
+    records.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    Ok(records)
+}
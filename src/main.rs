@@ -9,35 +9,115 @@ use types::{Config, OutputFmt};
 mod cli;
 mod db;
 mod commands;
+mod eventlog;
+mod preset;
+mod profile;
+mod rating;
+mod resolve;
+mod scripting;
 mod types;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let config_path = dirs::config_dir().context("no config dir")?.join("lazarus").join("config");
     let cfg = Config::load(&config_path)?;
-    let json_default = cfg.json_default();
     let alias_map = cfg.aliases();
 
     let new_args = rewrite_args(&alias_map);
-    
-    let cli = Cli::parse_from(new_args);
 
-    let fmt = OutputFmt {
-        json: cli.json || json_default,
-    };
+    let cli = Cli::parse_from(new_args.clone());
+
+    let format = cli
+        .format
+        .unwrap_or_else(|| if cli.json { types::OutputFormat::Json } else { cfg.output_format() });
+    let fmt = OutputFmt { format };
     
     let db_path = "./lazarus.db";
     assert!(!db_path.is_empty(), "database path must not be empty");
     
     let pool = open(&db_path).await?;
 
-    match cli.cmd {
-        Commands::Session(cmd) => commands::session::handle(cmd, &pool).await?,
-        Commands::Exercise(cmd) => commands::exercise::handle(cmd, &pool, fmt).await?,
-        Commands::Config(cmd) => commands::config::handle(cmd, cfg, config_path).await?,
-        Commands::Program(cmd) => commands::program::handle(cmd, &pool, fmt).await?,
-        Commands::Calendar { year, month } => commands::calendar::handle(&pool, year, month).await?,
-        Commands::Db(cmd) => commands::db::handle(cmd, &pool).await?
+    // Record this invocation's canonical (post-alias) argv into the
+    // in-progress macro, if any. Macro meta-commands themselves are never
+    // recorded, so `macro stop`/`macro run ...` can't record themselves.
+    if !matches!(cli.cmd, Commands::Macro(_)) {
+        if let Some(name) = commands::macro_::active_recording(&pool).await? {
+            commands::macro_::append_step(&pool, &name, &new_args[1..]).await?;
+        }
+    }
+
+    dispatch(cli.cmd, &pool, fmt, &cfg, &config_path, cli.profile).await
+}
+
+/// Runs a single already-parsed command. Pulled out of `main` so the macro
+/// subsystem can replay recorded steps through the exact same dispatch path.
+pub(crate) async fn dispatch(
+    cmd: Commands,
+    pool: &sqlx::SqlitePool,
+    fmt: OutputFmt,
+    cfg: &Config,
+    config_path: &std::path::Path,
+    profile: bool,
+) -> Result<()> {
+    match cmd {
+        Commands::Session(cmd) => commands::session::handle(cmd, pool, fmt, cfg).await?,
+        Commands::Exercise(cmd) => commands::exercise::handle(cmd, pool, fmt, cfg).await?,
+        Commands::Config(cmd) => commands::config::handle(cmd, cfg.clone(), config_path.to_path_buf()).await?,
+        Commands::Program(cmd) => commands::program::handle(cmd, pool, fmt, profile).await?,
+        Commands::Calendar { year, month, heatmap, metric, full_year, months, export, out, locale } => {
+            commands::calendar::handle(pool, year, month, heatmap, metric, full_year, months, export, out, locale)
+                .await?
+        }
+        Commands::Status {
+            muscle,
+            weeks,
+            graph,
+            week,
+            fit_1rm,
+            calendar,
+            heatmap,
+            pr_history,
+            exercise,
+            forecast,
+            outlier_threshold,
+            granularity,
+            stat,
+            offset,
+        } => {
+            if fit_1rm {
+                commands::status::fit_1rm_report(pool, exercise, cfg).await?
+            } else if calendar {
+                commands::status::show_training_calendar(pool, weeks).await?
+            } else if heatmap {
+                commands::status::show_volume_heatmap(pool, weeks, cfg).await?
+            } else if pr_history {
+                commands::status::show_pr_progression(pool, exercise, cfg).await?
+            } else {
+                commands::status::handle_status(
+                    muscle,
+                    weeks,
+                    graph,
+                    week,
+                    forecast,
+                    outlier_threshold,
+                    granularity,
+                    stat,
+                    offset,
+                    fmt,
+                    pool,
+                    cfg,
+                )
+                .await?
+            }
+        }
+        Commands::Db(cmd) => commands::db::handle(cmd, pool).await?,
+        Commands::Measure(cmd) => commands::measure::handle(cmd, pool, fmt, cfg).await?,
+        Commands::Macro(cmd) => commands::macro_::handle(cmd, pool, fmt, cfg, config_path).await?,
+        Commands::Heatmap { weeks, ramp, by_sets } => {
+            commands::heatmap::handle(pool, weeks, ramp, by_sets).await?
+        }
+        Commands::Preset(cmd) => commands::preset::handle(cmd, pool, fmt).await?,
+        Commands::Search { query } => commands::search::handle(query, pool, fmt, cfg).await?,
     }
 
     Ok(())
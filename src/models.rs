@@ -10,6 +10,10 @@ pub struct TrainingSession {
     pub start_time: DateTime<Local>,
     pub end_time: Option<DateTime<Local>>,
     pub exercises: Vec<SessionExercise>,
+    /// Absent in session files saved before this field existed, which
+    /// `storage::migrate_session_value` treats as version 0 and upgrades.
+    #[serde(default)]
+    pub schema_version: u32,
 }
 
 /// Exercise-specific data within a session.
@@ -56,6 +60,10 @@ pub struct PersonalRecord {
 pub struct Program {
     pub name: String,
     pub exercises: Vec<ProgramExercise>,
+    /// Absent in program files saved before this field existed, which
+    /// `storage::migrate_program_value` treats as version 0 and upgrades.
+    #[serde(default)]
+    pub schema_version: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,11 +72,3 @@ pub struct ProgramExercise {
     pub sets: u32,
     pub reps: String,
 }
-
-#[derive(Debug, Clone, Copy)]
-pub enum OneRMFormula {
-    Epley,
-    Brzycki,
-    Lombardi,
-    OConner,
-}
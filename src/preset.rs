@@ -0,0 +1,93 @@
+//! Named, reusable set-scheme presets.
+//!
+//! A preset is a scheme like `"100:1,90:2,95:1"` — proportions (percent of
+//! the day's top set, or a flat RPE) paired with how many sets get that
+//! value — expanded on demand into the same per-set vectors `session show`
+//! already builds from a block's literal `target_rpe`/`target_rm_percent`
+//! columns. Nothing is snapshotted at `session start`: a block that
+//! references a preset always reflects the preset's current definition, so
+//! re-tuning a scheme here re-tunes every block that uses it.
+
+use anyhow::{Result, anyhow};
+use sqlx::SqlitePool;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PresetKind {
+    /// Values are a percentage of the day's top set.
+    Percent,
+    /// Values are a flat RPE.
+    Rpe,
+}
+
+impl PresetKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PresetKind::Percent => "percent",
+            PresetKind::Rpe => "rpe",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "percent" => Some(PresetKind::Percent),
+            "rpe" => Some(PresetKind::Rpe),
+            _ => None,
+        }
+    }
+}
+
+/// Parses `"100:1,90:2,95:1"` into the flat per-set values it describes,
+/// e.g. `[100.0, 90.0, 90.0, 95.0]`. Malformed groups are skipped rather than
+/// failing the whole scheme, same leniency `target_rpe`/`target_rm_percent`
+/// parsing already affords a malformed CSV entry.
+pub fn expand(scheme: &str) -> Vec<f32> {
+    let mut values = Vec::new();
+    for group in scheme.split(',') {
+        let group = group.trim();
+        let Some((value, count)) = group.split_once(':') else {
+            continue;
+        };
+        let (Ok(value), Ok(count)) = (value.trim().parse::<f32>(), count.trim().parse::<usize>()) else {
+            continue;
+        };
+        values.extend(std::iter::repeat(value).take(count));
+    }
+    values
+}
+
+/// Looks up `name` and, if found, returns its kind and expanded per-set
+/// values — ready to drop straight into `target_rpes`/`target_rms`.
+pub async fn expand_named(pool: &SqlitePool, name: &str) -> Result<Option<(PresetKind, Vec<f32>)>> {
+    let row: Option<(String, String)> =
+        sqlx::query_as("SELECT kind, scheme FROM set_scheme_presets WHERE name = ?")
+            .bind(name)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(match row {
+        Some((kind, scheme)) => {
+            let kind = PresetKind::parse(&kind).ok_or_else(|| anyhow!("unknown preset kind `{kind}`"))?;
+            Some((kind, expand(&scheme)))
+        }
+        None => None,
+    })
+}
+
+/// Same as [`expand_named`], but by `program_exercises.preset_id` rather than
+/// name — the lookup `session show` needs once an exercise row carries a
+/// `preset_id`.
+pub async fn expand_by_id(pool: &SqlitePool, preset_id: &str) -> Result<Option<(PresetKind, Vec<f32>)>> {
+    let row: Option<(String, String)> =
+        sqlx::query_as("SELECT kind, scheme FROM set_scheme_presets WHERE id = ?")
+            .bind(preset_id)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(match row {
+        Some((kind, scheme)) => {
+            let kind = PresetKind::parse(&kind).ok_or_else(|| anyhow!("unknown preset kind `{kind}`"))?;
+            Some((kind, expand(&scheme)))
+        }
+        None => None,
+    })
+}
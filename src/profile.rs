@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use sqlx::SqlitePool;
+
+/// Statement executed more than this many times under one profiled command
+/// gets flagged as a likely N+1 pattern.
+const REPEAT_WARN_THRESHOLD: u32 = 3;
+
+#[derive(Default)]
+struct Stats {
+    count: u32,
+    total: Duration,
+}
+
+/// Lightweight, built-in stand-in for a tracing/query-log backend, enabled
+/// via the hidden `--profile` flag. Wraps individual `sqlx` statements with
+/// an `EXPLAIN QUERY PLAN` dump and a wall-clock timer, and accumulates
+/// per-statement counts so a command's repeated (N+1) queries stand out in
+/// the end-of-run summary.
+pub struct QueryProfiler {
+    enabled: bool,
+    stmts: Mutex<HashMap<String, Stats>>,
+}
+
+impl QueryProfiler {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, stmts: Mutex::new(HashMap::new()) }
+    }
+
+    /// Runs `sql`'s query plan and `run` side by side, printing the plan's
+    /// `detail` rows and the wall-clock time of `run`. A plain pass-through
+    /// to `run` when profiling is off.
+    pub async fn record<T>(&self, pool: &SqlitePool, sql: &str, run: impl std::future::Future<Output = T>) -> T {
+        if !self.enabled {
+            return run.await;
+        }
+
+        if let Ok(rows) =
+            sqlx::query_as::<_, (i64, i64, i64, String)>(&format!("EXPLAIN QUERY PLAN {sql}")).fetch_all(pool).await
+        {
+            for (_, _, _, detail) in rows {
+                println!("  {} {}", "plan:".dimmed(), detail);
+            }
+        }
+
+        let start = Instant::now();
+        let result = run.await;
+        let elapsed = start.elapsed();
+        println!("  {} {:?}", "took:".dimmed(), elapsed);
+
+        let mut stmts = self.stmts.lock().expect("query profiler mutex poisoned");
+        let entry = stmts.entry(normalize(sql)).or_default();
+        entry.count += 1;
+        entry.total += elapsed;
+
+        result
+    }
+
+    /// Prints the end-of-command summary: total statements run, total time
+    /// spent in them, and a warning for each normalized statement executed
+    /// more than [`REPEAT_WARN_THRESHOLD`] times.
+    pub fn summarize(&self) {
+        if !self.enabled {
+            return;
+        }
+
+        let stmts = self.stmts.lock().expect("query profiler mutex poisoned");
+        let total_stmts: u32 = stmts.values().map(|s| s.count).sum();
+        let total_time: Duration = stmts.values().map(|s| s.total).sum();
+        println!("{} {} statements, {:?} total", "profile:".cyan().bold(), total_stmts, total_time);
+
+        for (sql, stats) in stmts.iter() {
+            if stats.count > REPEAT_WARN_THRESHOLD {
+                println!(
+                    "{} `{}` ran {} times — likely an N+1 query",
+                    "warning:".yellow().bold(),
+                    sql,
+                    stats.count
+                );
+            }
+        }
+    }
+}
+
+/// Collapses whitespace so the same statement issued with different
+/// indentation still groups under one accumulator key.
+fn normalize(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,179 @@
+//! Time-decayed per-exercise strength rating (Glicko-2-inspired).
+//!
+//! An all-time max 1RM never ages: a PR from eight months ago reads
+//! identically to one from last week. `exercise_ratings` instead tracks a
+//! smoothed rating `r` (kg), a deviation `RD` (how uncertain `r` is), and a
+//! volatility `sigma`, folded in via a one-dimensional Bayesian update
+//! whenever a session finishes. Between sessions `RD` inflates with elapsed
+//! time to model detraining, so a stale rating widens instead of just
+//! sitting there looking confident.
+
+use anyhow::Result;
+use sqlx::{Sqlite, Transaction};
+
+const INITIAL_RD: f64 = 100.0;
+const INITIAL_VOLATILITY: f64 = 0.06;
+/// `RD` never grows past this — a maximally uncertain estimate still means
+/// *something*.
+const RD_MAX: f64 = 150.0;
+/// kg of added uncertainty per day of inactivity.
+const DECAY_C: f64 = 0.6;
+/// Damps how hard one surprising observation can swing volatility.
+const TAU: f64 = 0.5;
+
+/// Above this deviation (kg) `session show` dims the rating instead of
+/// printing it plainly — it's too stale/uncertain to read as a hard number.
+pub const LOW_CONFIDENCE_RD: f64 = 40.0;
+
+/// Observation noise for a set predicting 1RM from `reps` reps: a near-max
+/// single only wobbles a little; a 15-rep set is a much noisier proxy.
+fn observation_noise(reps: i32) -> f64 {
+    5.0 + reps.max(1) as f64 * 1.5
+}
+
+/// Folds a session's best working set (`weight` kg × `reps`) into
+/// `exercise_id`'s rating. `weight` is the effective load — for bodyweight
+/// exercises that's bodyweight plus any added weight. Call only when that
+/// load is nonzero — callers skip sets whose estimated 1RM came out to 0
+/// (pure bodyweight with no bodyweight logged yet), same as the PR-update
+/// path that calls this from `SessionCmd::End`.
+pub async fn update_after_session(
+    tx: &mut Transaction<'_, Sqlite>,
+    exercise_id: &str,
+    weight: f32,
+    reps: i32,
+) -> Result<()> {
+    let observed = crate::types::OneRmFormula::Epley.estimate(weight, reps) as f64;
+
+    let existing: Option<(f64, f64, f64, f64)> = sqlx::query_as(
+        r#"
+        SELECT rating, deviation, volatility, julianday('now') - julianday(last_update)
+        FROM exercise_ratings
+        WHERE exercise_id = ?
+        "#,
+    )
+    .bind(exercise_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let (rating, rd_inflated, volatility) = match existing {
+        None => (observed, INITIAL_RD, INITIAL_VOLATILITY),
+        Some((r, rd, sigma, days_since)) => {
+            let rd_inflated = (rd.powi(2) + DECAY_C.powi(2) * days_since.max(0.0)).sqrt().min(RD_MAX);
+            (r, rd_inflated, sigma)
+        }
+    };
+
+    let s = observation_noise(reps);
+    let rd_sq = rd_inflated.powi(2);
+    let s_sq = s.powi(2);
+    let gain = rd_sq / (rd_sq + s_sq);
+    let rating_new = rating + gain * (observed - rating);
+    let rd_new = (1.0 / (1.0 / rd_sq + 1.0 / s_sq)).sqrt();
+
+    // A big miss relative to the expected noise nudges volatility up; an
+    // unsurprising one lets it relax back down, both damped by tau.
+    let surprise = (observed - rating).abs() / s;
+    let volatility_new = (volatility + TAU * (surprise - volatility)).max(0.01);
+
+    sqlx::query(
+        r#"
+        INSERT INTO exercise_ratings (exercise_id, rating, deviation, volatility, last_update)
+        VALUES (?, ?, ?, ?, datetime('now'))
+        ON CONFLICT(exercise_id) DO UPDATE SET
+            rating = excluded.rating,
+            deviation = excluded.deviation,
+            volatility = excluded.volatility,
+            last_update = excluded.last_update
+        "#,
+    )
+    .bind(exercise_id)
+    .bind(rating_new)
+    .bind(rd_new)
+    .bind(volatility_new)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// `(rating, deviation)` for `exercise_id` right now — `deviation` already
+/// inflated for elapsed time, i.e. what `session show` should display, not
+/// the raw stored row. `None` if the exercise has no rating yet. Generic over
+/// the executor so callers mid-transaction (checking the pre-update rating
+/// before [`update_after_session`] runs) and callers with just a pool (e.g.
+/// `session show`) can share the same query.
+pub async fn current<'e, E>(executor: E, exercise_id: &str) -> Result<Option<(f64, f64)>>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    let row: Option<(f64, f64, f64)> = sqlx::query_as(
+        r#"
+        SELECT rating, deviation, julianday('now') - julianday(last_update)
+        FROM exercise_ratings
+        WHERE exercise_id = ?
+        "#,
+    )
+    .bind(exercise_id)
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(row.map(|(rating, rd, days_since)| {
+        let rd_inflated = (rd.powi(2) + DECAY_C.powi(2) * days_since.max(0.0)).sqrt().min(RD_MAX);
+        (rating, rd_inflated)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn new_pool_with_exercise(exercise_id: &str) -> sqlx::SqlitePool {
+        let pool = crate::db::open(":memory:").await.unwrap();
+        sqlx::query(
+            "INSERT INTO exercises (id, name, primary_muscle, created_at) VALUES (?, ?, 'chest', datetime('now'))",
+        )
+        .bind(exercise_id)
+        .bind(format!("exercise-{exercise_id}"))
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn rd_grows_the_longer_an_exercise_goes_untrained() {
+        let pool = new_pool_with_exercise("bench").await;
+        let mut tx = pool.begin().await.unwrap();
+        update_after_session(&mut tx, "bench", 100.0, 5).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let (_, rd_fresh) = current(&pool, "bench").await.unwrap().unwrap();
+
+        sqlx::query("UPDATE exercise_ratings SET last_update = datetime('now', '-90 days') WHERE exercise_id = ?")
+            .bind("bench")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let (_, rd_stale) = current(&pool, "bench").await.unwrap().unwrap();
+
+        assert!(rd_stale > rd_fresh, "expected deviation to widen after 90 days untrained: {rd_fresh} -> {rd_stale}");
+    }
+
+    #[tokio::test]
+    async fn repeated_consistent_performances_tighten_the_deviation() {
+        let pool = new_pool_with_exercise("squat").await;
+
+        let mut prev_rd = INITIAL_RD;
+        for _ in 0..4 {
+            let mut tx = pool.begin().await.unwrap();
+            update_after_session(&mut tx, "squat", 100.0, 5).await.unwrap();
+            tx.commit().await.unwrap();
+
+            let (_, rd) = current(&pool, "squat").await.unwrap().unwrap();
+            assert!(rd < prev_rd, "expected deviation to keep tightening on consistent observations: {prev_rd} -> {rd}");
+            prev_rd = rd;
+        }
+    }
+}
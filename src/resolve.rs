@@ -0,0 +1,60 @@
+//! Prefix/fuzzy name resolution for CLI arguments that pick one row out of a
+//! short named list (programs, blocks) — lets `session start sq hyp` land on
+//! "Squat Program" / "Hypertrophy Block" without the user spelling either
+//! name out in full or memorizing its numeric index.
+
+/// One name a user might be trying to resolve `query` against.
+pub struct Candidate<T> {
+    pub name: String,
+    pub value: T,
+}
+
+pub enum Resolution<T> {
+    /// Exactly one candidate matched.
+    Found(T),
+    /// Nothing matched at all.
+    NotFound,
+    /// More than one candidate matched at the same stage — caller should
+    /// print the shortlist and ask the user to disambiguate instead of
+    /// guessing.
+    Ambiguous(Vec<String>),
+}
+
+/// True if every character of `query` appears in `candidate`, in order,
+/// case-insensitively — a forgiving "fuzzy subsequence" match (e.g. "hyp"
+/// matches "Hypertrophy Block").
+fn is_subsequence(query: &str, candidate: &str) -> bool {
+    let mut rest = candidate.chars();
+    query.chars().all(|qc| rest.any(|cc| cc.eq_ignore_ascii_case(&qc)))
+}
+
+/// Resolves `query` against `candidates` in cascading stages — exact name,
+/// then case-insensitive prefix, then fuzzy subsequence — each only
+/// consulted if the previous stage had no hits, so a prefix match never
+/// loses to a looser subsequence one.
+pub fn resolve<T: Clone>(candidates: &[Candidate<T>], query: &str) -> Resolution<T> {
+    if let Some(exact) = candidates.iter().find(|c| c.name == query) {
+        return Resolution::Found(exact.value.clone());
+    }
+
+    let lower_query = query.to_ascii_lowercase();
+    let prefix: Vec<&Candidate<T>> = candidates
+        .iter()
+        .filter(|c| c.name.to_ascii_lowercase().starts_with(&lower_query))
+        .collect();
+    match prefix.len() {
+        1 => return Resolution::Found(prefix[0].value.clone()),
+        n if n > 1 => return Resolution::Ambiguous(prefix.iter().map(|c| c.name.clone()).collect()),
+        _ => {}
+    }
+
+    let fuzzy: Vec<&Candidate<T>> = candidates
+        .iter()
+        .filter(|c| is_subsequence(&lower_query, &c.name.to_ascii_lowercase()))
+        .collect();
+    match fuzzy.len() {
+        0 => Resolution::NotFound,
+        1 => Resolution::Found(fuzzy[0].value.clone()),
+        _ => Resolution::Ambiguous(fuzzy.iter().map(|c| c.name.clone()).collect()),
+    }
+}
@@ -0,0 +1,71 @@
+//! Embedded scripting for program-exercise prescriptions.
+//!
+//! A program exercise can carry a Rhai script instead of (or in addition to)
+//! a literal `reps` string. The script is evaluated once per `session start`
+//! and must return an array — one entry per set — which gets stringified
+//! and joined the same way a literal `reps` column is, so every other code
+//! path (display, `session edit` target checks, ...) stays oblivious to
+//! whether a set's prescription came from a script or a TOML literal.
+
+use anyhow::{anyhow, Result};
+use rhai::{Array, Engine};
+
+/// Everything a prescription script is allowed to see. Deliberately a flat,
+/// copyable bag of facts rather than a live DB handle — scripts run
+/// synchronously and must not be able to reach outside their sandbox.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptContext {
+    /// Best known estimated 1RM for this exercise, in kg (0.0 if none yet).
+    pub e1rm: f64,
+    /// Previous session's logged sets for this exercise, most recent first:
+    /// `(weight_kg, reps)`.
+    pub last_sets: Vec<(f64, i64)>,
+    /// Current program week (mirrors `session start`'s `--week`/positional week).
+    pub week: i64,
+}
+
+/// Evaluates `script` against `ctx`, returning one stringified prescription
+/// per set. The script's top-level expression value must be a Rhai array;
+/// each element is rendered with `to_string()` the same way `reps.join(",")`
+/// renders a literal column, so callers can treat the two uniformly.
+pub fn eval_prescription(script: &str, ctx: &ScriptContext) -> Result<Vec<String>> {
+    let mut engine = Engine::new();
+    register_host_fns(&mut engine, ctx.clone());
+
+    let result: Array = engine
+        .eval::<Array>(script)
+        .map_err(|e| anyhow!("prescription script error: {e}"))?;
+
+    Ok(result.into_iter().map(|v| v.to_string()).collect())
+}
+
+fn register_host_fns(engine: &mut Engine, ctx: ScriptContext) {
+    let e1rm_ctx = ctx.clone();
+    engine.register_fn("e1rm", move || e1rm_ctx.e1rm);
+
+    let week_ctx = ctx.clone();
+    engine.register_fn("week", move || week_ctx.week);
+
+    let last_set_ctx = ctx.clone();
+    engine.register_fn("last_set", move |i: i64| -> rhai::Map {
+        let mut m = rhai::Map::new();
+        match last_set_ctx.last_sets.get((i - 1).max(0) as usize) {
+            Some((w, r)) => {
+                m.insert("weight".into(), (*w).into());
+                m.insert("reps".into(), (*r).into());
+            }
+            None => {
+                m.insert("weight".into(), (0.0_f64).into());
+                m.insert("reps".into(), (0_i64).into());
+            }
+        }
+        m
+    });
+
+    engine.register_fn("round_to", |x: f64, step: f64| -> f64 {
+        if step <= 0.0 {
+            return x;
+        }
+        (x / step).round() * step
+    });
+}
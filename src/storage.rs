@@ -10,6 +10,84 @@ use crate::{models, utils};
 const PROGRAMS_DIR: &str = "programs";
 const SESSIONS_DIR: &str = "sessions";
 
+/// Bump whenever `models::TrainingSession`'s shape changes in a way a plain
+/// `#[serde(default)]` can't express, and add a matching step to
+/// `SESSION_MIGRATIONS`.
+const CURRENT_SESSION_SCHEMA_VERSION: u32 = 1;
+
+/// One step per schema change, ordered by source version: `SESSION_MIGRATIONS[i]`
+/// takes a session at version `i` and upgrades it to version `i + 1`.
+type SessionMigration = fn(serde_json::Value) -> serde_json::Value;
+
+const SESSION_MIGRATIONS: &[SessionMigration] = &[
+    // v0 -> v1: `schema_version` itself postdates the very first session
+    // format; every other field added since then is optional and already
+    // backfilled by `#[serde(default)]`, so this step is an identity pass
+    // that exists purely to carry the version number forward.
+    |value| value,
+];
+
+/// Walks `value` through `SESSION_MIGRATIONS` up to
+/// `CURRENT_SESSION_SCHEMA_VERSION`, defaulting an absent `schema_version`
+/// to 0 (pre-versioning session files).
+fn migrate_session_value(mut value: serde_json::Value) -> serde_json::Value {
+    let version = value
+        .get("schema_version")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as usize;
+    let version = version.min(SESSION_MIGRATIONS.len());
+
+    for step in &SESSION_MIGRATIONS[version..] {
+        value = step(value);
+    }
+
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert(
+            "schema_version".to_string(),
+            serde_json::json!(CURRENT_SESSION_SCHEMA_VERSION),
+        );
+    }
+
+    value
+}
+
+/// Bump whenever `models::Program`'s shape changes in a way a plain
+/// `#[serde(default)]` can't express, and add a matching step to
+/// `PROGRAM_MIGRATIONS`.
+const CURRENT_PROGRAM_SCHEMA_VERSION: u32 = 1;
+
+type ProgramMigration = fn(toml::Value) -> toml::Value;
+
+const PROGRAM_MIGRATIONS: &[ProgramMigration] = &[
+    // v0 -> v1: same story as sessions — `schema_version` postdates the
+    // original program format, nothing else needs reshaping.
+    |value| value,
+];
+
+/// Walks `value` through `PROGRAM_MIGRATIONS` up to
+/// `CURRENT_PROGRAM_SCHEMA_VERSION`, defaulting an absent `schema_version`
+/// to 0 (pre-versioning program files).
+fn migrate_program_value(mut value: toml::Value) -> toml::Value {
+    let version = value
+        .get("schema_version")
+        .and_then(|v| v.as_integer())
+        .unwrap_or(0) as usize;
+    let version = version.min(PROGRAM_MIGRATIONS.len());
+
+    for step in &PROGRAM_MIGRATIONS[version..] {
+        value = step(value);
+    }
+
+    if let Some(table) = value.as_table_mut() {
+        table.insert(
+            "schema_version".to_string(),
+            toml::Value::Integer(CURRENT_PROGRAM_SCHEMA_VERSION as i64),
+        );
+    }
+
+    value
+}
+
 pub fn ensure_dirs() -> Result<()> {
     for dir in [PROGRAMS_DIR, SESSIONS_DIR] {
         if !Path::new(dir).exists() {
@@ -45,6 +123,7 @@ pub fn start_session(program_name: &str) -> Result<()> {
                 pr: None,
             })
             .collect(),
+        schema_version: CURRENT_SESSION_SCHEMA_VERSION,
     };
 
     save_session(&session)?;
@@ -52,12 +131,42 @@ pub fn start_session(program_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Closest program name to `input` among the `.toml` stems in
+/// `programs_dir()` (Jaro–Winkler, same thresholds as
+/// `types::best_muscle_suggestions`), for a "did you mean?" prompt when
+/// the exact file isn't found.
+fn suggest_program_name(input: &str) -> Option<String> {
+    let stems: Vec<String> = fs::read_dir(programs_dir())
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "toml"))
+        .filter_map(|path| path.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .collect();
+
+    crate::types::closest_match(input, stems.iter().map(|s| (s.clone(), s.as_str())))
+}
+
 fn load_program(program_name: &str) -> Result<models::Program> {
     let path = programs_dir().join(format!("{}.toml", program_name));
-    let content = fs::read_to_string(&path)
-        .with_context(|| format!("Program '{}' not found", program_name))?;
+    let content = fs::read_to_string(&path).or_else(|err| {
+        match suggest_program_name(program_name) {
+            Some(sug) => Err(anyhow!(
+                "Program '{}' not found -- did you mean '{}'?",
+                program_name,
+                sug
+            )),
+            None => Err(err).with_context(|| format!("Program '{}' not found", program_name)),
+        }
+    })?;
+
+    let value: toml::Value = toml::from_str(&content)
+        .with_context(|| format!("Invalid program file: {}", path.display()))?;
+    let value = migrate_program_value(value);
 
-    toml::from_str(&content).with_context(|| format!("Invalid program file: {}", path.display()))
+    value
+        .try_into()
+        .with_context(|| format!("Invalid program file: {}", path.display()))
 }
 
 fn save_session(session: &models::TrainingSession) -> Result<()> {
@@ -167,6 +276,80 @@ pub fn edit_set(
     Ok(())
 }
 
+/// Reverts a single set back to a clean state: if `last_session_sets` has an
+/// entry at `set_idx`, the set is repopulated from it (same weight/reps,
+/// no RPE/notes carried over); otherwise the set is removed entirely since
+/// there was nothing to revert to.
+pub fn reset_set(exercise_idx: usize, set_idx: usize) -> Result<()> {
+    let mut session = load_current_session()?;
+
+    let ex_index = exercise_idx
+        .checked_sub(1)
+        .ok_or_else(|| anyhow!("Exercise index must be ≥ 1"))?;
+
+    let set_index = set_idx
+        .checked_sub(1)
+        .ok_or_else(|| anyhow!("Set index must be ≥ 1"))?;
+
+    let exercise = session.exercises.get_mut(ex_index).unwrap();
+
+    if set_index >= exercise.sets.len() {
+        return Err(anyhow!("Set {} does not exist", set_idx));
+    }
+
+    match exercise.last_session_sets.get(set_index) {
+        Some(prev) => {
+            exercise.sets[set_index] = models::ExerciseSet {
+                timestamp: Local::now(),
+                weight: Some(prev.weight),
+                reps: prev.reps,
+                rpe: None,
+                notes: None,
+            };
+        }
+        None => {
+            exercise.sets.remove(set_index);
+        }
+    }
+
+    save_session(&session)?;
+    println!("✅ Reset set {}-{}", exercise_idx, set_idx);
+    Ok(())
+}
+
+/// Clears every logged set for one exercise, preserving `last_session_sets`
+/// and `pr` so the next set logged still has prior-performance context.
+pub fn reset_exercise(exercise_idx: usize) -> Result<()> {
+    let mut session = load_current_session()?;
+
+    let ex_index = exercise_idx
+        .checked_sub(1)
+        .ok_or_else(|| anyhow!("Exercise index must be ≥ 1"))?;
+
+    let exercise = session.exercises.get_mut(ex_index).unwrap();
+    exercise.sets.clear();
+
+    save_session(&session)?;
+    println!("✅ Reset exercise {}", exercise_idx);
+    Ok(())
+}
+
+/// Wipes every logged set across every exercise and clears `end_time`,
+/// putting the whole session back to the state it was in right after
+/// `start_session`.
+pub fn reset_session() -> Result<()> {
+    let mut session = load_current_session()?;
+
+    for exercise in &mut session.exercises {
+        exercise.sets.clear();
+    }
+    session.end_time = None;
+
+    save_session(&session)?;
+    println!("✅ Reset session {}", session.id);
+    Ok(())
+}
+
 pub fn finish_session() -> Result<()> {
     let mut session = load_current_session()?;
     session.end_time = Some(Local::now());
@@ -196,7 +379,11 @@ fn load_session(session_id: Option<&str>) -> Result<models::TrainingSession> {
     let content = fs::read_to_string(&path)
         .with_context(|| format!("Failed to read session file: {}", path.display()))?;
 
-    serde_json::from_str(&content)
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse session file: {}", path.display()))?;
+    let value = migrate_session_value(value);
+
+    serde_json::from_value(value)
         .with_context(|| format!("Failed to parse session file: {}", path.display()))
 }
 
@@ -228,4 +415,136 @@ fn load_current_session() -> Result<models::TrainingSession> {
     load_session(None)
 }
 
-// Implement other storage functions (load_session, edit_set, etc.) similarly...
+/* ───────────────────────────── backup archive ───────────────────────── */
+
+/// Bump whenever [`BackupArchive`]'s shape changes in a way old archives
+/// can't be read back from.
+const CURRENT_ARCHIVE_VERSION: u32 = 1;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BackupManifest {
+    exported_at: chrono::DateTime<Local>,
+    archive_version: u32,
+    session_count: usize,
+    program_count: usize,
+}
+
+/// A single self-describing JSON document bundling every session and
+/// program file, so a training log can be backed up or moved between
+/// machines without manually copying `sessions/` and `programs/` around.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BackupArchive {
+    manifest: BackupManifest,
+    sessions: Vec<models::TrainingSession>,
+    programs: Vec<models::Program>,
+}
+
+fn list_program_names() -> Result<Vec<String>> {
+    let dir = programs_dir();
+    let names = fs::read_dir(&dir)
+        .with_context(|| format!("Failed to read program directory: {}", dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "toml"))
+        .filter_map(|path| path.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .collect();
+
+    Ok(names)
+}
+
+/// Bundles every session in `sessions_dir()` and every program in
+/// `programs_dir()`, plus a manifest, into one JSON archive at `out_path`.
+pub fn export(out_path: &Path) -> Result<()> {
+    let sessions = get_sessions()?
+        .into_iter()
+        .map(|path| {
+            let id = path
+                .file_stem()
+                .ok_or_else(|| anyhow!("session file with no stem: {}", path.display()))?
+                .to_string_lossy()
+                .into_owned();
+            load_session(Some(&id))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let programs = list_program_names()?
+        .into_iter()
+        .map(|name| load_program(&name))
+        .collect::<Result<Vec<_>>>()?;
+
+    let manifest = BackupManifest {
+        exported_at: Local::now(),
+        archive_version: CURRENT_ARCHIVE_VERSION,
+        session_count: sessions.len(),
+        program_count: programs.len(),
+    };
+
+    let archive = BackupArchive { manifest, sessions, programs };
+    let content = serde_json::to_string_pretty(&archive)?;
+    fs::write(out_path, content)
+        .with_context(|| format!("Failed to write archive to {}", out_path.display()))?;
+
+    println!(
+        "✅ Exported {} session(s) and {} program(s) to {}",
+        archive.manifest.session_count,
+        archive.manifest.program_count,
+        out_path.display()
+    );
+    Ok(())
+}
+
+/// Restores every session and program from the archive at `archive_path`.
+/// Entries whose id/name already exists on disk are skipped unless `force`
+/// is set, in which case they're overwritten.
+pub fn import(archive_path: &Path, force: bool) -> Result<()> {
+    let content = fs::read_to_string(archive_path)
+        .with_context(|| format!("Failed to read archive: {}", archive_path.display()))?;
+    let archive: BackupArchive = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid archive file: {}", archive_path.display()))?;
+
+    if archive.manifest.archive_version > CURRENT_ARCHIVE_VERSION {
+        return Err(anyhow!(
+            "archive version {} is newer than this build supports (max {}); upgrade lazaro before importing",
+            archive.manifest.archive_version,
+            CURRENT_ARCHIVE_VERSION
+        ));
+    }
+
+    ensure_dirs()?;
+
+    let mut sessions_written = 0;
+    let mut sessions_skipped = 0;
+    for session in &archive.sessions {
+        let path = sessions_dir().join(format!("{}.json", session.id));
+        if path.exists() && !force {
+            sessions_skipped += 1;
+            continue;
+        }
+        save_session(session)?;
+        sessions_written += 1;
+    }
+
+    let mut programs_written = 0;
+    let mut programs_skipped = 0;
+    for program in &archive.programs {
+        let path = programs_dir().join(format!("{}.toml", program.name));
+        if path.exists() && !force {
+            programs_skipped += 1;
+            continue;
+        }
+        let content = toml::to_string_pretty(program)?;
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write program to {}", path.display()))?;
+        programs_written += 1;
+    }
+
+    println!(
+        "✅ Imported {} session(s) ({} skipped) and {} program(s) ({} skipped) from {}",
+        sessions_written,
+        sessions_skipped,
+        programs_written,
+        programs_skipped,
+        archive_path.display()
+    );
+    Ok(())
+}
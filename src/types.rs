@@ -51,6 +51,319 @@ impl Display for Muscle {
     }
 }
 
+/// A user's preferred unit for displaying weights. Storage stays kg-canonical;
+/// this only governs presentation and `--unit` overrides.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeightUnit {
+    Kg,
+    Lb,
+}
+
+impl WeightUnit {
+    pub const KG_PER_LB: f32 = 0.45359237;
+
+    /// Convert a canonical kg value into this unit.
+    pub fn from_kg(self, kg: f32) -> f32 {
+        match self {
+            WeightUnit::Kg => kg,
+            WeightUnit::Lb => kg / Self::KG_PER_LB,
+        }
+    }
+
+    /// Convert a value expressed in this unit back into canonical kg.
+    pub fn to_kg(self, value: f32) -> f32 {
+        match self {
+            WeightUnit::Kg => value,
+            WeightUnit::Lb => value * Self::KG_PER_LB,
+        }
+    }
+
+    pub fn suffix(self) -> &'static str {
+        match self {
+            WeightUnit::Kg => "kg",
+            WeightUnit::Lb => "lb",
+        }
+    }
+}
+
+/// A weight magnitude paired with the unit it was entered in, so CLI input
+/// like `session edit WEIGHT` can accept either unit without corrupting the
+/// canonical-kg values stored in the DB.
+#[derive(Clone, Copy, Debug)]
+pub struct Weight {
+    pub kg: f32,
+}
+
+impl Weight {
+    /// Parses `100`, `100kg`, or `225lb`, falling back to `default_unit`
+    /// when no suffix is present.
+    pub fn parse(input: &str, default_unit: WeightUnit) -> Option<Weight> {
+        let trimmed = input.trim();
+        let lower = trimmed.to_ascii_lowercase();
+
+        if let Some(num) = lower.strip_suffix("kg") {
+            return num.trim().parse::<f32>().ok().map(|v| Weight { kg: WeightUnit::Kg.to_kg(v) });
+        }
+        if let Some(num) = lower.strip_suffix("lb") {
+            return num.trim().parse::<f32>().ok().map(|v| Weight { kg: WeightUnit::Lb.to_kg(v) });
+        }
+
+        trimmed.parse::<f32>().ok().map(|v| Weight { kg: default_unit.to_kg(v) })
+    }
+
+    /// Renders this weight in `unit`.
+    pub fn display(self, unit: WeightUnit) -> f32 {
+        unit.from_kg(self.kg)
+    }
+}
+
+impl Display for WeightUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.suffix())
+    }
+}
+
+/// Which equation to use when estimating a 1-rep max from a logged set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OneRmFormula {
+    Epley,
+    Brzycki,
+    Lombardi,
+    Wathan,
+    /// `100w / (101.3 - 2.67123r)` — another high-rep-friendly model,
+    /// alongside Lombardi and Wathan.
+    Lander,
+    /// `w*(1 + reps/40)` — an Epley-shaped model with a flatter rep penalty.
+    #[value(name = "oconner")]
+    #[serde(rename = "oconner")]
+    OConner,
+    /// Mean of every fixed-constant formula's estimate, skipping any whose
+    /// denominator is non-positive for the given rep count.
+    Average,
+}
+
+impl OneRmFormula {
+    /// The fixed-constant formulas considered by [`OneRmFormula::Average`].
+    const AVERAGED: [OneRmFormula; 6] = [
+        OneRmFormula::Epley,
+        OneRmFormula::Brzycki,
+        OneRmFormula::Lombardi,
+        OneRmFormula::Wathan,
+        OneRmFormula::Lander,
+        OneRmFormula::OConner,
+    ];
+
+    /// Estimate a 1RM from a single logged set. `reps == 1` always returns the
+    /// raw weight and `reps == 0` always returns `0.0`, regardless of formula.
+    pub fn estimate(self, weight: f32, reps: i32) -> f32 {
+        if reps == 0 {
+            return 0.0;
+        }
+        if reps == 1 {
+            return weight;
+        }
+
+        let r = reps as f32;
+        match self {
+            OneRmFormula::Average => {
+                let estimates: Vec<f32> =
+                    Self::AVERAGED.iter().filter_map(|f| f.raw_estimate(weight, r)).collect();
+                if estimates.is_empty() {
+                    weight * (1.0 + r / 30.0)
+                } else {
+                    estimates.iter().sum::<f32>() / estimates.len() as f32
+                }
+            }
+            // Undefined (and wildly wrong) outside their valid rep range;
+            // fall back to Epley rather than return garbage.
+            _ => self.raw_estimate(weight, r).unwrap_or_else(|| weight * (1.0 + r / 30.0)),
+        }
+    }
+
+    /// This formula's raw estimate for `reps` already cast to `r`, or `None`
+    /// if its denominator is non-positive at this rep count (Brzycki past
+    /// r=37, Lander past r≈37.9). Used directly by [`OneRmFormula::Average`]
+    /// and indirectly (via a fallback) by [`OneRmFormula::estimate`].
+    fn raw_estimate(self, weight: f32, r: f32) -> Option<f32> {
+        match self {
+            OneRmFormula::Epley => Some(weight * (1.0 + r / 30.0)),
+            OneRmFormula::Brzycki => {
+                let denom = 37.0 - r;
+                (denom > 0.0).then(|| weight * 36.0 / denom)
+            }
+            OneRmFormula::Lombardi => Some(weight * r.powf(0.10)),
+            OneRmFormula::Wathan => Some(100.0 * weight / (48.8 + 53.4 * (-0.075 * r).exp())),
+            OneRmFormula::Lander => {
+                let denom = 101.3 - 2.67123 * r;
+                (denom > 0.0).then(|| 100.0 * weight / denom)
+            }
+            OneRmFormula::OConner => Some(weight * (1.0 + r / 40.0)),
+            OneRmFormula::Average => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            OneRmFormula::Epley => "epley",
+            OneRmFormula::Brzycki => "brzycki",
+            OneRmFormula::Lombardi => "lombardi",
+            OneRmFormula::Wathan => "wathan",
+            OneRmFormula::Lander => "lander",
+            OneRmFormula::OConner => "oconner",
+            OneRmFormula::Average => "average",
+        }
+    }
+}
+
+/// Lower/upper bound on the fitted Epley-style coefficient `k` in
+/// `1RM = weight * (1 + reps / k)` — keeps the search away from degenerate
+/// values while still covering Epley (30) and Brzycki-ish (~36) territory.
+const PERSONALIZED_K_MIN: f32 = 20.0;
+const PERSONALIZED_K_MAX: f32 = 40.0;
+
+/// Estimate a 1RM using a personalized Epley-style coefficient `k` fitted by
+/// [`fit_personalized_k`], rather than one of [`OneRmFormula`]'s fixed constants.
+pub fn estimate_with_k(weight: f32, reps: i32, k: f32) -> f32 {
+    if reps <= 1 {
+        return weight;
+    }
+    weight * (1.0 + reps as f32 / k)
+}
+
+/// Fit a per-user Epley-style coefficient `k` to a set of logged
+/// `(weight, reps)` pairs for one exercise via 1-D Nelder-Mead (downhill
+/// simplex) search.
+///
+/// The objective is the coefficient of variation of the estimated 1RMs a
+/// candidate `k` produces across all sets — a good `k` makes heavy low-rep
+/// and light high-rep sets agree on the same 1RM. Falls back to Epley's
+/// `k = 30` when there are too few sets or no rep spread to fit against.
+pub fn fit_personalized_k(sets: &[(f32, i32)]) -> f32 {
+    const EPLEY_K: f32 = 30.0;
+
+    if sets.len() < 5 {
+        return EPLEY_K;
+    }
+    let distinct_reps: HashSet<i32> = sets.iter().map(|(_, r)| *r).collect();
+    if distinct_reps.len() < 2 {
+        return EPLEY_K;
+    }
+
+    let objective = |k: f32| -> f32 {
+        let estimates: Vec<f32> = sets.iter().map(|(w, r)| estimate_with_k(*w, *r, k)).collect();
+        let mean = estimates.iter().sum::<f32>() / estimates.len() as f32;
+        if mean <= 0.0 {
+            return f32::MAX;
+        }
+        let variance = estimates.iter().map(|e| (e - mean).powi(2)).sum::<f32>() / estimates.len() as f32;
+        variance.sqrt() / mean
+    };
+
+    // Two-point simplex, reflection=1.0, expansion=2.0, contraction=0.5.
+    let mut simplex = [(20.0_f32, 0.0_f32), (40.0_f32, 0.0_f32)];
+    simplex[0].1 = objective(simplex[0].0);
+    simplex[1].1 = objective(simplex[1].0);
+
+    const MAX_ITERS: usize = 50;
+    const TOL: f32 = 1e-6;
+
+    for _ in 0..MAX_ITERS {
+        simplex.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        let (best_k, best_v) = simplex[0];
+        let (worst_k, worst_v) = simplex[1];
+
+        if (worst_k - best_k).abs() < TOL || (worst_v - best_v).abs() < TOL {
+            break;
+        }
+
+        let clamp = |k: f32| k.clamp(PERSONALIZED_K_MIN, PERSONALIZED_K_MAX);
+
+        let reflected_k = clamp(best_k + (best_k - worst_k));
+        let reflected_v = objective(reflected_k);
+
+        if reflected_v < best_v {
+            let expanded_k = clamp(best_k + 2.0 * (best_k - worst_k));
+            let expanded_v = objective(expanded_k);
+            simplex[1] = if expanded_v < reflected_v {
+                (expanded_k, expanded_v)
+            } else {
+                (reflected_k, reflected_v)
+            };
+        } else if reflected_v < worst_v {
+            simplex[1] = (reflected_k, reflected_v);
+        } else {
+            let contracted_k = clamp(worst_k + 0.5 * (best_k - worst_k));
+            let contracted_v = objective(contracted_k);
+            if contracted_v < worst_v {
+                simplex[1] = (contracted_k, contracted_v);
+            } else {
+                let shrunk_k = clamp(best_k + 0.5 * (worst_k - best_k));
+                simplex[1] = (shrunk_k, objective(shrunk_k));
+            }
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    simplex[0].0.clamp(PERSONALIZED_K_MIN, PERSONALIZED_K_MAX)
+}
+
+impl Display for OneRmFormula {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Whether dates are rendered as `DD-MM-YYYY` or as relative "N days ago"
+/// strings in text output. JSON output always carries the raw timestamp
+/// regardless of this preference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeFormat {
+    Absolute,
+    Relative,
+}
+
+/// Renders `date` (an RFC3339 timestamp or a bare `YYYY-MM-DD` day) per
+/// `format`. Falls back to the raw string if it doesn't parse — callers
+/// pass already-validated DB dates, so this is a defensive fallback, not
+/// the expected path.
+pub fn format_date(date: &str, format: TimeFormat) -> String {
+    use chrono::NaiveDate;
+
+    let naive_day = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .or_else(|| date.get(0..10).and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()));
+
+    let Some(day) = naive_day else {
+        return date.to_string();
+    };
+
+    match format {
+        TimeFormat::Absolute => day.format("%d-%m-%Y").to_string(),
+        TimeFormat::Relative => {
+            let today = chrono::Local::now().date_naive();
+            let days = (today - day).num_days();
+
+            if days < 0 {
+                day.format("%d-%m-%Y").to_string()
+            } else if days == 0 {
+                "today".to_string()
+            } else if days == 1 {
+                "1 day ago".to_string()
+            } else if days < 14 {
+                format!("{days} days ago")
+            } else if days < 60 {
+                format!("{} weeks ago", days / 7)
+            } else if days < 730 {
+                format!("{} months ago", days / 30)
+            } else {
+                format!("{} years ago", days / 365)
+            }
+        }
+    }
+}
+
 pub static ALLOWED_MUSCLES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     HashSet::from([
         "biceps",
@@ -67,8 +380,10 @@ pub static ALLOWED_MUSCLES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     ])
 });
 
-/// Returns the canonical lowercase muscle name or `None` if not allowed.
-pub fn cannonical_muscle<S: AsRef<str>>(m: S) -> Option<String> {
+/// Returns the canonical lowercase muscle name or `None` if not allowed by
+/// `allowed` (the union of `ALLOWED_MUSCLES` and any config-defined
+/// muscles — see [`Config::allowed_muscles`]).
+pub fn cannonical_muscle<S: AsRef<str>>(m: S, allowed: &HashSet<String>) -> Option<String> {
     let raw = m.as_ref();
     assert!(
         raw.chars().all(|c| !c.is_control()),
@@ -76,57 +391,66 @@ pub fn cannonical_muscle<S: AsRef<str>>(m: S) -> Option<String> {
     );
 
     let m = raw.to_ascii_lowercase();
-    if ALLOWED_MUSCLES.contains(m.as_str()) {
+    if allowed.contains(&m) {
         Some(m)
     } else {
         None
     }
 }
 
-/// Return the closest allowed muscle for `input`
-/// if similarity ≥ 0.85 *and* clearly better than the runner-up.
-/// Otherwise return `None` (no suggestion shown).
-pub fn best_muscle_suggestions(input: &str) -> Option<&'static str> {
-    assert!(
-        !ALLOWED_MUSCLES.is_empty(),
-        "ALLOWED_MUSCLES must contain at least one entry"
-    );
-
-    let inp = input.to_ascii_lowercase();
-    assert!(
-        !inp.trim().is_empty(),
-        "best_muscle_suggestions called with empty input"
-    ); // Sanity check.
+/// Scores every `(item, label)` pair against `input` with Jaro–Winkler and
+/// returns the top item if it's both a confident match and clearly better
+/// than the runner-up — otherwise `None` (no suggestion shown). Shared by
+/// every "did you mean?" prompt in the crate so the same thresholds apply
+/// whether the candidates are muscle names, program files, or exercises.
+pub fn closest_match<'a, T>(input: &str, candidates: impl Iterator<Item = (T, &'a str)>) -> Option<T> {
+    // Tune these two constants to taste.
+    const MIN_SCORE: f64 = 0.80;
+    const GAP: f64 = 0.02;
 
-    // Collect (muscle, score) pairs.
-    let mut scores: Vec<(&'static str, f64)> = ALLOWED_MUSCLES
-        .iter()
-        .copied()
-        .map(|m| (m, jaro_winkler(input, m)))
+    let mut scores: Vec<(T, f64)> = candidates
+        .map(|(item, label)| (item, jaro_winkler(input, label)))
         .collect();
+    if scores.is_empty() {
+        return None;
+    }
 
     // Highest score first.
     scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
 
-    let (best_muscle, best_score) = scores[0];
-    let second_score = scores.get(1).map(|(_, s)| *s).unwrap_or(0.0);
-
-    // Tune these two constants to taste.
-    const MIN_SCORE: f64 = 0.80;
-    const GAP: f64 = 0.02;
+    let best_score = scores[0].1;
+    let second_score = scores.get(1).map(|(_, s)| s).copied().unwrap_or(0.0);
 
     if best_score >= MIN_SCORE && best_score - second_score >= GAP {
-        Some(best_muscle)
+        Some(scores.remove(0).0)
     } else {
         None
     }
 }
 
+/// Return the closest muscle in `allowed` for `input`
+/// if similarity ≥ 0.80 *and* at least 0.02 better than the runner-up
+/// (via [`closest_match`]).
+/// Otherwise return `None` (no suggestion shown).
+pub fn best_muscle_suggestions(input: &str, allowed: &HashSet<String>) -> Option<String> {
+    assert!(!allowed.is_empty(), "allowed must contain at least one entry");
+
+    let inp = input.to_ascii_lowercase();
+    assert!(
+        !inp.trim().is_empty(),
+        "best_muscle_suggestions called with empty input"
+    ); // Sanity check.
+
+    closest_match(input, allowed.iter().map(|m| (m.clone(), m.as_str())))
+}
+
 #[derive(Deserialize)]
 pub struct ExerciseDef {
     pub name: String,
     pub description: Option<String>,
     pub primary_muscle: String,
+    #[serde(default)]
+    pub instructions: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -134,7 +458,7 @@ pub struct ExerciseImport {
     pub exercise: Vec<ExerciseDef>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Config {
     pub map: HashMap<String, String>,
 }
@@ -192,6 +516,11 @@ impl Config {
     pub fn validate_key(&self, key: &str) -> bool {
         match key {
             "json" => true,
+            "format" => true,
+            "weight_unit" => true,
+            "one_rm_formula" => true,
+            "time_format" => true,
+            _ if key.starts_with("muscles.") => true,
             _ if key.starts_with("aliases.") => {
                 let rest = match key.strip_prefix("aliases.") {
                     Some(r) => r,
@@ -221,29 +550,125 @@ impl Config {
         }
     }
 
+    /// The union of the built-in [`ALLOWED_MUSCLES`] and any muscles the
+    /// user added in config, via either `muscles.extra = a,b,c` or
+    /// individual `muscles.<name> = <display>` entries. Always lowercase.
+    pub fn allowed_muscles(&self) -> HashSet<String> {
+        let mut set: HashSet<String> = ALLOWED_MUSCLES.iter().map(|m| m.to_string()).collect();
+
+        for (k, v) in &self.map {
+            let Some(rest) = k.strip_prefix("muscles.") else { continue };
+
+            if rest == "extra" {
+                set.extend(
+                    v.split(',')
+                        .map(|m| m.trim().to_ascii_lowercase())
+                        .filter(|m| !m.is_empty()),
+                );
+            } else {
+                set.insert(rest.to_ascii_lowercase());
+            }
+        }
+
+        set
+    }
+
     pub fn json_default(&self) -> bool {
         matches!(self.map.get("json").map(|v| v.as_str()), Some("true" | "1"))
     }
+
+    /// The user's default output format from `format = pretty|json|csv|cbor`.
+    /// Falls back to [`json_default`](Self::json_default) so existing
+    /// `json = true` configs keep working, then to [`OutputFormat::Pretty`].
+    pub fn output_format(&self) -> OutputFormat {
+        match self.map.get("format").map(|v| v.as_str()) {
+            Some("json") => OutputFormat::Json,
+            Some("csv") => OutputFormat::Csv,
+            Some("cbor") => OutputFormat::Cbor,
+            Some("pretty") => OutputFormat::Pretty,
+            _ if self.json_default() => OutputFormat::Json,
+            _ => OutputFormat::Pretty,
+        }
+    }
+
+    /// The user's preferred weight unit, defaulting to kg when unset or unrecognized.
+    pub fn weight_unit(&self) -> WeightUnit {
+        match self.map.get("weight_unit").map(|v| v.as_str()) {
+            Some("lb") => WeightUnit::Lb,
+            _ => WeightUnit::Kg,
+        }
+    }
+
+    /// The user's preferred 1RM estimator, defaulting to Epley when unset or unrecognized.
+    pub fn one_rm_formula(&self) -> OneRmFormula {
+        match self.map.get("one_rm_formula").map(|v| v.as_str()) {
+            Some("brzycki") => OneRmFormula::Brzycki,
+            Some("lombardi") => OneRmFormula::Lombardi,
+            Some("wathan") => OneRmFormula::Wathan,
+            Some("lander") => OneRmFormula::Lander,
+            Some("oconner") => OneRmFormula::OConner,
+            Some("average") => OneRmFormula::Average,
+            _ => OneRmFormula::Epley,
+        }
+    }
+
+    /// The user's preferred date rendering, defaulting to absolute
+    /// `DD-MM-YYYY` when unset or unrecognized.
+    pub fn time_format(&self) -> TimeFormat {
+        match self.map.get("time_format").map(|v| v.as_str()) {
+            Some("relative") => TimeFormat::Relative,
+            _ => TimeFormat::Absolute,
+        }
+    }
+}
+
+/// The shape of output a command should produce.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    /// Colorful, human-oriented text (the default).
+    Pretty,
+    /// Machine-readable JSON.
+    Json,
+    /// Flat rows for spreadsheets/pandas. Only meaningful to commands that
+    /// know how to flatten their own data into a table; others fall back
+    /// to `Pretty`.
+    Csv,
+    /// Compact binary CBOR, for other tools (or a future sync daemon) to
+    /// parse without a JSON deserializer or a shared schema.
+    Cbor,
 }
 
 /// How the user wants to see stuff.
 #[derive(Clone, Copy)]
 pub struct OutputFmt {
-    pub json: bool,
+    pub format: OutputFormat,
 }
 
-/// Generic one-liner: if JSON is requested -> dump, else, run closure.
+/// Generic one-liner: dumps `value` as JSON or CBOR when requested, otherwise
+/// runs `pretty`. CSV has no generic shape for an arbitrary `Serialize`
+/// value, so `pretty` also runs for `OutputFormat::Csv`: commands that
+/// support CSV either pass their own CSV renderer as `pretty` or check
+/// `fmt.format == OutputFormat::Csv` themselves before calling this.
 pub fn emit<T, F>(fmt: OutputFmt, value: &T, pretty: F)
 where
     T: Serialize,
     F: FnOnce(),
 {
-    if fmt.json {
-        println!(
+    use std::io::Write;
+
+    match fmt.format {
+        OutputFormat::Json => println!(
             "{}",
             serde_json::to_string_pretty(value).expect("json serialize")
-        );
-    } else {
-        pretty();
+        ),
+        // CBOR is binary, so write it straight to stdout's raw bytes rather
+        // than through `println!`, which would mangle it as lossy UTF-8.
+        OutputFormat::Cbor => {
+            let mut stdout = std::io::stdout();
+            ciborium::into_writer(value, &mut stdout).expect("cbor serialize");
+            stdout.flush().expect("flush stdout");
+        }
+        OutputFormat::Pretty | OutputFormat::Csv => pretty(),
     }
 }